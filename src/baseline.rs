@@ -0,0 +1,194 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::Diagnostic;
+use crate::rules::RuleRegistry;
+
+const BASELINE_VERSION: u32 = 1;
+
+/// A snapshot of accepted diagnostics, written by `--write-baseline` and
+/// consumed by `--baseline`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// One recorded diagnostic. Entries are matched on rule, file and snippet hash,
+/// so a finding stays baselined when surrounding code moves it to another line.
+/// Project-level (line 0) and file-level findings have no snippet and match on
+/// rule and file alone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub rule: String,
+    /// Path relative to the project root, with `/` separators.
+    pub file: String,
+    /// FNV-1a hash of the whitespace-normalized source line.
+    pub snippet_hash: String,
+    pub message: String,
+}
+
+impl Baseline {
+    /// Record `diagnostics` against the sources under `project_root`.
+    pub fn from_diagnostics(project_root: &Path, diagnostics: &[Diagnostic]) -> Self {
+        let mut snippets = SnippetSource::new(project_root);
+        let mut entries: Vec<BaselineEntry> = diagnostics
+            .iter()
+            .map(|d| {
+                let (file, snippet_hash) = snippets.key(d);
+                BaselineEntry {
+                    rule: d.rule.clone(),
+                    file,
+                    snippet_hash,
+                    message: d.message.clone(),
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| {
+            (&a.file, &a.rule, &a.snippet_hash, &a.message).cmp(&(
+                &b.file,
+                &b.rule,
+                &b.snippet_hash,
+                &b.message,
+            ))
+        });
+        Baseline {
+            version: BASELINE_VERSION,
+            entries,
+        }
+    }
+
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read baseline `{}`: {e}", path.display()))?;
+        let baseline: Baseline = serde_json::from_str(&content)
+            .map_err(|e| format!("Invalid baseline `{}`: {e}", path.display()))?;
+        if baseline.version != BASELINE_VERSION {
+            return Err(format!(
+                "Invalid baseline `{}`: unsupported version {} (expected {BASELINE_VERSION})",
+                path.display(),
+                baseline.version
+            ));
+        }
+        Ok(baseline)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| format!("Failed to serialize baseline: {e}"))?;
+        std::fs::write(path, format!("{content}\n"))
+            .map_err(|e| format!("Failed to write baseline `{}`: {e}", path.display()))
+    }
+
+    /// Drop diagnostics recorded in the baseline and return how many were dropped.
+    ///
+    /// Each entry absorbs at most one diagnostic, so a second copy of a
+    /// baselined finding in the same file is still reported.
+    pub fn filter(&self, project_root: &Path, diagnostics: &mut Vec<Diagnostic>) -> usize {
+        let mut remaining: HashMap<(String, String, String), usize> = HashMap::new();
+        for entry in &self.entries {
+            *remaining
+                .entry((
+                    entry.rule.clone(),
+                    entry.file.clone(),
+                    entry.snippet_hash.clone(),
+                ))
+                .or_insert(0) += 1;
+        }
+
+        let mut snippets = SnippetSource::new(project_root);
+        let before = diagnostics.len();
+        diagnostics.retain(|d| {
+            let (file, snippet_hash) = snippets.key(d);
+            match remaining.get_mut(&(d.rule.clone(), file, snippet_hash)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            }
+        });
+        before - diagnostics.len()
+    }
+}
+
+/// Resolves diagnostic locations to baseline keys, caching file contents.
+struct SnippetSource {
+    project_root: PathBuf,
+    canonical_root: Option<PathBuf>,
+    lines: HashMap<String, Vec<String>>,
+    /// Rules whose 1:1 location does not point at a real source line.
+    file_level_rules: HashSet<&'static str>,
+}
+
+impl SnippetSource {
+    fn new(project_root: &Path) -> Self {
+        SnippetSource {
+            project_root: project_root.to_path_buf(),
+            canonical_root: project_root.canonicalize().ok(),
+            lines: HashMap::new(),
+            file_level_rules: RuleRegistry::new()
+                .rules()
+                .iter()
+                .filter(|rule| rule.file_level())
+                .map(|rule| rule.id())
+                .collect(),
+        }
+    }
+
+    fn key(&mut self, diagnostic: &Diagnostic) -> (String, String) {
+        let file = self.relative_path(&diagnostic.file);
+        let no_snippet =
+            diagnostic.line == 0 || self.file_level_rules.contains(diagnostic.rule.as_str());
+        let snippet = if no_snippet {
+            String::new()
+        } else {
+            let lines = self
+                .lines
+                .entry(diagnostic.file.clone())
+                .or_insert_with(|| {
+                    std::fs::read_to_string(&diagnostic.file)
+                        .map(|content| content.lines().map(normalize_snippet).collect())
+                        .unwrap_or_default()
+                });
+            lines
+                .get(diagnostic.line as usize - 1)
+                .cloned()
+                .unwrap_or_default()
+        };
+        (file, format!("{:016x}", fnv1a(snippet.as_bytes())))
+    }
+
+    fn relative_path(&self, file: &str) -> String {
+        let path = Path::new(file);
+        let relative = path
+            .strip_prefix(&self.project_root)
+            .ok()
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                let root = self.canonical_root.as_ref()?;
+                let canonical = path.canonicalize().ok()?;
+                canonical.strip_prefix(root).ok().map(Path::to_path_buf)
+            })
+            .unwrap_or_else(|| path.to_path_buf());
+        relative
+            .to_string_lossy()
+            .replace('\\', "/")
+            .trim_start_matches("./")
+            .to_string()
+    }
+}
+
+fn normalize_snippet(line: &str) -> String {
+    line.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// 64-bit FNV-1a. Used instead of `DefaultHasher` so baseline files and
+/// cache entries stay stable across Rust releases.
+pub(crate) fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
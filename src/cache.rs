@@ -0,0 +1,108 @@
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use serde::{Deserialize, Serialize};
+
+use crate::baseline::fnv1a;
+use crate::config::AuthConfig;
+use crate::rules::context::analyze_source_with_auth;
+use crate::rules::FileAnalysis;
+
+/// Cache location used by `--cache`, relative to the project root.
+pub const DEFAULT_CACHE_DIR: &str = "node_modules/.cache/convex-doctor";
+
+/// Per-file analyses persisted between runs.
+///
+/// Each source file has one entry, named by a hash of the convex-doctor
+/// version, the analysis-relevant config and the file path. The entry records
+/// a hash of the file content and is reused only while that hash matches, so
+/// an edited file overwrites its previous entry instead of adding another.
+pub struct AnalysisCache {
+    dir: PathBuf,
+    config_key: String,
+    hits: AtomicUsize,
+    misses: AtomicUsize,
+}
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry<A> {
+    content_hash: String,
+    analysis: A,
+}
+
+impl AnalysisCache {
+    /// A cache stored in `dir`, which is created on first write.
+    /// `config_key` is [`crate::config::Config::analysis_key`].
+    pub fn new(dir: &Path, config_key: &str) -> Self {
+        AnalysisCache {
+            dir: dir.to_path_buf(),
+            config_key: config_key.to_string(),
+            hits: AtomicUsize::new(0),
+            misses: AtomicUsize::new(0),
+        }
+    }
+
+    /// Analyze `path`, reusing the stored analysis when the file is unchanged.
+    ///
+    /// Parse failures are not cached. Cache read and write errors fall back
+    /// to a fresh analysis. `auth` must be the settings the cache's config
+    /// key was computed from.
+    pub fn analyze(&self, path: &Path, auth: &AuthConfig) -> Result<FileAnalysis, String> {
+        let source_text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+        let entry_path = self.entry_path(path);
+        let content_hash = format!("{:016x}", fnv1a(source_text.as_bytes()));
+
+        let cached = std::fs::read_to_string(&entry_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<CacheEntry<FileAnalysis>>(&contents).ok())
+            .filter(|entry| entry.content_hash == content_hash);
+        if let Some(entry) = cached {
+            self.hits.fetch_add(1, Ordering::Relaxed);
+            return Ok(entry.analysis);
+        }
+
+        self.misses.fetch_add(1, Ordering::Relaxed);
+        let analysis = analyze_source_with_auth(path, &source_text, auth)?;
+        let _ = self.store(
+            &entry_path,
+            &CacheEntry {
+                content_hash,
+                analysis: &analysis,
+            },
+        );
+        Ok(analysis)
+    }
+
+    /// Files served from the cache so far.
+    pub fn hits(&self) -> usize {
+        self.hits.load(Ordering::Relaxed)
+    }
+
+    /// Files analyzed because they had no valid entry.
+    pub fn misses(&self) -> usize {
+        self.misses.load(Ordering::Relaxed)
+    }
+
+    fn entry_path(&self, path: &Path) -> PathBuf {
+        let key = fnv1a(
+            format!(
+                "{}\0{}\0{}",
+                env!("CARGO_PKG_VERSION"),
+                self.config_key,
+                path.to_string_lossy()
+            )
+            .as_bytes(),
+        );
+        self.dir.join(format!("{key:016x}.json"))
+    }
+
+    fn store(&self, entry_path: &Path, entry: &CacheEntry<&FileAnalysis>) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let contents = serde_json::to_string(entry)?;
+        // Write then rename so concurrent runs never read a partial entry.
+        let tmp = entry_path.with_extension(format!("tmp{}", std::process::id()));
+        std::fs::write(&tmp, contents)?;
+        std::fs::rename(&tmp, entry_path)
+    }
+}
@@ -1,9 +1,26 @@
 use crate::diagnostic::{Diagnostic, Severity};
+use crate::rules::{FunctionKind, RuleRegistry, FILE_PARSE_ERROR_RULE, UNUSED_SUPPRESSION_RULE};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::Path;
 use std::sync::{Arc, Mutex, OnceLock};
 
+/// Values accepted for entries in the `[rules]` table.
+const RULE_SETTINGS: &[&str] = &["error", "warning", "info", "off"];
+
+/// Rules whose warnings `strictness = "low_noise"` hides.
+fn low_noise_hidden_rules() -> &'static HashSet<&'static str> {
+    static RULES: OnceLock<HashSet<&'static str>> = OnceLock::new();
+    RULES.get_or_init(|| {
+        RuleRegistry::new()
+            .rules()
+            .iter()
+            .filter(|rule| rule.low_noise_hidden())
+            .map(|rule| rule.id())
+            .collect()
+    })
+}
+
 static IGNORE_PATTERN_CACHE: OnceLock<Mutex<HashMap<String, Arc<Vec<glob::Pattern>>>>> =
     OnceLock::new();
 
@@ -14,6 +31,8 @@ pub struct Config {
     pub ignore: IgnoreConfig,
     pub ci: CiConfig,
     pub convex: ConvexConfig,
+    pub client: ClientConfig,
+    pub auth: AuthConfig,
 }
 
 #[derive(Debug, Clone, Deserialize)]
@@ -28,6 +47,41 @@ pub struct CiConfig {
     pub fail_below: u32,
 }
 
+/// Where client (React) code lives. Client files only get `client/*` rules.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ClientConfig {
+    /// Directories relative to the project root. When empty, roots are
+    /// detected from the project's framework.
+    pub roots: Vec<String>,
+}
+
+/// Project-specific auth helpers that analysis cannot recognize on its own.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct AuthConfig {
+    /// Functions that check auth when called with `ctx`, e.g. `getViewer`.
+    pub helpers: Vec<String>,
+    /// Function builders that authenticate the caller before the handler
+    /// runs, e.g. `authedQuery`. The name says which kind of function each
+    /// one builds.
+    pub wrappers: Vec<String>,
+}
+
+impl AuthConfig {
+    pub fn is_helper(&self, name: &str) -> bool {
+        self.helpers.iter().any(|helper| helper == name)
+    }
+
+    /// The kind of function built by `name` when it is a configured wrapper.
+    pub fn wrapper_kind(&self, name: &str) -> Option<FunctionKind> {
+        if !self.wrappers.iter().any(|wrapper| wrapper == name) {
+            return None;
+        }
+        FunctionKind::from_wrapper_name(name)
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, PartialEq, Eq, Default)]
 #[serde(rename_all = "snake_case")]
 pub enum StrictnessMode {
@@ -92,13 +146,88 @@ impl Config {
             .map_err(|e| format!("Failed to read config: {e}"))?;
         let config: Config =
             toml::from_str(&contents).map_err(|e| format!("Failed to parse config: {e}"))?;
+        config.validate_rules()?;
+        config.validate_auth()?;
         Ok(config)
     }
 
+    /// Reject `[rules]` entries that name unknown rules or use unknown settings.
+    pub fn validate_rules(&self) -> Result<(), String> {
+        let registry = RuleRegistry::new();
+        let mut known_ids: Vec<&str> = registry.rules().iter().map(|r| r.id()).collect();
+        known_ids.push(FILE_PARSE_ERROR_RULE);
+        known_ids.push(UNUSED_SUPPRESSION_RULE);
+        known_ids.sort_unstable();
+
+        let mut entries: Vec<(&String, &String)> = self.rules.iter().collect();
+        entries.sort();
+        for (rule_id, value) in entries {
+            if known_ids.binary_search(&rule_id.as_str()).is_err() {
+                return Err(format!(
+                    "Invalid config: unknown rule `{rule_id}` in [rules]. Valid rule ids:\n  {}",
+                    known_ids.join("\n  ")
+                ));
+            }
+            if !RULE_SETTINGS.contains(&value.as_str()) {
+                return Err(format!(
+                    "Invalid config: rule `{rule_id}` has unknown setting `{value}` in [rules]. Expected one of: {}",
+                    RULE_SETTINGS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Reject `[auth] wrappers` whose function kind cannot be told from the name.
+    pub fn validate_auth(&self) -> Result<(), String> {
+        for wrapper in &self.auth.wrappers {
+            if FunctionKind::from_wrapper_name(wrapper).is_none() {
+                return Err(format!(
+                    "Invalid config: cannot tell which kind of function `{wrapper}` in [auth] wrappers builds. Its name must contain query, mutation or action."
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Settings that change what per-file analysis extracts, folded into the
+    /// analysis cache key. Configs without `[auth]` settings share entries.
+    pub fn analysis_key(&self) -> String {
+        if self.auth.helpers.is_empty() && self.auth.wrappers.is_empty() {
+            return String::new();
+        }
+        format!(
+            "auth.helpers={:?};auth.wrappers={:?}",
+            self.auth.helpers, self.auth.wrappers
+        )
+    }
+
     pub fn is_rule_enabled(&self, rule_id: &str) -> bool {
         !matches!(self.rules.get(rule_id), Some(v) if v == "off")
     }
 
+    /// Severity explicitly configured for a rule in `[rules]`, if any.
+    pub fn severity_override(&self, rule_id: &str) -> Option<Severity> {
+        match self.rules.get(rule_id)?.as_str() {
+            "error" => Some(Severity::Error),
+            "warning" => Some(Severity::Warning),
+            "info" => Some(Severity::Info),
+            _ => None,
+        }
+    }
+
+    /// Rewrite diagnostic severities for rules with an explicit `[rules]` setting.
+    ///
+    /// Runs after `apply_strictness`, which leaves overridden rules untouched so
+    /// an explicit per-rule choice always wins over the strictness mode.
+    pub fn apply_severity_overrides(&self, diagnostics: &mut [Diagnostic]) {
+        for diagnostic in diagnostics.iter_mut() {
+            if let Some(severity) = self.severity_override(&diagnostic.rule) {
+                diagnostic.severity = severity;
+            }
+        }
+    }
+
     pub fn is_file_ignored(&self, project_root: &Path, file_path: &Path) -> bool {
         let absolute = file_path.to_string_lossy().replace('\\', "/");
         let relative = file_path
@@ -139,38 +268,23 @@ impl Config {
             StrictnessMode::Tiered => {}
             StrictnessMode::Strict => {
                 for diagnostic in diagnostics.iter_mut() {
-                    if diagnostic.severity == Severity::Info {
+                    if diagnostic.severity == Severity::Info
+                        && self.severity_override(&diagnostic.rule).is_none()
+                    {
                         diagnostic.severity = Severity::Warning;
                     }
                 }
             }
             StrictnessMode::LowNoise => {
-                const SUPPRESSED_WARNING_RULES: &[&str] = &[
-                    "security/missing-auth-check",
-                    "security/missing-return-validators",
-                    "perf/sequential-run-calls",
-                    "perf/helper-vs-run",
-                    "perf/action-from-client",
-                    "correctness/missing-unique",
-                    "correctness/replace-vs-patch",
-                    "schema/index-name-includes-fields",
-                    "schema/optional-field-no-default-handling",
-                    "schema/missing-search-index-filter",
-                    "arch/large-handler",
-                    "arch/monolithic-file",
-                    "arch/duplicated-auth",
-                    "arch/deep-function-chain",
-                    "client/unhandled-loading-state",
-                    "client/action-instead-of-mutation",
-                    "client/missing-convex-provider",
-                ];
-
                 diagnostics.retain(|diagnostic| {
+                    if self.severity_override(&diagnostic.rule).is_some() {
+                        return true;
+                    }
                     if diagnostic.severity == Severity::Info {
                         return false;
                     }
                     if diagnostic.severity == Severity::Warning
-                        && SUPPRESSED_WARNING_RULES.contains(&diagnostic.rule.as_str())
+                        && low_noise_hidden_rules().contains(diagnostic.rule.as_str())
                     {
                         return false;
                     }
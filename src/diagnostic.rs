@@ -11,6 +11,35 @@ pub struct Diagnostic {
     pub file: String,
     pub line: u32,
     pub column: u32,
+    /// Edits to `file` that resolve this diagnostic, applied by `--fix`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fix: Option<Vec<TextEdit>>,
+}
+
+/// Replace the byte range `start..end` of a file with `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize)]
+pub struct TextEdit {
+    pub start: u32,
+    pub end: u32,
+    pub replacement: String,
+}
+
+impl TextEdit {
+    pub fn insert(at: u32, text: impl Into<String>) -> Self {
+        TextEdit {
+            start: at,
+            end: at,
+            replacement: text.into(),
+        }
+    }
+
+    pub fn replace(start: u32, end: u32, text: impl Into<String>) -> Self {
+        TextEdit {
+            start,
+            end,
+            replacement: text.into(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
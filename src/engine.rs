@@ -1,14 +1,24 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 
 use rayon::prelude::*;
 
+use crate::baseline::Baseline;
+use crate::cache::{AnalysisCache, DEFAULT_CACHE_DIR};
 use crate::config::Config;
 use crate::diagnostic::{Category, Diagnostic, Severity};
 use crate::project::ProjectInfo;
-use crate::rules::context::analyze_file;
-use crate::rules::{ProjectContext, RuleRegistry};
+use crate::rules::auth_summary::resolve_auth_helpers;
+use crate::rules::context::analyze_file_with_auth;
+use crate::rules::function_graph::FunctionGraph;
+use crate::rules::schema_model::SchemaModel;
+use crate::rules::security::attach_internal_reference_fixes;
+use crate::rules::FileAnalysis;
+use crate::rules::{
+    FileScope, ProjectContext, RuleRegistry, FILE_PARSE_ERROR_RULE, UNUSED_SUPPRESSION_RULE,
+};
 use crate::scoring::{compute_score, ScoreResult};
+use crate::suppression::apply_suppressions;
 
 pub struct EngineResult {
     pub diagnostics: Vec<Diagnostic>,
@@ -16,61 +26,174 @@ pub struct EngineResult {
     pub project_name: String,
     pub files_scanned: usize,
     pub fail_below: u32,
+    /// Diagnostics hidden by `--baseline`; `None` when no baseline was given.
+    pub baseline_suppressed: Option<usize>,
 }
 
-pub fn run(path: &Path, _verbose: bool, diff_base: Option<&str>) -> Result<EngineResult, String> {
+/// Options controlling a single analysis run.
+#[derive(Debug, Clone, Default)]
+pub struct RunOptions {
+    pub verbose: bool,
+    /// Only analyze files changed vs this git base.
+    pub diff_base: Option<String>,
+    /// With `diff_base`, only report diagnostics on lines changed vs the base.
+    pub diff_lines: bool,
+    /// Report suppression directives that no longer suppress anything.
+    pub report_unused_suppressions: bool,
+    /// Hide diagnostics recorded in this baseline file.
+    pub baseline: Option<PathBuf>,
+    /// Reuse per-file analyses of unchanged files between runs.
+    pub cache: bool,
+    /// Cache location; defaults to [`DEFAULT_CACHE_DIR`] under the project root.
+    pub cache_dir: Option<PathBuf>,
+}
+
+pub fn run(path: &Path, verbose: bool, diff_base: Option<&str>) -> Result<EngineResult, String> {
+    run_with_options(
+        path,
+        &RunOptions {
+            verbose,
+            diff_base: diff_base.map(ToString::to_string),
+            ..RunOptions::default()
+        },
+    )
+}
+
+pub fn run_with_options(path: &Path, options: &RunOptions) -> Result<EngineResult, String> {
+    let diff_base = options.diff_base.as_deref();
     let project = ProjectInfo::detect(path)?;
     let config = Config::load(path)?;
-    let registry = RuleRegistry::new();
-    let files = project.discover_files(&config);
+    let baseline = options
+        .baseline
+        .as_deref()
+        .map(Baseline::load)
+        .transpose()?;
+    let files = discover_scoped_files(&project, &config);
 
     // If a diff base is provided, filter to only changed files
-    let files = if let Some(base) = diff_base {
-        match get_changed_files(path, base) {
-            Ok(changed_files) => {
-                let changed: HashSet<String> = changed_files
-                    .into_iter()
-                    .flat_map(|p| normalize_file_paths(&p, path))
-                    .collect();
-
-                files
+    let changed = diff_base.and_then(|base| {
+        let changed = if options.diff_lines {
+            get_changed_lines(path, base)
+        } else {
+            get_changed_files(path, base)
+                .map(|files| files.into_iter().map(|f| (f, ChangedLines::All)).collect())
+        };
+        match changed {
+            Ok(changed) => Some(
+                changed
                     .into_iter()
-                    .filter(|f| {
-                        normalize_file_paths(f, path)
+                    .flat_map(|(file, lines)| {
+                        normalize_file_paths(&file, path)
                             .into_iter()
-                            .any(|candidate| changed.contains(&candidate))
+                            .map(move |candidate| (candidate, lines.clone()))
                     })
-                    .collect()
-            }
+                    .collect::<HashMap<String, ChangedLines>>(),
+            ),
             Err(e) => {
                 eprintln!(
                     "Warning: failed to compute changed files for --diff {base}: {e}. Scanning all files."
                 );
-                files
+                None
             }
         }
-    } else {
-        files
+    });
+    let files = match &changed {
+        Some(changed) => files
+            .into_iter()
+            .filter(|(f, _)| {
+                normalize_file_paths(f, path)
+                    .iter()
+                    .any(|candidate| changed.contains_key(candidate))
+            })
+            .collect(),
+        None => files,
     };
 
     let files_scanned = files.len();
+    let cache = open_cache(path, options, &config);
+    let analyses = Analyses::analyze(&files, &config, cache.as_ref());
 
-    // Analyze all files in parallel
-    let analyzed_results: Vec<_> = files
-        .par_iter()
-        .map(|file| (file, analyze_file(file)))
-        .collect();
+    // Project-level checks are intentionally skipped in diff mode because
+    // they are global and not attributable to changed files.
+    let mut all_diagnostics = diagnose(
+        &project,
+        &config,
+        &analyses,
+        diff_base.is_none(),
+        options.report_unused_suppressions,
+    );
+    if let Some(changed) = changed.as_ref().filter(|_| options.diff_lines) {
+        retain_changed_lines(&mut all_diagnostics, &analyses, changed, path);
+    }
+
+    // Score and `fail_below` only consider findings not recorded in the baseline.
+    let baseline_suppressed = baseline.map(|b| b.filter(path, &mut all_diagnostics));
+
+    let score = compute_score(&all_diagnostics);
+
+    Ok(EngineResult {
+        diagnostics: all_diagnostics,
+        score,
+        project_name: project_name(path),
+        files_scanned,
+        fail_below: config.ci.fail_below,
+        baseline_suppressed,
+    })
+}
+
+/// Analyzed files of one project, split by which per-file rules apply.
+#[derive(Debug, Default)]
+pub struct Analyses {
+    pub server: Vec<FileAnalysis>,
+    pub client: Vec<FileAnalysis>,
+    /// `correctness/file-parse-error` findings for files that failed to parse.
+    pub parse_failures: Vec<Diagnostic>,
+}
 
-    let mut analyses = Vec::new();
-    let mut parse_diagnostics = Vec::new();
-    for (file, result) in analyzed_results {
+impl Analyses {
+    /// Analyze `files` in parallel, reusing `cache` entries when given.
+    pub fn analyze(
+        files: &[(PathBuf, FileScope)],
+        config: &Config,
+        cache: Option<&AnalysisCache>,
+    ) -> Self {
+        let analyzed_results: Vec<_> = files
+            .par_iter()
+            .map(|(file, scope)| {
+                let result = match cache {
+                    Some(cache) => cache.analyze(file, &config.auth),
+                    None => analyze_file_with_auth(file, &config.auth),
+                };
+                (file, *scope, result)
+            })
+            .collect();
+
+        let mut analyses = Analyses::default();
+        for (file, scope, result) in analyzed_results {
+            analyses.insert(file, scope, result, config);
+        }
+        analyses
+    }
+
+    /// Record the outcome of analyzing `file`, turning a failure into a
+    /// parse-error diagnostic when that rule is enabled.
+    pub fn insert(
+        &mut self,
+        file: &Path,
+        scope: FileScope,
+        result: Result<FileAnalysis, String>,
+        config: &Config,
+    ) {
         match result {
-            Ok(analysis) => analyses.push(analysis),
+            Ok(analysis) => match scope {
+                FileScope::Server => self.server.push(analysis),
+                FileScope::Client => self.client.push(analysis),
+            },
             Err(e) => {
                 eprintln!("Warning: {e}");
-                if config.is_rule_enabled("correctness/file-parse-error") {
-                    parse_diagnostics.push(Diagnostic {
-                        rule: "correctness/file-parse-error".to_string(),
+                if config.is_rule_enabled(FILE_PARSE_ERROR_RULE) {
+                    self.parse_failures.push(Diagnostic {
+                        rule: FILE_PARSE_ERROR_RULE.to_string(),
                         severity: Severity::Error,
                         category: Category::Correctness,
                         message: format!("Failed to parse file `{}`", file.display()),
@@ -79,25 +202,99 @@ pub fn run(path: &Path, _verbose: bool, diff_base: Option<&str>) -> Result<Engin
                         file: file.display().to_string(),
                         line: 0,
                         column: 0,
+                        fix: None,
                     });
                 }
             }
         }
     }
 
+    /// Add the files analyzed in `other`.
+    pub fn extend(&mut self, other: Analyses) {
+        self.server.extend(other.server);
+        self.client.extend(other.client);
+        self.parse_failures.extend(other.parse_failures);
+    }
+
+    /// Forget everything recorded for `file`.
+    pub fn remove(&mut self, file: &Path) {
+        self.server.retain(|a| Path::new(&a.file_path) != file);
+        self.client.retain(|a| Path::new(&a.file_path) != file);
+        self.parse_failures.retain(|d| Path::new(&d.file) != file);
+    }
+}
+
+/// Source files of the project with the per-file rules that apply to each.
+pub fn discover_scoped_files(project: &ProjectInfo, config: &Config) -> Vec<(PathBuf, FileScope)> {
+    project
+        .discover_files(config)
+        .into_iter()
+        .map(|file| (file, FileScope::Server))
+        .chain(
+            project
+                .discover_client_files(config)
+                .into_iter()
+                .map(|file| (file, FileScope::Client)),
+        )
+        .collect()
+}
+
+/// The analysis cache requested by `options`, if any.
+pub fn open_cache(path: &Path, options: &RunOptions, config: &Config) -> Option<AnalysisCache> {
+    options.cache.then(|| {
+        let dir = options
+            .cache_dir
+            .clone()
+            .unwrap_or_else(|| path.join(DEFAULT_CACHE_DIR));
+        AnalysisCache::new(&dir, &config.analysis_key())
+    })
+}
+
+/// Run per-file rules and, when `project_checks` is set, project-level rules
+/// over `analyses`, then apply suppressions, strictness and severity overrides.
+///
+/// Auth checks made through helper functions and imported builders are
+/// resolved on a copy of the server analyses, so `analyses` can be kept and
+/// diagnosed again after other files change.
+pub fn diagnose(
+    project: &ProjectInfo,
+    config: &Config,
+    analyses: &Analyses,
+    project_checks: bool,
+    report_unused_suppressions: bool,
+) -> Vec<Diagnostic> {
+    let path = project.root.as_path();
+    let registry = RuleRegistry::new();
+    let mut server = analyses.server.clone();
+    resolve_auth_helpers(&mut server, &config.auth);
+
     // Run per-file rules in parallel
-    let mut all_diagnostics: Vec<Diagnostic> = analyses
+    let enabled = |rule_id: &str| config.is_rule_enabled(rule_id);
+    let mut all_diagnostics: Vec<Diagnostic> = server
         .par_iter()
-        .flat_map(|analysis| registry.run(analysis, &|rule_id| config.is_rule_enabled(rule_id)))
+        .flat_map(|analysis| registry.run(analysis, FileScope::Server, &enabled))
+        .chain(
+            analyses
+                .client
+                .par_iter()
+                .flat_map(|analysis| registry.run(analysis, FileScope::Client, &enabled)),
+        )
         .collect();
-    all_diagnostics.extend(parse_diagnostics);
+    all_diagnostics.extend(analyses.parse_failures.iter().cloned());
 
-    // Project-level checks are intentionally skipped in diff mode because
-    // they are global and not attributable to changed files.
-    if diff_base.is_none() {
-        let uses_auth = analyses
+    let function_graph = FunctionGraph::build(
+        &project.convex_dir,
+        server.iter().chain(&analyses.client),
+        &project.module_files(),
+    );
+    attach_internal_reference_fixes(&mut all_diagnostics, &server, &function_graph);
+
+    if project_checks {
+        let server = &server;
+        let uses_auth = server
             .iter()
-            .any(|a| a.functions.iter().any(|f| f.has_auth_check));
+            .any(|a| a.functions.iter().any(|f| f.checks_auth()));
+        let convex_dir = project.convex_dir_display();
         let project_ctx = ProjectContext {
             has_schema: project.has_schema,
             has_auth_config: project.has_auth_config,
@@ -108,19 +305,30 @@ pub fn run(path: &Path, _verbose: bool, diff_base: Option<&str>) -> Result<Engin
             has_generated_dir: project.convex_dir.join("_generated").is_dir(),
             has_tsconfig: project.convex_dir.join("tsconfig.json").exists(),
             node_version_from_config: read_node_version_from_convex_json(path),
-            generated_files_modified: check_generated_files_modified(path),
-            all_index_definitions: analyses
+            generated_files_modified: check_generated_files_modified(path, &convex_dir),
+            all_index_definitions: server
                 .iter()
                 .flat_map(|a| a.index_definitions.clone())
                 .collect(),
-            all_schema_id_fields: analyses
+            all_schema_id_fields: server
                 .iter()
                 .flat_map(|a| a.schema_id_fields.clone())
                 .collect(),
-            all_filter_field_names: analyses
+            all_filter_field_names: server
                 .iter()
                 .flat_map(|a| a.filter_field_names.clone())
                 .collect(),
+            all_with_index_calls: server
+                .iter()
+                .flat_map(|a| a.with_index_calls.clone())
+                .collect(),
+            all_table_name_refs: server
+                .iter()
+                .flat_map(|a| a.table_name_refs.clone())
+                .collect(),
+            function_graph,
+            schema: SchemaModel::build(&project.convex_dir, server),
+            convex_dir,
         };
 
         let project_diagnostics: Vec<Diagnostic> = registry
@@ -133,24 +341,102 @@ pub fn run(path: &Path, _verbose: bool, diff_base: Option<&str>) -> Result<Engin
         all_diagnostics.extend(project_diagnostics);
     }
 
+    let directives_by_file: HashMap<String, _> = analyses
+        .server
+        .iter()
+        .chain(&analyses.client)
+        .map(|a| (a.file_path.clone(), a.suppressions.clone()))
+        .collect();
+    apply_suppressions(
+        &mut all_diagnostics,
+        &directives_by_file,
+        report_unused_suppressions && config.is_rule_enabled(UNUSED_SUPPRESSION_RULE),
+        &|rule_id| config.is_rule_enabled(rule_id),
+    );
+
     config.apply_strictness(&mut all_diagnostics);
+    config.apply_severity_overrides(&mut all_diagnostics);
+    all_diagnostics
+}
 
-    let score = compute_score(&all_diagnostics);
+/// Lines of a file that differ from the diff base, numbered as in the
+/// working tree.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangedLines {
+    /// A new or untracked file: every line counts as changed.
+    All,
+    /// Inclusive `(start, end)` ranges of added or modified lines. A hunk that
+    /// only deletes lines after line `n` is recorded as the empty range
+    /// `(n + 1, n)`, so it touches the code around it but no line.
+    Ranges(Vec<(u32, u32)>),
+}
+
+impl ChangedLines {
+    pub fn contains(&self, line: u32) -> bool {
+        match self {
+            ChangedLines::All => true,
+            ChangedLines::Ranges(ranges) => ranges
+                .iter()
+                .any(|&(start, end)| start <= line && line <= end),
+        }
+    }
+
+    /// Whether any change falls within lines `start..=end`, including
+    /// deletions between two of those lines.
+    pub fn overlaps(&self, start: u32, end: u32) -> bool {
+        match self {
+            ChangedLines::All => true,
+            ChangedLines::Ranges(ranges) => ranges
+                .iter()
+                .any(|&(first, last)| first <= end && last >= start),
+        }
+    }
+}
+
+/// Drop diagnostics outside the changed lines of their file.
+///
+/// A diagnostic reported on a function's definition line is about the whole
+/// function, so it is kept when any line of that function changed.
+/// File-level diagnostics (line 0) are kept for every changed file.
+fn retain_changed_lines(
+    diagnostics: &mut Vec<Diagnostic>,
+    analyses: &Analyses,
+    changed: &HashMap<String, ChangedLines>,
+    project_root: &Path,
+) {
+    let functions_by_file: HashMap<&str, _> = analyses
+        .server
+        .iter()
+        .chain(&analyses.client)
+        .map(|a| (a.file_path.as_str(), &a.functions))
+        .collect();
+    diagnostics.retain(|diagnostic| {
+        let Some(lines) = normalize_file_paths(Path::new(&diagnostic.file), project_root)
+            .iter()
+            .find_map(|candidate| changed.get(candidate))
+        else {
+            return false;
+        };
+        if diagnostic.line == 0 || lines.contains(diagnostic.line) {
+            return true;
+        }
+        functions_by_file
+            .get(diagnostic.file.as_str())
+            .is_some_and(|functions| {
+                functions.iter().any(|f| {
+                    f.span_line == diagnostic.line && lines.overlaps(f.span_line, f.end_line)
+                })
+            })
+    });
+}
 
-    let project_name = path
-        .canonicalize()
+/// Display name of the project at `path`: its directory name.
+pub fn project_name(path: &Path) -> String {
+    path.canonicalize()
         .ok()
         .and_then(|p| p.file_name().map(|n| n.to_string_lossy().to_string()))
         .or_else(|| path.file_name().map(|n| n.to_string_lossy().to_string()))
-        .unwrap_or_else(|| ".".to_string());
-
-    Ok(EngineResult {
-        diagnostics: all_diagnostics,
-        score,
-        project_name,
-        files_scanned,
-        fail_below: config.ci.fail_below,
-    })
+        .unwrap_or_else(|| ".".to_string())
 }
 
 fn check_gitignore_contains(root: &Path, pattern: &str) -> bool {
@@ -211,13 +497,11 @@ fn normalize_file_paths(path: &Path, project_root: &Path) -> HashSet<String> {
 }
 
 pub fn get_changed_files(root: &Path, base: &str) -> Result<Vec<PathBuf>, String> {
-    let mut changed_files: std::collections::HashSet<PathBuf> = get_git_paths(
-        root,
-        &["diff", "--name-only", "--diff-filter=ACMRTUXB", base],
-    )?
-    .into_iter()
-    .map(|p| root.join(p))
-    .collect();
+    let mut changed_files: std::collections::HashSet<PathBuf> =
+        get_git_paths(root, &changed_files_args(base))?
+            .into_iter()
+            .map(|p| root.join(p))
+            .collect();
 
     changed_files.extend(
         get_git_paths(root, &["ls-files", "--others", "--exclude-standard"])?
@@ -228,7 +512,114 @@ pub fn get_changed_files(root: &Path, base: &str) -> Result<Vec<PathBuf>, String
     Ok(changed_files.into_iter().collect())
 }
 
+/// Changed files vs `base`, with the lines changed in each, from the hunks of
+/// `git diff -U0`. Untracked files count as changed throughout.
+pub fn get_changed_lines(
+    root: &Path,
+    base: &str,
+) -> Result<HashMap<PathBuf, ChangedLines>, String> {
+    // Renames and mode changes have no hunks but are still changed files.
+    let mut changed: HashMap<PathBuf, ChangedLines> =
+        get_git_paths(root, &changed_files_args(base))?
+            .into_iter()
+            .map(|p| (root.join(p), ChangedLines::Ranges(Vec::new())))
+            .collect();
+
+    let diff = git_output(
+        root,
+        &[
+            "diff",
+            "-U0",
+            "--no-color",
+            "--no-ext-diff",
+            "--src-prefix=a/",
+            "--dst-prefix=b/",
+            "--relative",
+            "--diff-filter=ACMRTUXB",
+            base,
+        ],
+    )?;
+    for (file, ranges) in parse_diff_hunks(&diff) {
+        changed.insert(root.join(file), ChangedLines::Ranges(ranges));
+    }
+
+    changed.extend(
+        get_git_paths(root, &["ls-files", "--others", "--exclude-standard"])?
+            .into_iter()
+            .map(|p| (root.join(p), ChangedLines::All)),
+    );
+
+    Ok(changed)
+}
+
+fn changed_files_args(base: &str) -> [&str; 5] {
+    [
+        "diff",
+        "--name-only",
+        "--relative",
+        "--diff-filter=ACMRTUXB",
+        base,
+    ]
+}
+
+/// Added or modified line ranges per file in unified diff output, in
+/// new-file line numbers. See [`ChangedLines::Ranges`] for deletions.
+pub fn parse_diff_hunks(diff: &str) -> HashMap<String, Vec<(u32, u32)>> {
+    let mut hunks: HashMap<String, Vec<(u32, u32)>> = HashMap::new();
+    let mut current: Option<String> = None;
+    // Body lines left in the current hunk; they may look like headers.
+    let mut remaining = 0u32;
+    for line in diff.lines() {
+        if remaining > 0 {
+            // "\ No newline at end of file" is not counted in the hunk header.
+            if !line.starts_with('\\') {
+                remaining -= 1;
+            }
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("+++ ") {
+            // `/dev/null` for deleted files.
+            current = path.strip_prefix("b/").map(ToString::to_string);
+        } else if let Some(header) = line.strip_prefix("@@ ") {
+            let mut ranges = header.split_whitespace();
+            let (Some(old), Some(new)) = (
+                ranges.next().and_then(|r| r.strip_prefix('-')),
+                ranges.next().and_then(|r| r.strip_prefix('+')),
+            ) else {
+                continue;
+            };
+            let (_, old_count) = parse_hunk_range(old);
+            let (start, count) = parse_hunk_range(new);
+            remaining = old_count + count;
+            if let Some(file) = &current {
+                let range = if count == 0 {
+                    (start + 1, start)
+                } else {
+                    (start, start + count - 1)
+                };
+                hunks.entry(file.clone()).or_default().push(range);
+            }
+        }
+    }
+    hunks
+}
+
+/// `start[,count]` from a hunk header; the count defaults to 1.
+fn parse_hunk_range(range: &str) -> (u32, u32) {
+    let (start, count) = range.split_once(',').unwrap_or((range, "1"));
+    (start.parse().unwrap_or(0), count.parse().unwrap_or(0))
+}
+
 fn get_git_paths(root: &Path, args: &[&str]) -> Result<Vec<String>, String> {
+    Ok(git_output(root, args)?
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(ToString::to_string)
+        .collect())
+}
+
+fn git_output(root: &Path, args: &[&str]) -> Result<String, String> {
     let output = std::process::Command::new("git")
         .args(args)
         .current_dir(root)
@@ -245,12 +636,7 @@ fn get_git_paths(root: &Path, args: &[&str]) -> Result<Vec<String>, String> {
         });
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout)
-        .lines()
-        .map(str::trim)
-        .filter(|line| !line.is_empty())
-        .map(ToString::to_string)
-        .collect())
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
 
 fn read_node_version_from_convex_json(root: &Path) -> Option<String> {
@@ -264,9 +650,9 @@ fn read_node_version_from_convex_json(root: &Path) -> Option<String> {
         .map(|s| s.to_string())
 }
 
-fn check_generated_files_modified(root: &Path) -> bool {
+fn check_generated_files_modified(root: &Path, convex_dir: &str) -> bool {
     std::process::Command::new("git")
-        .args(["status", "--porcelain", "convex/_generated"])
+        .args(["status", "--porcelain", &format!("{convex_dir}/_generated")])
         .current_dir(root)
         .output()
         .ok()
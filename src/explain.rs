@@ -0,0 +1,180 @@
+use owo_colors::OwoColorize;
+
+use crate::diagnostic::{Category, Severity};
+use crate::rules::docs::{guidance_version, guideline, rule_doc};
+use crate::rules::{RuleMetadata, RuleRegistry};
+
+/// Full documentation for `rule_id`, as printed by `convex-doctor explain`.
+pub fn explain(rule_id: &str) -> Result<String, String> {
+    let rules = RuleRegistry::new().metadata();
+    let Some(rule) = rules.iter().find(|r| r.id == rule_id) else {
+        let suggestion = rules
+            .iter()
+            .find(|r| r.id.ends_with(&format!("/{rule_id}")))
+            .map(|r| format!(" Did you mean `{}`?", r.id))
+            .unwrap_or_default();
+        return Err(format!(
+            "Unknown rule `{rule_id}`.{suggestion} Run `convex-doctor rules` to list rules."
+        ));
+    };
+    let doc = rule_doc(rule.id).ok_or_else(|| format!("No documentation for `{rule_id}`"))?;
+
+    let mut out = String::new();
+    out.push_str(&format!("{}\n", rule.id.bold()));
+    out.push_str(&format!("{}\n\n", rule.description));
+    out.push_str(&format!(
+        "  Category:          {} (score weight {}x)\n",
+        rule.category,
+        rule.category.weight()
+    ));
+    out.push_str(&format!("  Default severity:  {}\n", rule.default_severity));
+    out.push_str(&format!(
+        "  Checks:            {}\n",
+        if rule.project_level {
+            "the whole project"
+        } else {
+            "each file"
+        }
+    ));
+    out.push_str(&format!(
+        "  Automatic fix:     {}\n",
+        if rule.fixable { "yes (`--fix`)" } else { "no" }
+    ));
+    out.push_str(&format!("  Docs:              {}\n", rule.docs_url));
+
+    section(&mut out, "Why");
+    out.push_str(&indent(rule.docs));
+    section(&mut out, "How to fix");
+    out.push_str(&indent(rule.help));
+    section(&mut out, "Bad");
+    for line in indent(&doc.bad).lines() {
+        out.push_str(&format!("{}\n", line.red()));
+    }
+    section(&mut out, "Good");
+    for line in indent(&doc.good).lines() {
+        out.push_str(&format!("{}\n", line.green()));
+    }
+
+    section(
+        &mut out,
+        &format!("Convex guidance ({})", guidance_version()),
+    );
+    let guidelines: Vec<_> = rule
+        .guidance_ids
+        .iter()
+        .filter_map(|id| guideline(id))
+        .collect();
+    if guidelines.is_empty() {
+        out.push_str("  No Convex guideline bullet maps to this rule.\n");
+    } else {
+        for guideline in guidelines {
+            out.push_str(&format!(
+                "  - {} ({}:{})\n",
+                guideline.summary(),
+                guideline.source,
+                guideline.source_line
+            ));
+        }
+    }
+
+    section(&mut out, "Configuration");
+    for option in config_options(rule) {
+        out.push_str(&format!("  - {option}\n"));
+    }
+    for option in &doc.options {
+        out.push_str(&format!("  - {option}\n"));
+    }
+    Ok(out)
+}
+
+/// One line per rule, grouped by category, optionally filtered.
+pub fn list_rules(category: Option<Category>, severity: Option<Severity>) -> String {
+    let rules: Vec<RuleMetadata> = RuleRegistry::new()
+        .metadata()
+        .into_iter()
+        .filter(|r| category.map_or(true, |c| r.category == c))
+        .filter(|r| severity.map_or(true, |s| r.default_severity == s))
+        .collect();
+    let width = rules.iter().map(|r| r.id.len()).max().unwrap_or(0);
+
+    let mut out = String::new();
+    let mut categories: Vec<Category> = Vec::new();
+    for rule in &rules {
+        if !categories.contains(&rule.category) {
+            categories.push(rule.category);
+        }
+    }
+    for category in categories {
+        let in_category: Vec<&RuleMetadata> =
+            rules.iter().filter(|r| r.category == category).collect();
+        out.push_str(&format!(
+            "{} ({})\n",
+            category.to_string().bold(),
+            in_category.len()
+        ));
+        for rule in in_category {
+            out.push_str(&format!(
+                "  {:<width$}  {:<7}  {}\n",
+                rule.id,
+                rule.default_severity.to_string(),
+                rule.description
+            ));
+        }
+        out.push('\n');
+    }
+    if rules.is_empty() {
+        out.push_str("No rules match the given filters.\n");
+    } else {
+        out.push_str(&format!(
+            "{} rules. Run `convex-doctor explain <rule-id>` for details.\n",
+            rules.len()
+        ));
+    }
+    out
+}
+
+/// `convex-doctor.toml` settings that change whether or how the rule reports.
+fn config_options(rule: &RuleMetadata) -> Vec<String> {
+    let mut options = vec![format!(
+        "`[rules] \"{}\" = \"off\"` disables it; \"error\", \"warning\" or \"info\" overrides its severity.",
+        rule.id
+    )];
+    match rule.default_severity {
+        Severity::Info => {
+            options.push("`[convex] strictness = \"strict\"` reports it as a warning.".to_string());
+            options.push("`[convex] strictness = \"low_noise\"` hides it.".to_string());
+        }
+        Severity::Warning if rule.low_noise_hidden => {
+            options.push("`[convex] strictness = \"low_noise\"` hides it.".to_string());
+        }
+        _ => {}
+    }
+    if rule.category == Category::ClientSide {
+        options.push("`[client] roots` sets which directories count as client code.".to_string());
+    }
+    if !rule.engine_emitted {
+        options.push("`[ignore] files` skips matching files.".to_string());
+        options.push(format!(
+            "`// convex-doctor-disable-next-line {}` suppresses it on the next line.",
+            rule.id
+        ));
+    }
+    options
+}
+
+fn section(out: &mut String, title: &str) {
+    out.push_str(&format!("\n{}\n", title.bold()));
+}
+
+fn indent(text: &str) -> String {
+    text.trim_matches('\n')
+        .lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("  {line}\n")
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,321 @@
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use oxc_allocator::Allocator;
+use oxc_parser::Parser;
+use oxc_span::SourceType;
+
+use crate::diagnostic::{Diagnostic, TextEdit};
+
+const DIFF_CONTEXT: usize = 3;
+
+/// The result of applying every available fix to one file.
+#[derive(Debug, Clone)]
+pub struct FileFix {
+    pub file: String,
+    pub original: String,
+    pub fixed: String,
+    /// Diagnostics whose edits were applied.
+    pub applied: usize,
+    /// Diagnostics whose edits overlapped an earlier fix or broke parsing.
+    pub skipped: usize,
+}
+
+impl FileFix {
+    pub fn diff(&self, label: &str) -> String {
+        unified_diff(
+            &format!("a/{label}"),
+            &format!("b/{label}"),
+            &self.original,
+            &self.fixed,
+        )
+    }
+}
+
+/// Compute fixed file contents for all diagnostics that carry edits.
+///
+/// A diagnostic's edits are applied together or not at all. Fixes that
+/// overlap an already accepted fix are skipped, and the result is re-parsed
+/// so any fix that introduces a parse error is backed out. Files are not
+/// written; see [`write_fixes`].
+pub fn plan_fixes(diagnostics: &[Diagnostic]) -> Vec<FileFix> {
+    let mut by_file: BTreeMap<&str, Vec<&[TextEdit]>> = BTreeMap::new();
+    for d in diagnostics {
+        if let Some(edits) = d.fix.as_deref().filter(|edits| !edits.is_empty()) {
+            by_file.entry(&d.file).or_default().push(edits);
+        }
+    }
+
+    by_file
+        .into_iter()
+        .filter_map(|(file, mut fixes)| {
+            fixes.sort_by_key(|edits| edits.iter().map(|e| (e.start, e.end)).min());
+            fix_file(file, &fixes)
+        })
+        .collect()
+}
+
+pub fn write_fixes(fixes: &[FileFix]) -> Result<(), String> {
+    for fix in fixes.iter().filter(|f| f.applied > 0) {
+        std::fs::write(&fix.file, &fix.fixed)
+            .map_err(|e| format!("Failed to write fixes to {}: {e}", fix.file))?;
+    }
+    Ok(())
+}
+
+fn fix_file(file: &str, fixes: &[&[TextEdit]]) -> Option<FileFix> {
+    let path = Path::new(file);
+    let original = std::fs::read_to_string(path).ok()?;
+    let source_type = SourceType::from_path(path).ok()?;
+    let baseline_errors = parse_error_count(&original, source_type)?;
+    let parses = |edits: &[TextEdit]| {
+        render(&original, edits)
+            .and_then(|text| parse_error_count(&text, source_type))
+            .is_some_and(|errors| errors <= baseline_errors)
+    };
+
+    // Fast path: take every non-conflicting fix and check the file once.
+    let mut accepted: Vec<TextEdit> = Vec::new();
+    let mut candidates: Vec<&[TextEdit]> = Vec::new();
+    for fix in fixes {
+        if let Some(edits) = merge(&accepted, fix) {
+            accepted = edits;
+            candidates.push(fix);
+        }
+    }
+
+    let mut applied = candidates.len();
+    if !parses(&accepted) {
+        // Re-apply one fix at a time, keeping only those that still parse.
+        accepted.clear();
+        applied = 0;
+        for fix in candidates {
+            if let Some(edits) = merge(&accepted, fix).filter(|edits| parses(edits)) {
+                accepted = edits;
+                applied += 1;
+            }
+        }
+    }
+
+    let fixed = render(&original, &accepted)?;
+    Some(FileFix {
+        file: file.to_string(),
+        original,
+        fixed,
+        applied,
+        skipped: fixes.len() - applied,
+    })
+}
+
+/// Add `fix` to `accepted`, or return `None` if any of its edits conflict.
+/// Edits identical to an accepted edit (e.g. a shared import) are applied once.
+fn merge(accepted: &[TextEdit], fix: &[TextEdit]) -> Option<Vec<TextEdit>> {
+    let mut merged = accepted.to_vec();
+    for edit in fix {
+        if merged.contains(edit) {
+            continue;
+        }
+        if edit.start > edit.end || merged.iter().any(|other| conflicts(edit, other)) {
+            return None;
+        }
+        merged.push(edit.clone());
+    }
+    Some(merged)
+}
+
+fn conflicts(a: &TextEdit, b: &TextEdit) -> bool {
+    match (a.start == a.end, b.start == b.end) {
+        (true, true) => a.start == b.start,
+        (true, false) => b.start < a.start && a.start < b.end,
+        (false, true) => a.start < b.start && b.start < a.end,
+        (false, false) => a.start < b.end && b.start < a.end,
+    }
+}
+
+fn render(source: &str, edits: &[TextEdit]) -> Option<String> {
+    let mut edits: Vec<&TextEdit> = edits.iter().collect();
+    edits.sort_by_key(|e| (e.start, e.end));
+
+    let mut out = String::with_capacity(source.len());
+    let mut cursor = 0usize;
+    for edit in edits {
+        let (start, end) = (edit.start as usize, edit.end as usize);
+        if start < cursor || !source.is_char_boundary(start) || !source.is_char_boundary(end) {
+            return None;
+        }
+        out.push_str(&source[cursor..start]);
+        out.push_str(&edit.replacement);
+        cursor = end;
+    }
+    out.push_str(source.get(cursor..)?);
+    Some(out)
+}
+
+fn parse_error_count(source: &str, source_type: SourceType) -> Option<usize> {
+    let allocator = Allocator::default();
+    let ret = Parser::new(&allocator, source, source_type).parse();
+    (!ret.panicked).then_some(ret.errors.len())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineOp {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Render a unified diff (three lines of context) between two texts.
+pub fn unified_diff(old_label: &str, new_label: &str, old: &str, new: &str) -> String {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let ops = diff_lines(&a, &b);
+    if ops.iter().all(|op| *op == LineOp::Equal) {
+        return String::new();
+    }
+
+    // Position in `a` and `b` before each op.
+    let mut positions = Vec::with_capacity(ops.len() + 1);
+    let (mut x, mut y) = (0usize, 0usize);
+    for op in &ops {
+        positions.push((x, y));
+        match op {
+            LineOp::Equal => {
+                x += 1;
+                y += 1;
+            }
+            LineOp::Delete => x += 1,
+            LineOp::Insert => y += 1,
+        }
+    }
+    positions.push((x, y));
+
+    let mut out = format!("--- {old_label}\n+++ {new_label}\n");
+    let mut i = 0;
+    while i < ops.len() {
+        if ops[i] == LineOp::Equal {
+            i += 1;
+            continue;
+        }
+        let start = i.saturating_sub(DIFF_CONTEXT);
+        let mut last_change = i;
+        let mut j = i;
+        while j < ops.len() {
+            if ops[j] != LineOp::Equal {
+                last_change = j;
+            } else if j - last_change > 2 * DIFF_CONTEXT {
+                break;
+            }
+            j += 1;
+        }
+        let end = (last_change + DIFF_CONTEXT + 1).min(ops.len());
+
+        let (old_start, new_start) = positions[start];
+        let (old_end, new_end) = positions[end];
+        let old_count = old_end - old_start;
+        let new_count = new_end - new_start;
+        out.push_str(&format!(
+            "@@ -{},{} +{},{} @@\n",
+            if old_count == 0 {
+                old_start
+            } else {
+                old_start + 1
+            },
+            old_count,
+            if new_count == 0 {
+                new_start
+            } else {
+                new_start + 1
+            },
+            new_count
+        ));
+        for (op, &(x, y)) in ops[start..end].iter().zip(&positions[start..end]) {
+            match op {
+                LineOp::Equal => out.push_str(&format!(" {}\n", a[x])),
+                LineOp::Delete => out.push_str(&format!("-{}\n", a[x])),
+                LineOp::Insert => out.push_str(&format!("+{}\n", b[y])),
+            }
+        }
+        i = end;
+    }
+    out
+}
+
+/// Myers diff over lines, after trimming the common prefix and suffix.
+fn diff_lines(a: &[&str], b: &[&str]) -> Vec<LineOp> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let mid_a = &a[prefix..a.len() - suffix];
+    let mid_b = &b[prefix..b.len() - suffix];
+
+    let mut ops = vec![LineOp::Equal; prefix];
+    ops.extend(myers(mid_a, mid_b));
+    ops.extend(std::iter::repeat(LineOp::Equal).take(suffix));
+    ops
+}
+
+fn myers(a: &[&str], b: &[&str]) -> Vec<LineOp> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = n + m;
+    if max == 0 {
+        return Vec::new();
+    }
+    let mut v = vec![0isize; 2 * max as usize + 2];
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+
+    'search: for d in 0..=max {
+        trace.push(v.clone());
+        for k in (-d..=d).step_by(2) {
+            let idx = (k + max) as usize;
+            let mut x = if k == -d || (k != d && v[idx - 1] < v[idx + 1]) {
+                v[idx + 1]
+            } else {
+                v[idx - 1] + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[idx] = x;
+            if x >= n && y >= m {
+                break 'search;
+            }
+        }
+    }
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, v) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let k = x - y;
+        let prev_k = if k == -d || (k != d && v[(k - 1 + max) as usize] < v[(k + 1 + max) as usize])
+        {
+            k + 1
+        } else {
+            k - 1
+        };
+        let prev_x = v[(prev_k + max) as usize];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            ops.push(LineOp::Equal);
+            x -= 1;
+            y -= 1;
+        }
+        if d > 0 {
+            if x == prev_x {
+                ops.push(LineOp::Insert);
+            } else {
+                ops.push(LineOp::Delete);
+            }
+        }
+        x = prev_x;
+        y = prev_y;
+    }
+    ops.reverse();
+    ops
+}
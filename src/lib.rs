@@ -1,7 +1,15 @@
+pub mod baseline;
+pub mod cache;
 pub mod config;
 pub mod diagnostic;
 pub mod engine;
+pub mod explain;
+pub mod fix;
+pub mod lsp;
 pub mod project;
 pub mod reporter;
 pub mod rules;
 pub mod scoring;
+pub mod suppression;
+pub mod watch;
+pub mod workspace;
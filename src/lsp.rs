@@ -0,0 +1,564 @@
+use std::collections::{BTreeMap, HashMap};
+use std::io::{BufRead, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use serde_json::{json, Value};
+
+use crate::config::Config;
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::engine::{diagnose, discover_scoped_files, Analyses};
+use crate::project::ProjectInfo;
+use crate::rules::context::{analyze_file_with_auth, analyze_source_with_auth};
+use crate::rules::docs::{guidance_version, guidelines_for, RULE_REFERENCE_URL};
+use crate::rules::{FileScope, RuleRegistry};
+use crate::watch::{config_stamps, Stamp};
+
+const METHOD_NOT_FOUND: i64 = -32601;
+const SERVER_NOT_INITIALIZED: i64 = -32002;
+
+/// Zero-based line and UTF-16 character, as LSP counts positions.
+type Position = (u32, u32);
+type Range = (Position, Position);
+
+/// Run a language server over `reader` and `writer` until the client sends
+/// `exit`.
+///
+/// Open documents are analyzed from their in-editor contents and every other
+/// project file from disk. Each open or change re-runs the project-level rules
+/// and republishes diagnostics for all open documents.
+pub fn serve(reader: impl BufRead, writer: impl Write) -> Result<(), String> {
+    let mut reader = reader;
+    let mut server = Server {
+        writer,
+        root: None,
+        project: None,
+        documents: BTreeMap::new(),
+        shutdown: false,
+    };
+    while let Some(message) = read_message(&mut reader)? {
+        if server.handle(message)? {
+            return if server.shutdown {
+                Ok(())
+            } else {
+                Err("Received exit before shutdown".to_string())
+            };
+        }
+    }
+    Err("Client closed the connection without exit".to_string())
+}
+
+struct Server<W: Write> {
+    writer: W,
+    root: Option<PathBuf>,
+    project: Option<Project>,
+    documents: BTreeMap<PathBuf, Document>,
+    shutdown: bool,
+}
+
+/// The analyzed project behind the workspace root.
+struct Project {
+    info: ProjectInfo,
+    config: Config,
+    scopes: HashMap<PathBuf, FileScope>,
+    analyses: Analyses,
+    config_stamps: Vec<Option<Stamp>>,
+}
+
+struct Document {
+    uri: String,
+    text: String,
+    /// Diagnostics last published for this document, with their LSP ranges.
+    diagnostics: Vec<(Diagnostic, Range)>,
+}
+
+impl<W: Write> Server<W> {
+    /// Handle one message, returning `true` on `exit`.
+    fn handle(&mut self, message: Value) -> Result<bool, String> {
+        let Some(method) = message.get("method").and_then(Value::as_str) else {
+            // Responses to requests we never send.
+            return Ok(false);
+        };
+        let id = message.get("id").cloned();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+
+        match (method, id) {
+            ("initialize", Some(id)) => {
+                self.root = Some(workspace_root(&params));
+                self.respond(id, initialize_result())?;
+            }
+            ("initialized", _) => self.load_project()?,
+            ("shutdown", Some(id)) => {
+                self.shutdown = true;
+                self.respond(id, Value::Null)?;
+            }
+            ("exit", _) => return Ok(true),
+            ("textDocument/didOpen", _) => {
+                let document = &params["textDocument"];
+                if let (Some(uri), Some(text)) =
+                    (document["uri"].as_str(), document["text"].as_str())
+                {
+                    self.update_document(uri, text.to_string())?;
+                }
+            }
+            ("textDocument/didChange", _) => {
+                // Full sync: the last change holds the whole document.
+                let text = params["contentChanges"]
+                    .as_array()
+                    .and_then(|changes| changes.last())
+                    .and_then(|change| change["text"].as_str());
+                if let (Some(uri), Some(text)) = (params["textDocument"]["uri"].as_str(), text) {
+                    self.update_document(uri, text.to_string())?;
+                }
+            }
+            ("textDocument/didClose", _) => {
+                if let Some(uri) = params["textDocument"]["uri"].as_str() {
+                    self.close_document(uri)?;
+                }
+            }
+            ("textDocument/didSave", _) => {
+                // Saves may fix a missing project or edit its config.
+                let root = self.root.clone().unwrap_or_default();
+                let stale = self
+                    .project
+                    .as_ref()
+                    .map_or(true, |p| p.config_stamps != config_stamps(&root));
+                if stale {
+                    self.load_project()?;
+                }
+            }
+            ("textDocument/hover", Some(id)) => {
+                let result = self.hover(&params);
+                self.respond(id, result)?;
+            }
+            ("textDocument/codeAction", Some(id)) => {
+                let result = self.code_actions(&params);
+                self.respond(id, result)?;
+            }
+            (_, Some(id)) if self.root.is_none() => {
+                self.respond_error(id, SERVER_NOT_INITIALIZED, "Server not initialized")?;
+            }
+            (method, Some(id)) if !method.starts_with("$/") => {
+                self.respond_error(id, METHOD_NOT_FOUND, &format!("Unknown method {method}"))?;
+            }
+            _ => {}
+        }
+        Ok(false)
+    }
+
+    /// Analyze the project from disk, overlaying open documents, and publish.
+    fn load_project(&mut self) -> Result<(), String> {
+        let Some(root) = self.root.clone() else {
+            return Ok(());
+        };
+        let loaded = ProjectInfo::detect(&root).and_then(|info| {
+            let config = Config::load(&root)?;
+            Ok((info, config))
+        });
+        let (info, config) = match loaded {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.project = None;
+                return self.notify(
+                    "window/showMessage",
+                    json!({ "type": 1, "message": format!("convex-doctor: {e}") }),
+                );
+            }
+        };
+
+        let scoped = discover_scoped_files(&info, &config);
+        let mut analyses = Analyses::analyze(&scoped, &config, None);
+        let scopes: HashMap<PathBuf, FileScope> = scoped.into_iter().collect();
+        for (path, document) in &self.documents {
+            if let Some(scope) = scopes.get(path) {
+                analyses.remove(path);
+                let result = analyze_source_with_auth(path, &document.text, &config.auth);
+                analyses.insert(path, *scope, result, &config);
+            }
+        }
+        self.project = Some(Project {
+            info,
+            config,
+            scopes,
+            analyses,
+            config_stamps: config_stamps(&root),
+        });
+        self.publish()
+    }
+
+    fn update_document(&mut self, uri: &str, text: String) -> Result<(), String> {
+        let Some(path) = uri_to_path(uri) else {
+            return Ok(());
+        };
+        if let Some(project) = &mut self.project {
+            if let Some(scope) = project.scopes.get(&path) {
+                project.analyses.remove(&path);
+                let result = analyze_source_with_auth(&path, &text, &project.config.auth);
+                project
+                    .analyses
+                    .insert(&path, *scope, result, &project.config);
+            }
+        }
+        self.documents.insert(
+            path,
+            Document {
+                uri: uri.to_string(),
+                text,
+                diagnostics: Vec::new(),
+            },
+        );
+        self.publish()
+    }
+
+    /// Forget the editor contents of `uri` and go back to the file on disk.
+    fn close_document(&mut self, uri: &str) -> Result<(), String> {
+        let Some(path) = uri_to_path(uri) else {
+            return Ok(());
+        };
+        if self.documents.remove(&path).is_none() {
+            return Ok(());
+        }
+        if let Some(project) = &mut self.project {
+            if let Some(scope) = project.scopes.get(&path) {
+                project.analyses.remove(&path);
+                if path.is_file() {
+                    let result = analyze_file_with_auth(&path, &project.config.auth);
+                    project
+                        .analyses
+                        .insert(&path, *scope, result, &project.config);
+                }
+            }
+        }
+        self.notify(
+            "textDocument/publishDiagnostics",
+            json!({ "uri": uri, "diagnostics": [] }),
+        )?;
+        self.publish()
+    }
+
+    /// Re-run all rules and publish diagnostics for every open document.
+    fn publish(&mut self) -> Result<(), String> {
+        let Some(project) = &self.project else {
+            return Ok(());
+        };
+        let diagnostics = diagnose(
+            &project.info,
+            &project.config,
+            &project.analyses,
+            true,
+            false,
+        );
+        let root = project.info.root.clone();
+
+        let mut notifications = Vec::new();
+        for (path, document) in &mut self.documents {
+            document.diagnostics = diagnostics
+                .iter()
+                .filter(|d| resolve(&root, &d.file) == *path)
+                .map(|d| (d.clone(), diagnostic_range(&document.text, d)))
+                .collect();
+            let lsp_diagnostics: Vec<Value> = document
+                .diagnostics
+                .iter()
+                .map(|(d, range)| lsp_diagnostic(d, *range))
+                .collect();
+            notifications.push(json!({ "uri": document.uri, "diagnostics": lsp_diagnostics }));
+        }
+        for params in notifications {
+            self.notify("textDocument/publishDiagnostics", params)?;
+        }
+        Ok(())
+    }
+
+    /// Rule help and a documentation link for diagnostics under the cursor.
+    fn hover(&self, params: &Value) -> Value {
+        let (Some(document), Some(position)) =
+            (self.document(params), lsp_position(&params["position"]))
+        else {
+            return Value::Null;
+        };
+        let hits: Vec<&(Diagnostic, Range)> = document
+            .diagnostics
+            .iter()
+            .filter(|(_, (start, end))| *start <= position && position <= *end)
+            .collect();
+        let Some((_, range)) = hits.first() else {
+            return Value::Null;
+        };
+        let value = hits
+            .iter()
+            .map(|(d, _)| {
+                format!(
+                    "**{}** ({})\n\n{}\n\n{}\n\n{}[Rule documentation]({})",
+                    d.rule,
+                    d.severity,
+                    d.message,
+                    d.help,
+                    guidance_section(&d.rule),
+                    docs_url(&d.rule)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join("\n\n---\n\n");
+        json!({
+            "contents": { "kind": "markdown", "value": value },
+            "range": lsp_range(*range),
+        })
+    }
+
+    /// Quick fixes for fixable diagnostics overlapping the requested range.
+    fn code_actions(&self, params: &Value) -> Value {
+        let (Some(document), Some(start), Some(end)) = (
+            self.document(params),
+            lsp_position(&params["range"]["start"]),
+            lsp_position(&params["range"]["end"]),
+        ) else {
+            return json!([]);
+        };
+        let actions: Vec<Value> = document
+            .diagnostics
+            .iter()
+            .filter(|(_, (d_start, d_end))| *d_start <= end && start <= *d_end)
+            .filter_map(|(d, range)| {
+                let edits: Vec<Value> = d
+                    .fix
+                    .as_deref()?
+                    .iter()
+                    .map(|edit| {
+                        json!({
+                            "range": lsp_range((
+                                offset_position(&document.text, edit.start as usize),
+                                offset_position(&document.text, edit.end as usize),
+                            )),
+                            "newText": edit.replacement,
+                        })
+                    })
+                    .collect();
+                Some(json!({
+                    "title": format!("Fix: {}", d.message),
+                    "kind": "quickfix",
+                    "diagnostics": [lsp_diagnostic(d, *range)],
+                    "isPreferred": true,
+                    "edit": { "changes": { document.uri.clone(): edits } },
+                }))
+            })
+            .collect();
+        Value::Array(actions)
+    }
+
+    fn document(&self, params: &Value) -> Option<&Document> {
+        let path = uri_to_path(params["textDocument"]["uri"].as_str()?)?;
+        self.documents.get(&path)
+    }
+
+    fn respond(&mut self, id: Value, result: Value) -> Result<(), String> {
+        write_message(
+            &mut self.writer,
+            &json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+        )
+    }
+
+    fn respond_error(&mut self, id: Value, code: i64, message: &str) -> Result<(), String> {
+        write_message(
+            &mut self.writer,
+            &json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } }),
+        )
+    }
+
+    fn notify(&mut self, method: &str, params: Value) -> Result<(), String> {
+        write_message(
+            &mut self.writer,
+            &json!({ "jsonrpc": "2.0", "method": method, "params": params }),
+        )
+    }
+}
+
+fn initialize_result() -> Value {
+    json!({
+        "capabilities": {
+            "textDocumentSync": { "openClose": true, "change": 1, "save": true },
+            "hoverProvider": true,
+            "codeActionProvider": { "codeActionKinds": ["quickfix"] },
+        },
+        "serverInfo": { "name": "convex-doctor", "version": env!("CARGO_PKG_VERSION") },
+    })
+}
+
+/// The project root named by `initialize`, or the current directory.
+fn workspace_root(params: &Value) -> PathBuf {
+    let root = params["rootUri"]
+        .as_str()
+        .and_then(uri_to_path)
+        .or_else(|| params["rootPath"].as_str().map(PathBuf::from))
+        .unwrap_or_else(|| PathBuf::from("."));
+    root.canonicalize().unwrap_or(root)
+}
+
+/// Read one `Content-Length` framed message, or `None` at end of input.
+pub fn read_message(reader: &mut impl BufRead) -> Result<Option<Value>, String> {
+    let mut content_length = None;
+    loop {
+        let mut line = String::new();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|e| format!("Failed to read LSP message: {e}"))?;
+        if read == 0 {
+            return Ok(None);
+        }
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = content_length.ok_or("LSP message without Content-Length")?;
+    let mut body = vec![0; length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|e| format!("Failed to read LSP message: {e}"))?;
+    serde_json::from_slice(&body)
+        .map(Some)
+        .map_err(|e| format!("Invalid LSP message: {e}"))
+}
+
+pub fn write_message(writer: &mut impl Write, message: &Value) -> Result<(), String> {
+    let body = message.to_string();
+    write!(writer, "Content-Length: {}\r\n\r\n{body}", body.len())
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("Failed to write LSP message: {e}"))
+}
+
+fn lsp_diagnostic(d: &Diagnostic, range: Range) -> Value {
+    let severity = match d.severity {
+        Severity::Error => 1,
+        Severity::Warning => 2,
+        Severity::Info => 3,
+    };
+    json!({
+        "range": lsp_range(range),
+        "severity": severity,
+        "code": d.rule,
+        "codeDescription": { "href": docs_url(&d.rule) },
+        "source": "convex-doctor",
+        "message": d.message,
+    })
+}
+
+/// The Convex guideline bullets `rule_id` enforces, with where each comes
+/// from, or nothing when no bullet maps to the rule.
+fn guidance_section(rule_id: &str) -> String {
+    let guidelines = guidelines_for(rule_id);
+    if guidelines.is_empty() {
+        return String::new();
+    }
+    let bullets: String = guidelines
+        .iter()
+        .map(|g| format!("- {} (`{}:{}`)\n", g.summary(), g.source, g.source_line))
+        .collect();
+    format!("Convex guidance ({}):\n\n{bullets}\n", guidance_version())
+}
+
+/// Documentation link for a rule id, falling back to the rule reference.
+fn docs_url(rule_id: &str) -> String {
+    static URLS: OnceLock<HashMap<&'static str, String>> = OnceLock::new();
+    URLS.get_or_init(|| {
+        RuleRegistry::new()
+            .metadata()
+            .into_iter()
+            .map(|rule| (rule.id, rule.docs_url))
+            .collect()
+    })
+    .get(rule_id)
+    .cloned()
+    .unwrap_or_else(|| RULE_REFERENCE_URL.to_string())
+}
+
+fn lsp_range((start, end): Range) -> Value {
+    json!({
+        "start": { "line": start.0, "character": start.1 },
+        "end": { "line": end.0, "character": end.1 },
+    })
+}
+
+fn lsp_position(value: &Value) -> Option<Position> {
+    Some((
+        value["line"].as_u64()? as u32,
+        value["character"].as_u64()? as u32,
+    ))
+}
+
+/// From the diagnostic's 1-based line and byte column to the end of that line.
+/// File-level diagnostics (line 0) cover the first line.
+fn diagnostic_range(text: &str, d: &Diagnostic) -> Range {
+    let line_start = text
+        .split_inclusive('\n')
+        .take(d.line.saturating_sub(1) as usize)
+        .map(str::len)
+        .sum::<usize>();
+    let line = text[line_start..].lines().next().unwrap_or_default();
+    let line_end = line_start + line.trim_end().len();
+    let start = (line_start + d.column.saturating_sub(1) as usize).min(line_end);
+    (
+        offset_position(text, start),
+        offset_position(text, line_end),
+    )
+}
+
+fn offset_position(text: &str, offset: usize) -> Position {
+    let mut offset = offset.min(text.len());
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    let before = &text[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    (
+        before.matches('\n').count() as u32,
+        before[line_start..].encode_utf16().count() as u32,
+    )
+}
+
+/// Diagnostic file paths are absolute for per-file rules and relative to the
+/// project root for project-level rules.
+fn resolve(root: &Path, file: &str) -> PathBuf {
+    let path = Path::new(file);
+    if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        root.join(path)
+    }
+}
+
+/// The local path of a `file://` URI, canonicalized when it exists.
+pub fn uri_to_path(uri: &str) -> Option<PathBuf> {
+    let encoded = uri.strip_prefix("file://")?;
+    let bytes = encoded.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes
+            .get(i + 1..i + 3)
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            }
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            }
+        }
+    }
+    let mut decoded = String::from_utf8(decoded).ok()?;
+    // `file:///C:/x` names `C:/x` on Windows.
+    if cfg!(windows) && decoded.get(2..3) == Some(":") {
+        decoded.remove(0);
+    }
+    let path = PathBuf::from(decoded);
+    Some(path.canonicalize().unwrap_or(path))
+}
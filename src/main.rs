@@ -1,32 +1,109 @@
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
-use clap::{Parser, ValueEnum};
+use clap::{Parser, Subcommand, ValueEnum};
 
+use convex_doctor::baseline::Baseline;
+use convex_doctor::diagnostic::{Category, Diagnostic, Severity};
+use convex_doctor::engine::RunOptions;
+use convex_doctor::fix::{plan_fixes, write_fixes};
 use convex_doctor::reporter::cli::CliReporter;
 use convex_doctor::reporter::json::JsonReporter;
+use convex_doctor::reporter::sarif::SarifReporter;
 use convex_doctor::reporter::Reporter;
+use convex_doctor::watch::WatchSession;
 
 #[derive(Clone, Debug, ValueEnum)]
 enum OutputFormat {
     Cli,
     Json,
+    Sarif,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum CategoryFilter {
+    Security,
+    #[value(alias = "perf")]
+    Performance,
+    Correctness,
+    Schema,
+    #[value(alias = "arch")]
+    Architecture,
+    #[value(alias = "config")]
+    Configuration,
+    #[value(alias = "client-side")]
+    Client,
+}
+
+impl From<CategoryFilter> for Category {
+    fn from(filter: CategoryFilter) -> Self {
+        match filter {
+            CategoryFilter::Security => Category::Security,
+            CategoryFilter::Performance => Category::Performance,
+            CategoryFilter::Correctness => Category::Correctness,
+            CategoryFilter::Schema => Category::Schema,
+            CategoryFilter::Architecture => Category::Architecture,
+            CategoryFilter::Configuration => Category::Configuration,
+            CategoryFilter::Client => Category::ClientSide,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+enum SeverityFilter {
+    Error,
+    Warning,
+    Info,
+}
+
+impl From<SeverityFilter> for Severity {
+    fn from(filter: SeverityFilter) -> Self {
+        match filter {
+            SeverityFilter::Error => Severity::Error,
+            SeverityFilter::Warning => Severity::Warning,
+            SeverityFilter::Info => Severity::Info,
+        }
+    }
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run a language server over stdio for editor diagnostics
+    Lsp,
+    /// Show a rule's rationale, examples, guidance and config options
+    Explain {
+        /// Rule id, e.g. perf/unbounded-collect
+        rule: String,
+    },
+    /// List rules with their default severity
+    Rules {
+        /// Only list rules in this category
+        #[arg(long, value_enum)]
+        category: Option<CategoryFilter>,
+        /// Only list rules with this default severity
+        #[arg(long, value_enum)]
+        severity: Option<SeverityFilter>,
+    },
 }
 
 #[derive(Parser)]
 #[command(
     name = "convex-doctor",
     version,
-    about = "Diagnose your Convex backend"
+    about = "Diagnose your Convex backend",
+    args_conflicts_with_subcommands = true
 )]
 struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+
     /// Path to the project root (defaults to current directory)
     #[arg(default_value = ".")]
     path: PathBuf,
 
-    /// Output format: cli, json
+    /// Output format: cli, json, sarif
     #[arg(long, value_enum, default_value_t = OutputFormat::Cli)]
     format: OutputFormat,
 
@@ -38,24 +115,136 @@ struct Cli {
     #[arg(long)]
     diff: Option<String>,
 
+    /// With --diff, only report findings on changed lines (or in changed functions)
+    #[arg(long, requires = "diff")]
+    diff_lines: bool,
+
     /// Show verbose output with file paths and line numbers
     #[arg(long, short)]
     verbose: bool,
+
+    /// Report suppression comments that no longer suppress any diagnostic
+    #[arg(long)]
+    report_unused_suppressions: bool,
+
+    /// Hide diagnostics recorded in this baseline file
+    #[arg(long, value_name = "FILE", conflicts_with = "write_baseline")]
+    baseline: Option<PathBuf>,
+
+    /// Record current diagnostics to this baseline file
+    #[arg(long, value_name = "FILE")]
+    write_baseline: Option<PathBuf>,
+
+    /// Apply automatic fixes, then report what remains
+    #[arg(long, conflicts_with = "fix_dry_run")]
+    fix: bool,
+
+    /// Print automatic fixes as a unified diff without writing files
+    #[arg(long)]
+    fix_dry_run: bool,
+
+    /// Reuse analyses of unchanged files from previous runs
+    #[arg(long)]
+    cache: bool,
+
+    /// Cache directory (implies --cache) [default: node_modules/.cache/convex-doctor]
+    #[arg(long, value_name = "DIR")]
+    cache_dir: Option<PathBuf>,
+
+    /// Keep running and report new and resolved findings as files change
+    #[arg(
+        long,
+        conflicts_with_all = ["diff", "write_baseline", "fix", "fix_dry_run", "score", "format", "workspace"]
+    )]
+    watch: bool,
+
+    /// How often --watch checks files for changes, in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 500, requires = "watch")]
+    poll_interval: u64,
+
+    /// Analyze every Convex project in a monorepo, each with its own config
+    #[arg(long, conflicts_with_all = ["baseline", "write_baseline"])]
+    workspace: bool,
 }
 
 fn main() {
     let cli = Cli::parse();
+    match &cli.command {
+        Some(Command::Lsp) => {
+            let stdin = io::stdin();
+            if let Err(e) = convex_doctor::lsp::serve(stdin.lock(), io::stdout()) {
+                eprintln!("Error: {e}");
+                process::exit(1);
+            }
+            return;
+        }
+        Some(Command::Explain { rule }) => {
+            match convex_doctor::explain::explain(rule) {
+                Ok(output) => print!("{output}"),
+                Err(e) => {
+                    eprintln!("Error: {e}");
+                    process::exit(1);
+                }
+            }
+            return;
+        }
+        Some(Command::Rules { category, severity }) => {
+            print!(
+                "{}",
+                convex_doctor::explain::list_rules(
+                    category.map(Category::from),
+                    severity.map(Severity::from)
+                )
+            );
+            return;
+        }
+        None => {}
+    }
 
     let start = Instant::now();
-    let result = match convex_doctor::engine::run(&cli.path, cli.verbose, cli.diff.as_deref()) {
+    let options = RunOptions {
+        verbose: cli.verbose,
+        diff_base: cli.diff.clone(),
+        diff_lines: cli.diff_lines,
+        report_unused_suppressions: cli.report_unused_suppressions,
+        baseline: cli.baseline.clone(),
+        cache: cli.cache || cli.cache_dir.is_some(),
+        cache_dir: cli.cache_dir.clone(),
+    };
+    if cli.watch {
+        run_watch(&cli, &options);
+    }
+    if cli.workspace {
+        run_workspace(&cli, &options, start);
+        return;
+    }
+    let run = || match convex_doctor::engine::run_with_options(&cli.path, &options) {
         Ok(r) => r,
         Err(e) => {
             eprintln!("Error: {e}");
             process::exit(1);
         }
     };
+    let mut result = run();
+
+    if (cli.fix || cli.fix_dry_run) && handle_fixes(&cli, &result.diagnostics) {
+        result = run();
+    }
     let elapsed = start.elapsed();
 
+    if let Some(baseline_path) = &cli.write_baseline {
+        let baseline = Baseline::from_diagnostics(&cli.path, &result.diagnostics);
+        if let Err(e) = baseline.save(baseline_path) {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+        eprintln!(
+            "Wrote {} diagnostics to baseline `{}`",
+            baseline.entries.len(),
+            baseline_path.display()
+        );
+    }
+
     if cli.score {
         let mut stdout = io::stdout();
         let output = convex_doctor::reporter::score_only(&result.score);
@@ -66,7 +255,19 @@ fn main() {
     } else {
         let output = match cli.format {
             OutputFormat::Json => {
-                let reporter = JsonReporter;
+                let reporter =
+                    JsonReporter::default().with_baseline_suppressed(result.baseline_suppressed);
+                reporter.format(
+                    &result.diagnostics,
+                    &result.score,
+                    &result.project_name,
+                    cli.verbose,
+                    result.files_scanned,
+                    elapsed,
+                )
+            }
+            OutputFormat::Sarif => {
+                let reporter = SarifReporter::new(&cli.path);
                 reporter.format(
                     &result.diagnostics,
                     &result.score,
@@ -77,7 +278,8 @@ fn main() {
                 )
             }
             OutputFormat::Cli => {
-                let reporter = CliReporter;
+                let reporter =
+                    CliReporter::default().with_baseline_suppressed(result.baseline_suppressed);
                 reporter.format(
                     &result.diagnostics,
                     &result.score,
@@ -95,7 +297,130 @@ fn main() {
         stdout.flush().expect("failed to flush report output");
     }
 
-    if result.fail_below > 0 && result.score.value < result.fail_below {
+    // Writing a baseline accepts the current findings, so it never fails the run.
+    if cli.write_baseline.is_none()
+        && result.fail_below > 0
+        && result.score.value < result.fail_below
+    {
         process::exit(1);
     }
 }
+
+fn run_watch(cli: &Cli, options: &RunOptions) -> ! {
+    let start = Instant::now();
+    let mut session = match WatchSession::start(&cli.path, options) {
+        Ok(session) => session,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+    let reporter = CliReporter::default();
+    let output = reporter.format(
+        session.diagnostics(),
+        session.score(),
+        &convex_doctor::engine::project_name(&cli.path),
+        cli.verbose,
+        session.files_scanned(),
+        start.elapsed(),
+    );
+    print!("{output}");
+    eprintln!("Watching for changes (Ctrl-C to stop)...");
+
+    let interval = Duration::from_millis(cli.poll_interval);
+    loop {
+        std::thread::sleep(interval);
+        match session.poll() {
+            Ok(Some(update)) => {
+                print!("{}", reporter.format_watch_update(&update, &cli.path));
+                io::stdout().flush().expect("failed to flush watch output");
+            }
+            Ok(None) => {}
+            Err(e) => eprintln!("Error: {e}"),
+        }
+    }
+}
+
+fn run_workspace(cli: &Cli, options: &RunOptions, start: Instant) {
+    let run = || match convex_doctor::workspace::run_workspace(&cli.path, options) {
+        Ok(r) => r,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+    let mut workspace = run();
+
+    if cli.fix || cli.fix_dry_run {
+        let diagnostics: Vec<Diagnostic> = workspace.diagnostics().cloned().collect();
+        if handle_fixes(cli, &diagnostics) {
+            workspace = run();
+        }
+    }
+    let elapsed = start.elapsed();
+
+    let output = if cli.score {
+        convex_doctor::reporter::score_only(&workspace.score)
+    } else {
+        match cli.format {
+            OutputFormat::Json => {
+                JsonReporter::default().format_workspace(&workspace, cli.verbose, elapsed)
+            }
+            OutputFormat::Sarif => {
+                SarifReporter::new(&cli.path).format_workspace(&workspace, cli.verbose, elapsed)
+            }
+            OutputFormat::Cli => {
+                CliReporter::default().format_workspace(&workspace, cli.verbose, elapsed)
+            }
+        }
+    };
+    let mut stdout = io::stdout();
+    stdout
+        .write_all(output.as_bytes())
+        .expect("failed to write report output");
+    stdout.flush().expect("failed to flush report output");
+
+    // Each project is held to its own `fail_below`.
+    if workspace.failing_projects().next().is_some() {
+        process::exit(1);
+    }
+}
+
+/// Apply `--fix`, or print `--fix-dry-run` output and exit. Returns `true`
+/// when files were rewritten and the analysis should run again.
+fn handle_fixes(cli: &Cli, diagnostics: &[Diagnostic]) -> bool {
+    let fixes = plan_fixes(diagnostics);
+    let applied: usize = fixes.iter().map(|f| f.applied).sum();
+    let skipped: usize = fixes.iter().map(|f| f.skipped).sum();
+    let files = fixes.iter().filter(|f| f.applied > 0).count();
+
+    if cli.fix_dry_run {
+        let diff: String = fixes
+            .iter()
+            .map(|f| f.diff(&relative_label(&f.file, &cli.path)))
+            .collect();
+        let mut stdout = io::stdout();
+        stdout
+            .write_all(diff.as_bytes())
+            .expect("failed to write fix diff");
+        stdout.flush().expect("failed to flush fix diff");
+        eprintln!("{applied} fixes available in {files} files ({skipped} skipped)");
+        process::exit(0);
+    }
+
+    if let Err(e) = write_fixes(&fixes) {
+        eprintln!("Error: {e}");
+        process::exit(1);
+    }
+    eprintln!("Applied {applied} fixes in {files} files ({skipped} skipped)");
+    applied > 0
+}
+
+/// Path of `file` relative to the project root, for diff headers.
+fn relative_label(file: &str, root: &Path) -> String {
+    let path = Path::new(file);
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
@@ -15,9 +15,13 @@ pub struct ProjectInfo {
 
 impl ProjectInfo {
     pub fn detect(root: &Path) -> Result<Self, String> {
-        let convex_dir = root.join("convex");
+        let convex_dir = functions_dir(root);
         if !convex_dir.is_dir() {
-            return Err(format!("No convex/ directory found in {}", root.display()));
+            return Err(format!(
+                "No {}/ directory found in {}",
+                relative_display(root, &convex_dir),
+                root.display()
+            ));
         }
 
         let has_schema = SCHEMA_FILENAMES
@@ -83,12 +87,72 @@ impl ProjectInfo {
 
     pub fn discover_files(&self, config: &Config) -> Vec<PathBuf> {
         let mut files = Vec::new();
-        Self::walk_dir(&self.root, &self.convex_dir, config, &mut files);
+        Self::walk_dir(
+            &self.convex_dir,
+            &|path| !config.is_file_ignored(&self.root, path),
+            &mut files,
+        );
         files.sort();
         files
     }
 
-    fn walk_dir(project_root: &Path, dir: &Path, config: &Config, files: &mut Vec<PathBuf>) {
+    /// The functions directory relative to the project root, e.g. `convex`.
+    pub fn convex_dir_display(&self) -> String {
+        relative_display(&self.root, &self.convex_dir)
+    }
+
+    /// Directories holding client code: `[client] roots` when configured,
+    /// otherwise the conventional locations for the detected framework.
+    pub fn client_roots(&self, config: &Config) -> Vec<PathBuf> {
+        let mut roots: Vec<PathBuf> = if config.client.roots.is_empty() {
+            let defaults: &[&str] = match self.framework.as_deref() {
+                Some("nextjs") => &["app", "pages", "components", "src"],
+                Some("vite") => &["src"],
+                Some("remix") => &["app"],
+                _ => &[],
+            };
+            defaults.iter().map(|dir| self.root.join(dir)).collect()
+        } else {
+            config
+                .client
+                .roots
+                .iter()
+                .map(|dir| self.root.join(dir.trim_start_matches("./")))
+                .collect()
+        };
+        roots.retain(|root| root.is_dir() && !root.starts_with(&self.convex_dir));
+        roots.sort();
+        roots.dedup();
+        roots
+    }
+
+    /// Source files under the client roots, excluding the functions directory
+    /// and `node_modules`.
+    pub fn discover_client_files(&self, config: &Config) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        for root in self.client_roots(config) {
+            Self::walk_dir(
+                &root,
+                &|path| {
+                    !path.starts_with(&self.convex_dir) && !config.is_file_ignored(&self.root, path)
+                },
+                &mut files,
+            );
+        }
+        files.sort();
+        files.dedup();
+        files
+    }
+
+    /// Every source file under the functions directory, including ignored ones.
+    pub fn module_files(&self) -> Vec<PathBuf> {
+        let mut files = Vec::new();
+        Self::walk_dir(&self.convex_dir, &|_| true, &mut files);
+        files.sort();
+        files
+    }
+
+    fn walk_dir(dir: &Path, include: &dyn Fn(&Path) -> bool, files: &mut Vec<PathBuf>) {
         let entries = match std::fs::read_dir(dir) {
             Ok(e) => e,
             Err(_) => return,
@@ -96,12 +160,15 @@ impl ProjectInfo {
         for entry in entries.flatten() {
             let path = entry.path();
             if path.is_dir() {
-                if path.file_name().is_some_and(|n| n == "_generated") {
+                if path
+                    .file_name()
+                    .is_some_and(|n| n == "_generated" || n == "node_modules")
+                {
                     continue;
                 }
-                Self::walk_dir(project_root, &path, config, files);
+                Self::walk_dir(&path, include, files);
             } else if let Some(ext) = path.extension() {
-                if is_supported_source_file(ext) && !config.is_file_ignored(project_root, &path) {
+                if is_supported_source_file(ext) && include(&path) {
                     files.push(path);
                 }
             }
@@ -109,6 +176,31 @@ impl ProjectInfo {
     }
 }
 
+/// The functions directory of the project at `root`: the `functions` path from
+/// `convex.json` when set, otherwise `convex/`.
+pub fn functions_dir(root: &Path) -> PathBuf {
+    std::fs::read_to_string(root.join("convex.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+        .and_then(|json| {
+            json.get("functions").and_then(|v| v.as_str()).map(|dir| {
+                dir.trim_start_matches("./")
+                    .trim_end_matches('/')
+                    .to_string()
+            })
+        })
+        .filter(|dir| !dir.is_empty())
+        .map(|dir| root.join(dir))
+        .unwrap_or_else(|| root.join("convex"))
+}
+
+fn relative_display(root: &Path, path: &Path) -> String {
+    path.strip_prefix(root)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
 fn is_supported_source_file(ext: &std::ffi::OsStr) -> bool {
     matches!(
         ext.to_str(),
@@ -1,16 +1,28 @@
 use std::collections::BTreeMap;
+use std::path::Path;
 use std::time::Duration;
 
 use owo_colors::OwoColorize;
 
 use crate::diagnostic::{Diagnostic, Severity};
 use crate::scoring::ScoreResult;
+use crate::watch::WatchUpdate;
+use crate::workspace::WorkspaceResult;
 
 use super::Reporter;
 
-pub struct CliReporter;
+#[derive(Default)]
+pub struct CliReporter {
+    baseline_suppressed: Option<usize>,
+}
 
 impl CliReporter {
+    /// Mention the number of diagnostics hidden by a baseline in the summary.
+    pub fn with_baseline_suppressed(mut self, suppressed: Option<usize>) -> Self {
+        self.baseline_suppressed = suppressed;
+        self
+    }
+
     fn face_art(score: u32) -> [&'static str; 5] {
         match score.min(100) {
             85..=100 => [
@@ -65,6 +77,70 @@ impl CliReporter {
         }
     }
 
+    /// One `--watch` update: the changed files, the score change and the
+    /// findings that appeared or went away.
+    pub fn format_watch_update(&self, update: &WatchUpdate, project_root: &Path) -> String {
+        let relative = |file: &str| {
+            let path = Path::new(file);
+            path.strip_prefix(project_root)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+        let mut out = String::new();
+
+        let files: Vec<String> = update
+            .changed_files
+            .iter()
+            .map(|f| relative(&f.to_string_lossy()))
+            .collect();
+        let before = update.previous_score.value;
+        let after = update.score.value;
+        let change = match after.cmp(&before) {
+            std::cmp::Ordering::Greater => format!("(+{})", after - before).green().to_string(),
+            std::cmp::Ordering::Less => format!("(-{})", before - after).red().to_string(),
+            std::cmp::Ordering::Equal => "(no change)".dimmed().to_string(),
+        };
+        out.push_str(&format!(
+            "\n  {} {}  score {} → {} {}\n",
+            "↻".cyan(),
+            files.join(", ").bold(),
+            before,
+            Self::colored_score(&update.score),
+            change
+        ));
+
+        let line = |marker: String, d: &Diagnostic| {
+            format!(
+                "   {} {} {} {}\n     {}\n",
+                marker,
+                Self::severity_icon(&d.severity),
+                d.message,
+                format!("{}:{}:{}", relative(&d.file), d.line, d.column).dimmed(),
+                d.rule.dimmed()
+            )
+        };
+        for d in &update.new {
+            out.push_str(&line("+".red().bold().to_string(), d));
+        }
+        for d in &update.resolved {
+            out.push_str(&line("−".green().bold().to_string(), d));
+        }
+        if update.new.is_empty() && update.resolved.is_empty() {
+            out.push_str(&format!("   {}\n", "No new or resolved findings".dimmed()));
+        }
+        out
+    }
+
+    fn colored_score(score: &ScoreResult) -> String {
+        let text = format!("{:>3} / 100  {}", score.value.min(100), score.label);
+        match score.value.min(100) {
+            85..=100 => text.green().to_string(),
+            70..=84 => text.yellow().to_string(),
+            _ => text.red().to_string(),
+        }
+    }
+
     fn severity_icon(severity: &Severity) -> String {
         match severity {
             Severity::Error => "✖".red().bold().to_string(),
@@ -171,6 +247,22 @@ impl Reporter for CliReporter {
             Self::format_duration(elapsed).dimmed()
         ));
 
+        if let Some(suppressed) = self.baseline_suppressed.filter(|&n| n > 0) {
+            out.push_str(&format!(
+                "  {}\n",
+                format!(
+                    "{} baselined {} hidden",
+                    suppressed,
+                    if suppressed == 1 {
+                        "finding"
+                    } else {
+                        "findings"
+                    }
+                )
+                .dimmed()
+            ));
+        }
+
         if diagnostics.is_empty() {
             out.push('\n');
             return out;
@@ -228,4 +320,61 @@ impl Reporter for CliReporter {
         out.push('\n');
         out
     }
+
+    fn format_workspace(
+        &self,
+        workspace: &WorkspaceResult,
+        verbose: bool,
+        elapsed: Duration,
+    ) -> String {
+        let mut out = String::new();
+        for project in &workspace.projects {
+            let result = &project.result;
+            let reporter =
+                CliReporter::default().with_baseline_suppressed(result.baseline_suppressed);
+            out.push_str(&reporter.format(
+                &result.diagnostics,
+                &result.score,
+                &project.name,
+                verbose,
+                result.files_scanned,
+                project.elapsed,
+            ));
+        }
+
+        let name_width = workspace
+            .projects
+            .iter()
+            .map(|p| p.name.len())
+            .chain(["Aggregate".len()])
+            .max()
+            .unwrap_or_default();
+        out.push_str(&format!(
+            "\n  {} {} {}\n",
+            "──".dimmed(),
+            "Workspace".bold(),
+            "─".repeat(43).dimmed()
+        ));
+        for project in &workspace.projects {
+            out.push_str(&format!(
+                "   {:<name_width$}  {}  {}\n",
+                project.name,
+                Self::colored_score(&project.result.score),
+                format!("{} files", project.result.files_scanned).dimmed()
+            ));
+        }
+        out.push_str(&format!(
+            "   {}  {}  {}\n\n",
+            format!("{:<name_width$}", "Aggregate").bold(),
+            Self::colored_score(&workspace.score),
+            format!(
+                "{} projects, {} files in {}",
+                workspace.projects.len(),
+                workspace.files_scanned,
+                Self::format_duration(elapsed)
+            )
+            .dimmed()
+        ));
+        out
+    }
 }
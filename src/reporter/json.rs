@@ -4,11 +4,24 @@ use serde::Serialize;
 use serde_json::json;
 
 use crate::diagnostic::{Diagnostic, Severity};
+use crate::rules::{RuleMetadata, RuleRegistry};
 use crate::scoring::ScoreResult;
+use crate::workspace::WorkspaceResult;
 
 use super::Reporter;
 
-pub struct JsonReporter;
+#[derive(Default)]
+pub struct JsonReporter {
+    baseline_suppressed: Option<usize>,
+}
+
+impl JsonReporter {
+    /// Include the number of diagnostics hidden by a baseline in the summary.
+    pub fn with_baseline_suppressed(mut self, suppressed: Option<usize>) -> Self {
+        self.baseline_suppressed = suppressed;
+        self
+    }
+}
 
 #[derive(Serialize)]
 struct JsonOutput<'a> {
@@ -16,6 +29,33 @@ struct JsonOutput<'a> {
     score: ScoreJson,
     summary: SummaryJson,
     diagnostics: &'a [Diagnostic],
+    rules: Vec<RuleMetadata>,
+}
+
+#[derive(Serialize)]
+struct WorkspaceJson<'a> {
+    version: &'static str,
+    score: ScoreJson,
+    summary: WorkspaceSummaryJson,
+    projects: Vec<ProjectJson<'a>>,
+    rules: Vec<RuleMetadata>,
+}
+
+#[derive(Serialize)]
+struct WorkspaceSummaryJson {
+    projects: usize,
+    errors: usize,
+    warnings: usize,
+    infos: usize,
+    files_scanned: usize,
+}
+
+#[derive(Serialize)]
+struct ProjectJson<'a> {
+    name: &'a str,
+    score: ScoreJson,
+    summary: SummaryJson,
+    diagnostics: &'a [Diagnostic],
 }
 
 #[derive(Serialize)]
@@ -30,6 +70,30 @@ struct SummaryJson {
     warnings: usize,
     infos: usize,
     files_scanned: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    baseline_suppressed: Option<usize>,
+}
+
+impl ScoreJson {
+    fn new(score: &ScoreResult) -> Self {
+        ScoreJson {
+            value: score.value,
+            label: score.label.to_string(),
+        }
+    }
+}
+
+/// Error, warning and info counts.
+fn severity_counts<'a>(diagnostics: impl IntoIterator<Item = &'a Diagnostic>) -> [usize; 3] {
+    let mut counts = [0; 3];
+    for d in diagnostics {
+        match d.severity {
+            Severity::Error => counts[0] += 1,
+            Severity::Warning => counts[1] += 1,
+            Severity::Info => counts[2] += 1,
+        }
+    }
+    counts
 }
 
 impl Reporter for JsonReporter {
@@ -42,31 +106,19 @@ impl Reporter for JsonReporter {
         files_scanned: usize,
         _elapsed: Duration,
     ) -> String {
-        let errors = diagnostics
-            .iter()
-            .filter(|d| d.severity == Severity::Error)
-            .count();
-        let warnings = diagnostics
-            .iter()
-            .filter(|d| d.severity == Severity::Warning)
-            .count();
-        let infos = diagnostics
-            .iter()
-            .filter(|d| d.severity == Severity::Info)
-            .count();
+        let [errors, warnings, infos] = severity_counts(diagnostics);
         let output = JsonOutput {
             version: env!("CARGO_PKG_VERSION"),
-            score: ScoreJson {
-                value: score.value,
-                label: score.label.to_string(),
-            },
+            score: ScoreJson::new(score),
             summary: SummaryJson {
                 errors,
                 warnings,
                 infos,
                 files_scanned,
+                baseline_suppressed: self.baseline_suppressed,
             },
             diagnostics,
+            rules: RuleRegistry::new().metadata(),
         };
         serde_json::to_string_pretty(&output).unwrap_or_else(|err| {
             serde_json::to_string_pretty(&json!({
@@ -82,10 +134,66 @@ impl Reporter for JsonReporter {
                     "warnings": warnings,
                     "infos": infos,
                     "files_scanned": files_scanned,
+                    "baseline_suppressed": self.baseline_suppressed,
                 },
                 "diagnostics": [],
             }))
             .unwrap_or_else(|_| "{}".to_string())
         })
     }
+
+    fn format_workspace(
+        &self,
+        workspace: &WorkspaceResult,
+        _verbose: bool,
+        _elapsed: Duration,
+    ) -> String {
+        let [errors, warnings, infos] = severity_counts(workspace.diagnostics());
+        let projects = workspace
+            .projects
+            .iter()
+            .map(|project| {
+                let result = &project.result;
+                let [errors, warnings, infos] = severity_counts(&result.diagnostics);
+                ProjectJson {
+                    name: &project.name,
+                    score: ScoreJson::new(&result.score),
+                    summary: SummaryJson {
+                        errors,
+                        warnings,
+                        infos,
+                        files_scanned: result.files_scanned,
+                        baseline_suppressed: result.baseline_suppressed,
+                    },
+                    diagnostics: &result.diagnostics,
+                }
+            })
+            .collect();
+        let output = WorkspaceJson {
+            version: env!("CARGO_PKG_VERSION"),
+            score: ScoreJson::new(&workspace.score),
+            summary: WorkspaceSummaryJson {
+                projects: workspace.projects.len(),
+                errors,
+                warnings,
+                infos,
+                files_scanned: workspace.files_scanned,
+            },
+            projects,
+            rules: RuleRegistry::new().metadata(),
+        };
+        serde_json::to_string_pretty(&output).unwrap_or_else(|err| {
+            serde_json::to_string_pretty(&json!({
+                "version": env!("CARGO_PKG_VERSION"),
+                "error": "serialization_failed",
+                "message": err.to_string(),
+                "score": {
+                    "value": workspace.score.value,
+                    "label": workspace.score.label,
+                },
+                "projects": [],
+            }))
+            .unwrap_or_else(|_| "{}".to_string())
+        })
+    }
 }
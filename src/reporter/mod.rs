@@ -1,10 +1,12 @@
 pub mod cli;
 pub mod json;
+pub mod sarif;
 
 use std::time::Duration;
 
 use crate::diagnostic::Diagnostic;
 use crate::scoring::ScoreResult;
+use crate::workspace::WorkspaceResult;
 
 pub trait Reporter {
     fn format(
@@ -16,6 +18,32 @@ pub trait Reporter {
         files_scanned: usize,
         elapsed: Duration,
     ) -> String;
+
+    /// Report a `--workspace` run: every project plus the aggregate score.
+    ///
+    /// By default each project is reported with [`Reporter::format`], one
+    /// after another.
+    fn format_workspace(
+        &self,
+        workspace: &WorkspaceResult,
+        verbose: bool,
+        _elapsed: Duration,
+    ) -> String {
+        workspace
+            .projects
+            .iter()
+            .map(|project| {
+                self.format(
+                    &project.result.diagnostics,
+                    &project.result.score,
+                    &project.name,
+                    verbose,
+                    project.result.files_scanned,
+                    project.elapsed,
+                )
+            })
+            .collect()
+    }
 }
 
 pub fn score_only(score: &ScoreResult) -> String {
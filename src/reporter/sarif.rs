@@ -0,0 +1,274 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::json;
+
+use crate::diagnostic::{Diagnostic, Severity};
+use crate::rules::RuleRegistry;
+use crate::scoring::ScoreResult;
+use crate::workspace::WorkspaceResult;
+
+use super::Reporter;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+
+/// Emits SARIF 2.1.0 for code-scanning dashboards.
+///
+/// Result locations are made relative to `project_root` so uploads resolve
+/// against the repository checkout.
+pub struct SarifReporter {
+    project_root: PathBuf,
+}
+
+impl SarifReporter {
+    pub fn new(project_root: &Path) -> Self {
+        SarifReporter {
+            project_root: project_root.to_path_buf(),
+        }
+    }
+
+    fn level(severity: Severity) -> &'static str {
+        match severity {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "note",
+        }
+    }
+
+    fn relative_uri(&self, file: &str) -> String {
+        let path = Path::new(file);
+        let relative = path
+            .strip_prefix(&self.project_root)
+            .ok()
+            .map(Path::to_path_buf)
+            .or_else(|| {
+                let root = self.project_root.canonicalize().ok()?;
+                let canonical = path.canonicalize().ok()?;
+                canonical.strip_prefix(root).ok().map(Path::to_path_buf)
+            })
+            .unwrap_or_else(|| path.to_path_buf());
+
+        let uri = relative.to_string_lossy().replace('\\', "/");
+        let uri = uri.trim_start_matches("./");
+        if uri.is_empty() {
+            ".".to_string()
+        } else {
+            uri.to_string()
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct SarifLog {
+    #[serde(rename = "$schema")]
+    schema: &'static str,
+    version: &'static str,
+    runs: Vec<SarifRun>,
+}
+
+#[derive(Serialize)]
+struct SarifRun {
+    tool: SarifTool,
+    results: Vec<SarifResult>,
+}
+
+#[derive(Serialize)]
+struct SarifTool {
+    driver: SarifDriver,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifDriver {
+    name: &'static str,
+    version: &'static str,
+    information_uri: &'static str,
+    rules: Vec<SarifRule>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRule {
+    id: String,
+    name: String,
+    short_description: SarifText,
+    full_description: SarifText,
+    help: SarifText,
+    help_uri: String,
+    default_configuration: SarifConfiguration,
+    properties: SarifRuleProperties,
+}
+
+#[derive(Serialize)]
+struct SarifText {
+    text: String,
+}
+
+#[derive(Serialize)]
+struct SarifConfiguration {
+    level: &'static str,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRuleProperties {
+    category: String,
+    tags: Vec<String>,
+    fixable: bool,
+    project_level: bool,
+    guidance_ids: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifResult {
+    rule_id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    rule_index: Option<usize>,
+    level: &'static str,
+    message: SarifText,
+    locations: Vec<SarifLocation>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifLocation {
+    physical_location: SarifPhysicalLocation,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifPhysicalLocation {
+    artifact_location: SarifArtifactLocation,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    region: Option<SarifRegion>,
+}
+
+#[derive(Serialize)]
+struct SarifArtifactLocation {
+    uri: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct SarifRegion {
+    start_line: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    start_column: Option<u32>,
+}
+
+impl Reporter for SarifReporter {
+    fn format(
+        &self,
+        diagnostics: &[Diagnostic],
+        _score: &ScoreResult,
+        _project_name: &str,
+        _verbose: bool,
+        _files_scanned: usize,
+        _elapsed: Duration,
+    ) -> String {
+        let rules: Vec<SarifRule> = RuleRegistry::new()
+            .metadata()
+            .into_iter()
+            .map(|rule| SarifRule {
+                id: rule.id.to_string(),
+                name: rule.id.to_string(),
+                short_description: SarifText {
+                    text: rule.description.to_string(),
+                },
+                full_description: SarifText {
+                    text: rule.docs.to_string(),
+                },
+                help: SarifText {
+                    text: rule.help.to_string(),
+                },
+                help_uri: rule.docs_url,
+                default_configuration: SarifConfiguration {
+                    level: Self::level(rule.default_severity),
+                },
+                properties: SarifRuleProperties {
+                    category: rule.category.to_string(),
+                    tags: vec![rule.category.to_string()],
+                    fixable: rule.fixable,
+                    project_level: rule.project_level,
+                    guidance_ids: rule.guidance_ids,
+                },
+            })
+            .collect();
+
+        let results: Vec<SarifResult> = diagnostics
+            .iter()
+            .map(|d| {
+                // Project-level diagnostics (line 0) point at a file or directory
+                // rather than a position, so they are reported without a region.
+                let region = (d.line > 0).then(|| SarifRegion {
+                    start_line: d.line,
+                    start_column: (d.column > 0).then_some(d.column),
+                });
+                SarifResult {
+                    rule_id: d.rule.clone(),
+                    rule_index: rules.iter().position(|r| r.id == d.rule),
+                    level: Self::level(d.severity),
+                    message: SarifText {
+                        text: d.message.clone(),
+                    },
+                    locations: vec![SarifLocation {
+                        physical_location: SarifPhysicalLocation {
+                            artifact_location: SarifArtifactLocation {
+                                uri: self.relative_uri(&d.file),
+                            },
+                            region,
+                        },
+                    }],
+                }
+            })
+            .collect();
+
+        let log = SarifLog {
+            schema: SARIF_SCHEMA,
+            version: SARIF_VERSION,
+            runs: vec![SarifRun {
+                tool: SarifTool {
+                    driver: SarifDriver {
+                        name: "convex-doctor",
+                        version: env!("CARGO_PKG_VERSION"),
+                        information_uri: env!("CARGO_PKG_HOMEPAGE"),
+                        rules,
+                    },
+                },
+                results,
+            }],
+        };
+
+        serde_json::to_string_pretty(&log).unwrap_or_else(|err| {
+            serde_json::to_string_pretty(&json!({
+                "$schema": SARIF_SCHEMA,
+                "version": SARIF_VERSION,
+                "runs": [],
+                "error": "serialization_failed",
+                "message": err.to_string(),
+            }))
+            .unwrap_or_else(|_| "{}".to_string())
+        })
+    }
+
+    fn format_workspace(
+        &self,
+        workspace: &WorkspaceResult,
+        verbose: bool,
+        elapsed: Duration,
+    ) -> String {
+        // A single run whose locations are relative to the workspace root.
+        let diagnostics: Vec<Diagnostic> = workspace.diagnostics().cloned().collect();
+        self.format(
+            &diagnostics,
+            &workspace.score,
+            "",
+            verbose,
+            workspace.files_scanned,
+            elapsed,
+        )
+    }
+}
@@ -58,6 +58,15 @@ impl Rule for LargeHandler {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Extract logic into helper functions. Keep handlers focused on validation, auth, and orchestration."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
 
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
@@ -66,16 +75,17 @@ impl Rule for LargeHandler {
             .filter(|f| f.handler_line_count > 50)
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Handler `{}` is {} lines long",
                     f.name, f.handler_line_count
                 ),
-                help: "Extract logic into helper functions. Keep handlers focused on validation, auth, and orchestration.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -90,21 +100,34 @@ impl Rule for MonolithicFile {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Split into smaller files organized by feature."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
 
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         if analysis.exported_function_count > 10 {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "File has {} exported functions",
                     analysis.exported_function_count
                 ),
-                help: "Split into smaller files organized by feature.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: 1,
                 column: 1,
+                fix: None,
             }]
         } else {
             vec![]
@@ -122,6 +145,18 @@ impl Rule for DuplicatedAuth {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Extract authentication logic into a shared helper function to avoid copy-pasting the same auth pattern."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let auth_function_count = analysis
             .functions
@@ -131,16 +166,17 @@ impl Rule for DuplicatedAuth {
         if auth_function_count >= 3 {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "{} functions contain inline auth checks",
                     auth_function_count
                 ),
-                help: "Extract authentication logic into a shared helper function to avoid copy-pasting the same auth pattern.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: 1,
                 column: 1,
+                fix: None,
             }]
         } else {
             vec![]
@@ -158,6 +194,12 @@ impl Rule for ActionWithoutScheduling {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "If the action fails, mutation writes are still committed. Consider `ctx.scheduler.runAfter(0, ...)` to decouple the action."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -170,13 +212,17 @@ impl Rule for ActionWithoutScheduling {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: format!("`ctx.runAction` called directly from mutation `{}`", c.chain),
-                help: "If the action fails, mutation writes are still committed. Consider `ctx.scheduler.runAfter(0, ...)` to decouple the action.".to_string(),
+                message: format!(
+                    "`ctx.runAction` called directly from mutation `{}`",
+                    c.chain
+                ),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -192,19 +238,26 @@ impl Rule for NoConvexError {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Generic errors are redacted to 'Server Error' in production. Use `throw new ConvexError(...)` to send structured error data to clients."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .throw_generic_errors
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`throw new Error(...)` in Convex handler".to_string(),
-                help: "Generic errors are redacted to 'Server Error' in production. Use `throw new ConvexError(...)` to send structured error data to clients.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -220,19 +273,29 @@ impl Rule for MixedFunctionTypes {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Mixing public and internal functions in the same file makes security auditing harder. Consider splitting into separate files."
+    }
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let has_public = analysis.functions.iter().any(|f| f.is_public());
         let has_internal = analysis.functions.iter().any(|f| !f.is_public());
         if has_public && has_internal {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "File exports both public and internal functions".to_string(),
-                help: "Mixing public and internal functions in the same file makes security auditing harder. Consider splitting into separate files.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: 1,
                 column: 1,
+                fix: None,
             }]
         } else {
             vec![]
@@ -250,6 +313,15 @@ impl Rule for NoHelperFunctions {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Extract shared business logic into unexported helper functions to improve readability and testability."
+    }
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let large_handler_count = analysis
             .functions
@@ -269,16 +341,17 @@ impl Rule for NoHelperFunctions {
         {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "{} handlers with >15 lines and no helper functions",
                     large_handler_count
                 ),
-                help: "Extract shared business logic into unexported helper functions to improve readability and testability.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: 1,
                 column: 1,
+                fix: None,
             }]
         } else {
             vec![]
@@ -296,6 +369,15 @@ impl Rule for DeepFunctionChain {
     fn category(&self) -> Category {
         Category::Architecture
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Each `ctx.runQuery`/`ctx.runMutation` is a separate transaction. Consider batching related operations into fewer mutations."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         use std::collections::HashMap;
 
@@ -332,17 +414,18 @@ impl Rule for DeepFunctionChain {
                 if calls.len() >= 4 {
                     vec![Diagnostic {
                         rule: self.id().to_string(),
-                        severity: Severity::Warning,
+                        severity: self.default_severity(),
                         category: self.category(),
                         message: format!(
                             "Action `{}` has {} ctx.run* calls — deep function chain",
                             function_name,
                             calls.len()
                         ),
-                        help: "Each `ctx.runQuery`/`ctx.runMutation` is a separate transaction. Consider batching related operations into fewer mutations.".to_string(),
+                        help: self.help().to_string(),
                         file: analysis.file_path.clone(),
                         line: calls[0].line,
                         column: calls[0].col,
+                        fix: None,
                     }]
                 } else {
                     Vec::new()
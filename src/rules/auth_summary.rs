@@ -0,0 +1,331 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::AuthConfig;
+
+use super::context::analyze_file_with_auth;
+use super::schema_model::{import_candidates, normalize, SchemaImport};
+use super::{FileAnalysis, FunctionKind};
+
+/// Nesting limit when following helper calls and imports.
+pub(crate) const MAX_RESOLVE_DEPTH: u32 = 16;
+
+/// A top-level function that is not a Convex function, such as
+/// `async function getViewer(ctx) { ... }` or `const loadMembership = ...`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HelperFunction {
+    /// Declared name; `default` for `export default function`.
+    pub name: String,
+    /// Reads `ctx.auth` (through any name for its first parameter), or calls
+    /// a helper whose name marks it as an auth check, such as `requireUser(ctx)`.
+    pub reads_auth: bool,
+    /// Callees as written, e.g. `getViewer` or `auth.requireMember`.
+    pub calls: Vec<String>,
+}
+
+/// `const userQuery = customQuery(query, customCtx(...))` from
+/// `convex-helpers`, or a plain alias of another builder such as
+/// `export const authed = userQuery`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CustomBuilder {
+    /// Local binding of the builder.
+    pub name: String,
+    /// The builder it customizes as written: `query`, `internalMutation`,
+    /// or another custom builder.
+    pub base: String,
+    /// Public kind of the `customQuery`/`customMutation`/`customAction` call;
+    /// `None` for aliases.
+    pub kind: Option<FunctionKind>,
+    /// The customization reads `ctx.auth` or calls a recognized auth helper.
+    pub reads_auth: bool,
+    /// Calls made by the customization, e.g. `getUser`.
+    pub calls: Vec<String>,
+}
+
+/// Set [`ConvexFunction::auth_helper`](super::ConvexFunction::auth_helper) on
+/// every function whose handler calls a local or imported helper that checks
+/// auth, directly or through further helpers.
+///
+/// Functions built with an imported custom builder also get the builder's
+/// kind, and count as checking auth when the builder's customization does.
+/// Calls to an imported function that turns out not to be a builder are
+/// dropped.
+///
+/// Helpers in files that are not part of `analyses` (ignored files, or files
+/// unchanged in `--diff` mode) are analyzed on demand with `auth`.
+pub fn resolve_auth_helpers(analyses: &mut [FileAnalysis], auth: &AuthConfig) {
+    let mut summaries = AuthSummaries {
+        auth,
+        files: analyses
+            .iter()
+            .map(|a| {
+                (
+                    normalize(Path::new(&a.file_path)),
+                    Some(FileHelpers::from_analysis(a)),
+                )
+            })
+            .collect(),
+        memo: HashMap::new(),
+        in_progress: HashSet::new(),
+    };
+
+    for analysis in analyses.iter_mut() {
+        let file = normalize(Path::new(&analysis.file_path));
+        let mut unresolved = Vec::new();
+        for function in &mut analysis.functions {
+            function.auth_helper = None;
+            if let Some(name) = function.imported_builder.clone() {
+                match summaries.builder(&file, &name, 0) {
+                    Some(builder) => {
+                        if let Some(kind) = builder.kind {
+                            for call in &mut analysis.ctx_calls {
+                                if call.enclosing_function_kind.is_some()
+                                    && (function.span_line..=function.end_line).contains(&call.line)
+                                {
+                                    call.enclosing_function_kind = Some(kind.clone());
+                                }
+                            }
+                            function.kind = kind;
+                        }
+                        if builder.checks_auth {
+                            function.auth_helper = Some(name);
+                        }
+                    }
+                    None if FunctionKind::from_wrapper_name(&name).is_none()
+                        && !analysis.custom_builders.iter().any(|b| b.base == name) =>
+                    {
+                        unresolved.push(function.name.clone());
+                    }
+                    None => {}
+                }
+            }
+            if function.auth_helper.is_none() {
+                function.auth_helper = function
+                    .helper_calls
+                    .iter()
+                    .find(|callee| summaries.callee_checks_auth(&file, callee, 0))
+                    .cloned();
+            }
+        }
+        if !unresolved.is_empty() {
+            let before = analysis.functions.len();
+            analysis
+                .functions
+                .retain(|f| f.imported_builder.is_none() || !unresolved.contains(&f.name));
+            let dropped = (before - analysis.functions.len()) as u32;
+            analysis.exported_function_count =
+                analysis.exported_function_count.saturating_sub(dropped);
+        }
+    }
+}
+
+/// What an imported custom builder resolves to.
+struct ResolvedBuilder {
+    /// `None` when only the builder's family is known.
+    kind: Option<FunctionKind>,
+    checks_auth: bool,
+}
+
+/// Helpers and imports of one file.
+struct FileHelpers {
+    helpers: Vec<HelperFunction>,
+    builders: Vec<CustomBuilder>,
+    imports: Vec<SchemaImport>,
+    reexports: Vec<SchemaImport>,
+}
+
+impl FileHelpers {
+    fn from_analysis(analysis: &FileAnalysis) -> Self {
+        Self {
+            helpers: analysis.helper_functions.clone(),
+            builders: analysis.custom_builders.clone(),
+            imports: analysis.schema_facts.imports.clone(),
+            reexports: analysis.schema_facts.reexports.clone(),
+        }
+    }
+}
+
+struct AuthSummaries<'a> {
+    auth: &'a AuthConfig,
+    /// `None` for imported files that could not be read or parsed.
+    files: HashMap<PathBuf, Option<FileHelpers>>,
+    memo: HashMap<(PathBuf, String), bool>,
+    /// Helpers being resolved, so recursive helpers terminate.
+    in_progress: HashSet<(PathBuf, String)>,
+}
+
+impl AuthSummaries<'_> {
+    /// Whether calling `callee` from `file` checks auth.
+    fn callee_checks_auth(&mut self, file: &Path, callee: &str, depth: u32) -> bool {
+        if depth > MAX_RESOLVE_DEPTH {
+            return false;
+        }
+        let Some(Some(facts)) = self.files.get(file) else {
+            return false;
+        };
+
+        if let Some((namespace, member)) = callee.split_once('.') {
+            let Some(source) = facts
+                .imports
+                .iter()
+                .find(|i| i.local == namespace && i.imported == "*")
+                .map(|i| i.source.clone())
+            else {
+                return false;
+            };
+            return match self.resolve_import(file, &source) {
+                Some(target) => self.helper_checks_auth(&target, member, depth + 1),
+                None => false,
+            };
+        }
+
+        if facts.helpers.iter().any(|h| h.name == callee) {
+            return self.helper_checks_auth(file, callee, depth + 1);
+        }
+        let Some((source, imported)) = facts
+            .imports
+            .iter()
+            .find(|i| i.local == callee && i.imported != "*")
+            .map(|i| (i.source.clone(), i.imported.clone()))
+        else {
+            return false;
+        };
+        match self.resolve_import(file, &source) {
+            Some(target) => self.helper_checks_auth(&target, &imported, depth + 1),
+            None => false,
+        }
+    }
+
+    /// Resolve the builder `name` as seen from `file`: an `[auth] wrappers`
+    /// entry, a custom builder, or an import or re-export of a builder.
+    fn builder(&mut self, file: &Path, name: &str, depth: u32) -> Option<ResolvedBuilder> {
+        if let Some(kind) = self.auth.wrapper_kind(name) {
+            return Some(ResolvedBuilder {
+                kind: Some(kind),
+                checks_auth: true,
+            });
+        }
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        let facts = match self.files.get(file) {
+            Some(Some(facts)) => facts,
+            _ => return None,
+        };
+        let mut bindings = facts.imports.iter().chain(&facts.reexports);
+
+        if let Some((namespace, member)) = name.split_once('.') {
+            let source = bindings
+                .find(|i| i.local == namespace && i.imported == "*")
+                .map(|i| i.source.clone())?;
+            return self.imported_builder(file, &source, member, depth);
+        }
+
+        if let Some(local) = facts.builders.iter().find(|b| b.name == name).cloned() {
+            let base = self.builder(file, &local.base, depth + 1);
+            let checks_auth = local.reads_auth
+                || base.as_ref().is_some_and(|b| b.checks_auth)
+                || local
+                    .calls
+                    .iter()
+                    .any(|callee| self.callee_checks_auth(file, callee, depth + 1));
+            return Some(ResolvedBuilder {
+                kind: base.and_then(|b| b.kind).or(local.kind),
+                checks_auth,
+            });
+        }
+
+        if let Some((source, imported)) = bindings
+            .find(|i| i.local == name && i.imported != "*")
+            .map(|i| (i.source.clone(), i.imported.clone()))
+        {
+            return self.imported_builder(file, &source, &imported, depth);
+        }
+
+        // An unimported `query` is Convex's, unless the file declares its own.
+        let declared = facts.helpers.iter().any(|h| h.name == name);
+        let star_sources: Vec<String> = facts
+            .reexports
+            .iter()
+            .filter(|i| i.local == "*")
+            .map(|i| i.source.clone())
+            .collect();
+        for source in star_sources {
+            if let Some(builder) = self.imported_builder(file, &source, name, depth) {
+                return Some(builder);
+            }
+        }
+        FunctionKind::from_callee(name)
+            .filter(|_| !declared)
+            .map(|kind| ResolvedBuilder {
+                kind: Some(kind),
+                checks_auth: false,
+            })
+    }
+
+    /// Resolve `imported` from the module `source` imported by `file`.
+    fn imported_builder(
+        &mut self,
+        file: &Path,
+        source: &str,
+        imported: &str,
+        depth: u32,
+    ) -> Option<ResolvedBuilder> {
+        if let Some(kind) = FunctionKind::from_import(source, imported) {
+            return Some(ResolvedBuilder {
+                kind: Some(kind),
+                checks_auth: false,
+            });
+        }
+        let target = self.resolve_import(file, source)?;
+        self.builder(&target, imported, depth + 1)
+    }
+
+    /// Whether the helper `name` declared in `file` checks auth.
+    fn helper_checks_auth(&mut self, file: &Path, name: &str, depth: u32) -> bool {
+        let key = (file.to_path_buf(), name.to_string());
+        if let Some(&checks) = self.memo.get(&key) {
+            return checks;
+        }
+        if !self.in_progress.insert(key.clone()) {
+            return false;
+        }
+
+        let helper = match self.files.get(file) {
+            Some(Some(facts)) => facts.helpers.iter().find(|h| h.name == name).cloned(),
+            _ => None,
+        };
+        let checks = helper.is_some_and(|helper| {
+            helper.reads_auth
+                || helper
+                    .calls
+                    .iter()
+                    .any(|callee| self.callee_checks_auth(file, callee, depth))
+        });
+
+        self.in_progress.remove(&key);
+        // A negative answer reached inside a cycle may depend on a helper
+        // that is still being resolved, so only keep it at the top level.
+        if checks || self.in_progress.is_empty() {
+            self.memo.insert(key, checks);
+        }
+        checks
+    }
+
+    /// Map a relative import specifier to a known file, analyzing files that
+    /// exist on disk but are not known yet.
+    fn resolve_import(&mut self, from: &Path, source: &str) -> Option<PathBuf> {
+        let candidates = import_candidates(from, source);
+        if let Some(known) = candidates.iter().find(|c| self.files.contains_key(*c)) {
+            return Some(known.clone());
+        }
+        let target = candidates.into_iter().find(|c| c.is_file())?;
+        let facts = analyze_file_with_auth(&target, self.auth)
+            .ok()
+            .map(|analysis| FileHelpers::from_analysis(&analysis));
+        self.files.insert(target.clone(), facts);
+        Some(target)
+    }
+}
@@ -11,6 +11,12 @@ impl Rule for MutationInRender {
     fn category(&self) -> Category {
         Category::ClientSide
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Invoking mutations during render causes infinite write loops. Call the mutate function inside event handlers or useEffect."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .convex_hook_calls
@@ -18,13 +24,14 @@ impl Rule for MutationInRender {
             .filter(|h| h.hook_name == "useMutation" && h.in_render_body)
             .map(|h| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`useMutation(...)` result is invoked during render".to_string(),
-                help: "Invoking mutations during render causes infinite write loops. Call the mutate function inside event handlers or useEffect.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: h.line,
                 column: h.col,
+                fix: None,
             })
             .collect()
     }
@@ -40,6 +47,15 @@ impl Rule for UnhandledLoadingState {
     fn category(&self) -> Category {
         Category::ClientSide
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "The first render returns `undefined`. Always check `if (data === undefined) return <Loading />` before using query results."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         // Emit at most one diagnostic per file, at the first useQuery location.
         analysis
@@ -48,13 +64,14 @@ impl Rule for UnhandledLoadingState {
             .find(|h| h.hook_name == "useQuery")
             .map(|h| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`useQuery` result may be undefined while loading".to_string(),
-                help: "The first render returns `undefined`. Always check `if (data === undefined) return <Loading />` before using query results.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: h.line,
                 column: h.col,
+                fix: None,
             })
             .into_iter()
             .collect()
@@ -71,6 +88,15 @@ impl Rule for ActionInsteadOfMutation {
     fn category(&self) -> Category {
         Category::ClientSide
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Actions don't have transactional guarantees. If you're only reading/writing the database, `useMutation` is simpler and more reliable."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .convex_hook_calls
@@ -78,13 +104,14 @@ impl Rule for ActionInsteadOfMutation {
             .filter(|h| h.hook_name == "useAction")
             .map(|h| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`useAction` used — consider if `useMutation` would suffice".to_string(),
-                help: "Actions don't have transactional guarantees. If you're only reading/writing the database, `useMutation` is simpler and more reliable.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: h.line,
                 column: h.col,
+                fix: None,
             })
             .collect()
     }
@@ -100,6 +127,15 @@ impl Rule for MissingConvexProvider {
     fn category(&self) -> Category {
         Category::ClientSide
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Convex hooks require a ConvexProvider ancestor. Typically set up in your root layout."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         // Only warn if hooks are used but no ConvexProvider import is present.
         if analysis.convex_hook_calls.is_empty() || analysis.has_convex_provider {
@@ -110,13 +146,15 @@ impl Rule for MissingConvexProvider {
         let first = &analysis.convex_hook_calls[0];
         vec![Diagnostic {
             rule: self.id().to_string(),
-            severity: Severity::Info,
+            severity: self.default_severity(),
             category: self.category(),
-            message: "Convex hooks used — ensure ConvexProvider wraps the component tree".to_string(),
-            help: "Convex hooks require a ConvexProvider ancestor. Typically set up in your root layout.".to_string(),
+            message: "Convex hooks used — ensure ConvexProvider wraps the component tree"
+                .to_string(),
+            help: self.help().to_string(),
             file: analysis.file_path.clone(),
             line: first.line,
             column: first.col,
+            fix: None,
         }]
     }
 }
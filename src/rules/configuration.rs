@@ -10,6 +10,15 @@ impl Rule for MissingConvexJson {
     fn category(&self) -> Category {
         Category::Configuration
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Create convex.json to configure your Convex deployment settings."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -17,14 +26,14 @@ impl Rule for MissingConvexJson {
         if !ctx.has_convex_json {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "No convex.json found in project root".to_string(),
-                help: "Create convex.json to configure your Convex deployment settings."
-                    .to_string(),
+                help: self.help().to_string(),
                 file: ".".to_string(),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
@@ -41,6 +50,15 @@ impl Rule for MissingGeneratedCode {
     fn category(&self) -> Category {
         Category::Configuration
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Run `npx convex dev` to generate type-safe API references. Consider checking in generated code per Convex recommendations."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -48,13 +66,14 @@ impl Rule for MissingGeneratedCode {
         if !ctx.has_generated_dir {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: "Missing convex/_generated/ directory".to_string(),
-                help: "Run `npx convex dev` to generate type-safe API references. Consider checking in generated code per Convex recommendations.".to_string(),
-                file: "convex/".to_string(),
+                message: format!("Missing {} directory", ctx.convex_path("_generated/")),
+                help: self.help().to_string(),
+                file: ctx.convex_path(""),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
@@ -79,6 +98,15 @@ impl Rule for OutdatedNodeVersion {
     fn category(&self) -> Category {
         Category::Configuration
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Update to Node 20 or later in convex.json for continued support."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -88,17 +116,17 @@ impl Rule for OutdatedNodeVersion {
                 if version <= 18 {
                     return vec![Diagnostic {
                         rule: self.id().to_string(),
-                        severity: Severity::Warning,
+                        severity: self.default_severity(),
                         category: self.category(),
                         message: format!(
                             "convex.json specifies Node {} which is no longer supported",
                             version
                         ),
-                        help: "Update to Node 20 or later in convex.json for continued support."
-                            .to_string(),
+                        help: self.help().to_string(),
                         file: "convex.json".to_string(),
                         line: 0,
                         column: 0,
+                        fix: None,
                     }];
                 }
             }
@@ -116,6 +144,15 @@ impl Rule for MissingTsconfig {
     fn category(&self) -> Category {
         Category::Configuration
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Create convex/tsconfig.json for proper TypeScript type-checking during `npx convex dev`."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -123,13 +160,17 @@ impl Rule for MissingTsconfig {
         if ctx.has_schema && !ctx.has_tsconfig {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: "No tsconfig.json found in convex/ directory".to_string(),
-                help: "Create convex/tsconfig.json for proper TypeScript type-checking during `npx convex dev`.".to_string(),
-                file: "convex/".to_string(),
+                message: format!(
+                    "No tsconfig.json found in {} directory",
+                    ctx.convex_path("")
+                ),
+                help: self.help().to_string(),
+                file: ctx.convex_path(""),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
@@ -146,6 +187,15 @@ impl Rule for MissingAuthConfig {
     fn category(&self) -> Category {
         Category::Configuration
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Create convex/auth.config.ts to configure authentication providers."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -153,14 +203,14 @@ impl Rule for MissingAuthConfig {
         if ctx.uses_auth && !ctx.has_auth_config {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "Functions use ctx.auth but no auth.config.ts found".to_string(),
-                help: "Create convex/auth.config.ts to configure authentication providers."
-                    .to_string(),
-                file: "convex/".to_string(),
+                help: self.help().to_string(),
+                file: ctx.convex_path(""),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
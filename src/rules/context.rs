@@ -8,20 +8,48 @@ use oxc_ast_visit::{walk, Visit};
 use oxc_parser::{ParseOptions, Parser};
 use oxc_span::{GetSpan, SourceType};
 
+use crate::config::AuthConfig;
+use crate::suppression::parse_directives;
+
+use super::auth_summary::{CustomBuilder, HelperFunction, MAX_RESOLVE_DEPTH};
+use super::schema_model::{
+    Field, Index, SchemaConstant, SchemaConstantValue, SchemaDefinition, SchemaFacts, SchemaImport,
+    SearchIndex, SourceSpan, TableDef, Validator, VectorIndex,
+};
 use super::{
-    CallLocation, ConvexFunction, ConvexHookCall, CtxCall, DeprecatedCall, FileAnalysis,
-    FilterField, FunctionKind, HttpRoute, ImportInfo, IndexDef, SchemaIdField, SearchIndexDef,
+    ByteRange, CallLocation, ConvexFunction, ConvexHookCall, CtxCall, DeprecatedCall, FileAnalysis,
+    FilterField, FunctionKind, HandlerArgs, HttpRoute, ImportInfo, IndexDef, OldSyntaxFunction,
+    SchemaIdField, SearchIndexDef, StorageMetadataCall, TableNameRef, WithIndexCall,
 };
 
 /// Analyze a TypeScript/JavaScript file for Convex-specific patterns.
 pub fn analyze_file(path: &Path) -> Result<FileAnalysis, String> {
+    analyze_file_with_auth(path, &AuthConfig::default())
+}
+
+/// [`analyze_file`] recognizing the project's `[auth]` helpers and wrappers.
+pub fn analyze_file_with_auth(path: &Path, auth: &AuthConfig) -> Result<FileAnalysis, String> {
     let source_text = std::fs::read_to_string(path)
         .map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    analyze_source_with_auth(path, &source_text, auth)
+}
+
+/// Analyze already-read source text as if it were the file at `path`.
+pub fn analyze_source(path: &Path, source_text: &str) -> Result<FileAnalysis, String> {
+    analyze_source_with_auth(path, source_text, &AuthConfig::default())
+}
+
+/// [`analyze_source`] recognizing the project's `[auth]` helpers and wrappers.
+pub fn analyze_source_with_auth(
+    path: &Path,
+    source_text: &str,
+    auth: &AuthConfig,
+) -> Result<FileAnalysis, String> {
     let source_type = SourceType::from_path(path)
         .map_err(|_| format!("Unknown file type: {}", path.display()))?;
 
     let allocator = Allocator::default();
-    let ret = Parser::new(&allocator, &source_text, source_type)
+    let ret = Parser::new(&allocator, source_text, source_type)
         .with_options(ParseOptions {
             parse_regular_expression: true,
             ..ParseOptions::default()
@@ -32,9 +60,20 @@ pub fn analyze_file(path: &Path) -> Result<FileAnalysis, String> {
         return Err(format!("Parser panicked on {}", path.display()));
     }
 
-    let mut visitor = ConvexVisitor::new(path, &source_text);
+    let schema_facts = extract_schema_facts(&ret.program, source_text, path);
+    let custom_builders = extract_custom_builders(&ret.program, &schema_facts.imports, auth);
+
+    let mut visitor = ConvexVisitor::new(path, source_text, auth);
+    visitor.custom_builders = custom_builders.clone();
+    visitor.imports = schema_facts.imports.clone();
+    visitor.local_bindings = top_level_bindings(&ret.program);
     visitor.visit_program(&ret.program);
-    Ok(visitor.into_analysis())
+    let mut analysis = visitor.into_analysis();
+    analysis.suppressions = parse_directives(source_text, &ret.program.comments);
+    analysis.schema_facts = schema_facts;
+    analysis.helper_functions = extract_helper_functions(&ret.program, auth);
+    analysis.custom_builders = custom_builders;
+    Ok(analysis)
 }
 
 /// Builder for accumulating function properties during AST traversal.
@@ -52,6 +91,20 @@ struct FunctionBuilder {
     handler_line_count: u32,
     span_line: u32,
     span_col: u32,
+    end_line: u32,
+    helper_calls: Vec<String>,
+    imported_builder: Option<String>,
+}
+
+/// What a call such as `query({...})` or `userQuery({...})` builds.
+struct BuilderCall {
+    kind: FunctionKind,
+    /// The builder authenticates the caller before the handler runs.
+    checks_auth: bool,
+    /// Calls made by the custom builders the function goes through.
+    helper_calls: Vec<String>,
+    /// Imported builder whose kind and auth are resolved across files.
+    imported_builder: Option<String>,
 }
 
 impl FunctionBuilder {
@@ -69,12 +122,17 @@ impl FunctionBuilder {
             handler_line_count: self.handler_line_count,
             span_line: self.span_line,
             span_col: self.span_col,
+            end_line: self.end_line,
+            helper_calls: self.helper_calls,
+            auth_helper: None,
+            imported_builder: self.imported_builder,
         }
     }
 }
 
 /// Visitor that walks the AST to extract Convex-specific patterns.
 struct ConvexVisitor<'a> {
+    auth: &'a AuthConfig,
     source_text: &'a str,
     source_lines: Vec<&'a str>,
     analysis: FileAnalysis,
@@ -95,11 +153,18 @@ struct ConvexVisitor<'a> {
     schema_table_aliases: HashMap<String, String>,
     convex_hook_aliases: HashMap<String, String>,
     identifier_aliases: HashMap<String, String>,
+    /// Top-level custom builders of the file.
+    custom_builders: Vec<CustomBuilder>,
+    /// Import bindings of the file, used to tell which builder a callee is.
+    imports: Vec<SchemaImport>,
+    /// Names declared at the top level, which shadow the builder names.
+    local_bindings: HashSet<String>,
 }
 
 impl<'a> ConvexVisitor<'a> {
-    fn new(path: &Path, source_text: &'a str) -> Self {
+    fn new(path: &Path, source_text: &'a str, auth: &'a AuthConfig) -> Self {
         Self {
+            auth,
             source_text,
             source_lines: source_text.lines().collect(),
             analysis: FileAnalysis {
@@ -127,6 +192,9 @@ impl<'a> ConvexVisitor<'a> {
                 ("useAction".to_string(), "useAction".to_string()),
             ]),
             identifier_aliases: HashMap::new(),
+            custom_builders: Vec::new(),
+            imports: Vec::new(),
+            local_bindings: HashSet::new(),
         }
     }
 
@@ -185,14 +253,221 @@ impl<'a> ConvexVisitor<'a> {
         }
     }
 
-    /// Check if a callee expression represents a Convex function constructor (query, mutation, etc.).
-    fn get_function_kind(callee: &Expression<'_>) -> Option<FunctionKind> {
-        match callee {
-            Expression::Identifier(ident) => FunctionKind::from_callee(ident.name.as_str()),
+    /// Span of the leftmost identifier of a member chain (`api` in `api.foo.bar`).
+    fn member_chain_root_span(expr: &Expression<'_>) -> Option<ByteRange> {
+        match expr {
+            Expression::Identifier(ident) => Some(ByteRange {
+                start: ident.span.start,
+                end: ident.span.end,
+            }),
+            Expression::StaticMemberExpression(mem) => Self::member_chain_root_span(&mem.object),
             _ => None,
         }
     }
 
+    /// Describe the args parameter of a handler passed as `query(async (ctx, args) => ...)`.
+    fn old_syntax_handler_args(handler: &Expression<'_>) -> HandlerArgs {
+        let params = match handler {
+            Expression::ArrowFunctionExpression(arrow) => &arrow.params,
+            Expression::FunctionExpression(func) => &func.params,
+            _ => return HandlerArgs::Unknown,
+        };
+        if params.rest.is_some() {
+            return HandlerArgs::Unknown;
+        }
+        let Some(args_param) = params.items.get(1) else {
+            return HandlerArgs::None;
+        };
+        let BindingPattern::ObjectPattern(pattern) = &args_param.pattern else {
+            return HandlerArgs::Unknown;
+        };
+        if pattern.rest.is_some() {
+            return HandlerArgs::Unknown;
+        }
+        let mut fields = Vec::new();
+        for prop in &pattern.properties {
+            let Some(name) = prop.key.static_name().filter(|_| !prop.computed) else {
+                return HandlerArgs::Unknown;
+            };
+            let has_default = matches!(prop.value, BindingPattern::AssignmentPattern(_));
+            fields.push((name.to_string(), has_default));
+        }
+        HandlerArgs::Destructured(fields)
+    }
+
+    /// Check if a call builds a Convex function: `query({...})`, a renamed
+    /// or namespaced import of a builder, a wrapper from `[auth] wrappers`,
+    /// a custom builder such as `userQuery({...})`, or a builder imported
+    /// from another project file.
+    fn builder_call(&self, call: &CallExpression<'_>) -> Option<BuilderCall> {
+        let name = match &call.callee {
+            Expression::Identifier(ident) => ident.name.as_str().to_string(),
+            Expression::StaticMemberExpression(mem) => match &mem.object {
+                Expression::Identifier(object) => format!("{}.{}", object.name, mem.property.name),
+                _ => return None,
+            },
+            _ => return None,
+        };
+        let direct = |kind, checks_auth| BuilderCall {
+            kind,
+            checks_auth,
+            helper_calls: Vec::new(),
+            imported_builder: None,
+        };
+        if let Some(kind) = self.auth.wrapper_kind(&name) {
+            return Some(direct(kind, true));
+        }
+        if self.custom_builders.iter().any(|b| b.name == name) {
+            return Some(self.custom_builder_call(&name));
+        }
+        if let Some(kind) = self.convex_builder_kind(&name) {
+            return Some(direct(kind, false));
+        }
+        let imported = self.project_import(&name)?;
+        let has_handler = matches!(call.arguments.first(), Some(Argument::ObjectExpression(obj))
+            if object_value(obj, "handler").is_some());
+        let kind = FunctionKind::from_callee(imported)
+            .or_else(|| has_handler.then(|| FunctionKind::from_wrapper_name(&name))?)
+            .or_else(|| has_handler.then_some(FunctionKind::Query))?;
+        Some(BuilderCall {
+            imported_builder: Some(name.clone()),
+            ..direct(kind, false)
+        })
+    }
+
+    /// The Convex builder `name` is bound to. `q` and `server.query` count
+    /// when imported from `./_generated/server`; an unimported `query` counts
+    /// unless the file declares its own.
+    fn convex_builder_kind(&self, name: &str) -> Option<FunctionKind> {
+        if let Some((namespace, member)) = name.split_once('.') {
+            return self
+                .imports
+                .iter()
+                .find(|i| i.local == namespace && i.imported == "*")
+                .and_then(|i| FunctionKind::from_import(&i.source, member));
+        }
+        match self.imports.iter().find(|i| i.local == name) {
+            Some(import) => FunctionKind::from_import(&import.source, &import.imported),
+            None if self.local_bindings.contains(name) => None,
+            None => FunctionKind::from_callee(name),
+        }
+    }
+
+    /// The name `name` (or `ns.name`) is imported as, when it comes from a
+    /// module other than Convex's own and may be a builder defined there.
+    fn project_import<'n>(&self, name: &'n str) -> Option<&'n str> {
+        let (local, imported) = match name.split_once('.') {
+            Some((namespace, member)) => (namespace, Some(member)),
+            None => (name, None),
+        };
+        let import = self.imports.iter().find(|i| i.local == local)?;
+        if import.source.contains("_generated/") || import.source.starts_with("convex/") {
+            return None;
+        }
+        match (imported, import.imported.as_str()) {
+            (Some(member), "*") => Some(member),
+            (None, imported) if imported != "*" => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Whether `name` is the zod namespace: imported from `zod` (or a
+    /// `zod/...` entry point), or the conventional `z` when the file
+    /// imports nothing from zod.
+    fn is_zod(&self, name: &str) -> bool {
+        let is_zod_source = |source: &str| source == "zod" || source.starts_with("zod/");
+        match self.imports.iter().find(|i| i.local == name) {
+            Some(import) => is_zod_source(&import.source),
+            None => name == "z" && !self.imports.iter().any(|i| is_zod_source(&i.source)),
+        }
+    }
+
+    /// The properties of an args validator: a plain object, or a zod
+    /// `z.object({...})` with any chained modifiers such as `.strict()`.
+    fn args_shape<'e, 'b>(&self, expr: &'e Expression<'b>) -> Option<&'e ObjectExpression<'b>> {
+        match strip_ts_wrappers(expr) {
+            Expression::ObjectExpression(obj) => Some(obj),
+            Expression::CallExpression(call) => {
+                let Expression::StaticMemberExpression(mem) = &call.callee else {
+                    return None;
+                };
+                match &mem.object {
+                    Expression::Identifier(object)
+                        if self.is_zod(object.name.as_str())
+                            && matches!(
+                                mem.property.name.as_str(),
+                                "object" | "strictObject" | "looseObject"
+                            ) =>
+                    {
+                        as_object_expression(call.arguments.first()?.as_expression()?)
+                    }
+                    inner @ Expression::CallExpression(_) => self.args_shape(inner),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// `z.any()` or `z.unknown()`, possibly with modifiers like `.optional()`.
+    fn is_zod_any(&self, expr: &Expression<'_>) -> bool {
+        let Expression::CallExpression(call) = strip_ts_wrappers(expr) else {
+            return false;
+        };
+        let Expression::StaticMemberExpression(mem) = &call.callee else {
+            return false;
+        };
+        match &mem.object {
+            Expression::Identifier(object) => {
+                self.is_zod(object.name.as_str())
+                    && matches!(mem.property.name.as_str(), "any" | "unknown")
+            }
+            inner @ Expression::CallExpression(_) => self.is_zod_any(inner),
+            _ => false,
+        }
+    }
+
+    /// Follow a local custom builder through the builders it customizes
+    /// down to `query`, a wrapper, or an import.
+    fn custom_builder_call(&self, name: &str) -> BuilderCall {
+        let mut family = None;
+        let mut base_kind = None;
+        let mut checks_auth = false;
+        let mut helper_calls: Vec<String> = Vec::new();
+        let mut imported_builder = None;
+        let mut current = name;
+        for _ in 0..=MAX_RESOLVE_DEPTH {
+            let Some(builder) = self.custom_builders.iter().find(|b| b.name == current) else {
+                if let Some(kind) = self.auth.wrapper_kind(current) {
+                    base_kind = Some(kind);
+                    checks_auth = true;
+                } else if let Some(kind) = self.convex_builder_kind(current) {
+                    base_kind = Some(kind);
+                } else if self.project_import(current).is_some() {
+                    imported_builder = Some(current.to_string());
+                }
+                break;
+            };
+            family = family.or_else(|| builder.kind.clone());
+            checks_auth |= builder.reads_auth;
+            for callee in &builder.calls {
+                if !helper_calls.contains(callee) {
+                    helper_calls.push(callee.clone());
+                }
+            }
+            current = &builder.base;
+        }
+        BuilderCall {
+            kind: base_kind
+                .or(family)
+                .or_else(|| FunctionKind::from_wrapper_name(name))
+                .unwrap_or(FunctionKind::Query),
+            checks_auth,
+            helper_calls,
+            imported_builder,
+        }
+    }
+
     /// Find the base defineTable(...) call start offset for a chained .index(...) expression.
     fn find_define_table_call_start(expr: &Expression<'_>) -> Option<u32> {
         match expr {
@@ -488,6 +763,21 @@ impl<'a> ConvexVisitor<'a> {
         }
     }
 
+    /// `name` or `namespace.name` for calls that may target a helper function.
+    /// Calls on `ctx` and `v` never do.
+    fn helper_callee(callee: &Expression<'_>) -> Option<String> {
+        match callee {
+            Expression::Identifier(ident) => Some(ident.name.as_str().to_string()),
+            Expression::StaticMemberExpression(mem) => match &mem.object {
+                Expression::Identifier(object) if !matches!(object.name.as_str(), "ctx" | "v") => {
+                    Some(format!("{}.{}", object.name, mem.property.name))
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
     fn expression_has_identifier(expr: &Expression<'_>, target: &str) -> bool {
         match expr {
             Expression::Identifier(ident) => ident.name.as_str() == target,
@@ -514,11 +804,21 @@ impl<'a> ConvexVisitor<'a> {
         }
     }
 
-    fn call_uses_auth_helper(call: &CallExpression<'_>) -> bool {
+    fn call_uses_auth_helper(call: &CallExpression<'_>, auth: &AuthConfig) -> bool {
         let Some(callee_name) = Self::callee_name(&call.callee) else {
             return false;
         };
 
+        // Helpers declared in `[auth] helpers` count whenever they get `ctx`.
+        if auth.is_helper(&callee_name)
+            && call.arguments.iter().any(|arg| {
+                arg.as_expression()
+                    .is_some_and(|expr| Self::expression_has_identifier(expr, "ctx"))
+            })
+        {
+            return true;
+        }
+
         let name = callee_name.to_ascii_lowercase();
         let known_helpers = [
             "requireadmin",
@@ -608,14 +908,55 @@ impl<'a> ConvexVisitor<'a> {
     }
 
     /// Check if an expression is a Convex function constructor call.
-    fn is_convex_function_call(expr: &Expression<'_>) -> bool {
+    fn is_convex_function_call(&self, expr: &Expression<'_>) -> bool {
         if let Expression::CallExpression(call) = expr {
-            Self::get_function_kind(&call.callee).is_some()
+            self.builder_call(call).is_some()
         } else {
             false
         }
     }
 
+    /// Read `(q) => q.eq("a", x).gt("b", y)` as `[("eq", "a"), ("gt", "b")]`.
+    fn index_range_steps(expr: &Expression<'_>) -> Option<Vec<(String, String)>> {
+        let body = match expr {
+            Expression::ArrowFunctionExpression(arrow) => match arrow.body.statements.first()? {
+                Statement::ExpressionStatement(stmt) if arrow.expression => &stmt.expression,
+                Statement::ReturnStatement(ret) => ret.argument.as_ref()?,
+                _ => return None,
+            },
+            Expression::FunctionExpression(func) => {
+                match func.body.as_ref()?.statements.first()? {
+                    Statement::ReturnStatement(ret) => ret.argument.as_ref()?,
+                    _ => return None,
+                }
+            }
+            _ => return None,
+        };
+
+        let mut steps = Vec::new();
+        let mut current = body;
+        loop {
+            match current {
+                Expression::Identifier(_) => break,
+                Expression::ParenthesizedExpression(paren) => current = &paren.expression,
+                Expression::CallExpression(call) => {
+                    let Expression::StaticMemberExpression(mem) = &call.callee else {
+                        return None;
+                    };
+                    let op = mem.property.name.as_str();
+                    if !matches!(op, "eq" | "gt" | "gte" | "lt" | "lte") {
+                        return None;
+                    }
+                    steps.push((op.to_string(), string_arg(call, 0)?));
+                    current = &mem.object;
+                }
+                _ => return None,
+            }
+        }
+        steps.reverse();
+        Some(steps)
+    }
+
     /// Extract field names from a filter callback argument by searching for q.field("name") patterns.
     fn extract_filter_field_names(expr: &Expression<'_>) -> Vec<String> {
         let mut fields = vec![];
@@ -730,6 +1071,12 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
             for spec in specs {
                 match spec {
                     ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                        if source_str.ends_with("_generated/api")
+                            && s.local.name.as_str() == "api"
+                            && s.imported.name().as_str() == "api"
+                        {
+                            self.analysis.api_import_end = Some(s.span.end);
+                        }
                         specifiers.push(s.local.name.as_str().to_string());
                     }
                     ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
@@ -763,6 +1110,7 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
             specifiers,
             line,
         });
+        self.analysis.last_import_end = Some(it.span.end);
 
         walk::walk_import_declaration(self, it);
     }
@@ -770,6 +1118,30 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
     fn visit_export_named_declaration(&mut self, it: &ExportNamedDeclaration<'a>) {
         let mut export_names = vec![];
 
+        match &it.declaration {
+            Some(Declaration::FunctionDeclaration(func)) => self
+                .analysis
+                .exported_names
+                .extend(func.id.as_ref().map(|id| id.name.to_string())),
+            Some(Declaration::ClassDeclaration(class)) => self
+                .analysis
+                .exported_names
+                .extend(class.id.as_ref().map(|id| id.name.to_string())),
+            Some(Declaration::VariableDeclaration(var_decl)) => {
+                for declarator in &var_decl.declarations {
+                    for ident in declarator.id.get_binding_identifiers() {
+                        self.analysis.exported_names.push(ident.name.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+        for spec in &it.specifiers {
+            self.analysis
+                .exported_names
+                .push(spec.exported.name().to_string());
+        }
+
         // Extract exported names from `export const foo = ..., bar = ...`
         if let Some(Declaration::VariableDeclaration(var_decl)) = &it.declaration {
             for declarator in &var_decl.declarations {
@@ -780,8 +1152,8 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                 // 6. Detect conditional exports: export const x = process.env.X ? query(...) : mutation(...)
                 if let Some(Expression::ConditionalExpression(cond)) = &declarator.init {
                     let test_has_process_env = Self::contains_process_env(&cond.test);
-                    let has_convex_call = Self::is_convex_function_call(&cond.consequent)
-                        || Self::is_convex_function_call(&cond.alternate);
+                    let has_convex_call = self.is_convex_function_call(&cond.consequent)
+                        || self.is_convex_function_call(&cond.alternate);
                     if test_has_process_env && has_convex_call {
                         let (line, col) = self.line_col(declarator.span.start);
                         self.analysis.conditional_exports.push(CallLocation {
@@ -813,10 +1185,20 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
         self.next_export_index = 0;
     }
 
+    fn visit_export_all_declaration(&mut self, it: &ExportAllDeclaration<'a>) {
+        self.analysis.exported_names.push(
+            it.exported
+                .as_ref()
+                .map_or_else(|| "*".to_string(), |name| name.name().to_string()),
+        );
+        walk::walk_export_all_declaration(self, it);
+    }
+
     fn visit_export_default_declaration(&mut self, it: &ExportDefaultDeclaration<'a>) {
+        self.analysis.exported_names.push("default".to_string());
         // Check if the default export is a Convex function call expression
         if let ExportDefaultDeclarationKind::CallExpression(_) = &it.declaration {
-            if Self::is_convex_function_call(it.declaration.to_expression()) {
+            if self.is_convex_function_call(it.declaration.to_expression()) {
                 self.current_export_names = vec!["default".to_string()];
                 self.next_export_index = 0;
             }
@@ -848,7 +1230,13 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
 
         // Check if this is a Convex function definition: query({...}), mutation({...}), etc.
         let mut is_direct_export = false;
-        if let Some(kind) = Self::get_function_kind(&it.callee) {
+        if let Some(BuilderCall {
+            kind,
+            checks_auth,
+            helper_calls,
+            imported_builder,
+        }) = self.builder_call(it)
+        {
             let export_name = self.next_export_name();
             is_direct_export = export_name.is_some();
             let tracking_name = export_name.or_else(|| self.current_assignment_target.clone());
@@ -861,8 +1249,12 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                         &self.source_lines,
                         line,
                     ),
+                    has_auth_check: checks_auth,
                     span_line: line,
                     span_col: col,
+                    end_line: self.line_col(it.span.end).0,
+                    helper_calls,
+                    imported_builder,
                     ..Default::default()
                 };
 
@@ -875,8 +1267,7 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                                 match prop_name.as_ref() {
                                     "args" => {
                                         builder.has_args_validator = true;
-                                        if let Expression::ObjectExpression(args_obj) = &prop.value
-                                        {
+                                        if let Some(args_obj) = self.args_shape(&prop.value) {
                                             for arg_prop in &args_obj.properties {
                                                 if let ObjectPropertyKind::ObjectProperty(arg) =
                                                     arg_prop
@@ -904,6 +1295,10 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                                                         }
                                                     }
 
+                                                    if self.is_zod_any(&arg.value) {
+                                                        builder.has_any_validator_in_args = true;
+                                                    }
+
                                                     // Check for v.any() in arg values
                                                     if let Expression::CallExpression(val_call) =
                                                         &arg.value
@@ -961,7 +1356,8 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                                             }
                                         }
                                     }
-                                    "returns" => builder.has_return_validator = true,
+                                    // `output` is the zod builders' name for `returns`.
+                                    "returns" | "output" => builder.has_return_validator = true,
                                     "handler" => {
                                         let handler_start_line =
                                             self.line_col(prop.value.span().start).0;
@@ -975,16 +1371,25 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                             }
                         }
                     }
-                } else if !it.arguments.is_empty() {
+                } else if let Some(handler) = it.arguments.first().filter(|_| {
+                    // httpAction only has the function form.
+                    !matches!(kind, FunctionKind::HttpAction)
+                }) {
                     // Old function syntax: direct function arg instead of config object
                     // e.g., query(async (ctx) => ...) instead of query({ handler: ... })
-                    self.analysis
-                        .old_syntax_functions
-                        .push(super::CallLocation {
-                            line,
-                            col,
-                            detail: format!("{}() using old function syntax", name),
-                        });
+                    let span = handler.span();
+                    self.analysis.old_syntax_functions.push(OldSyntaxFunction {
+                        line,
+                        col,
+                        detail: format!("{}() using old function syntax", name),
+                        handler: ByteRange {
+                            start: span.start,
+                            end: span.end,
+                        },
+                        handler_args: handler
+                            .as_expression()
+                            .map_or(HandlerArgs::Unknown, Self::old_syntax_handler_args),
+                    });
                 }
 
                 self.function_builder_stack.push(builder);
@@ -1112,6 +1517,10 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                     arg.as_expression()
                         .and_then(|expr| Self::resolve_member_chain(expr))
                 });
+                let first_arg_root = it.arguments.get(target_arg_index).and_then(|arg| {
+                    arg.as_expression()
+                        .and_then(|expr| Self::member_chain_root_span(expr))
+                });
 
                 let (
                     enclosing_function_name,
@@ -1136,6 +1545,21 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                     None => (None, false, None),
                 };
 
+                if matches!(
+                    chain.as_str(),
+                    "ctx.db.query" | "ctx.db.insert" | "ctx.db.get" | "ctx.db.normalizeId"
+                ) {
+                    if let Some(table) = string_arg(it, 0) {
+                        self.analysis.table_name_refs.push(TableNameRef {
+                            table,
+                            usage: chain.clone(),
+                            file: self.analysis.file_path.clone(),
+                            line,
+                            col,
+                        });
+                    }
+                }
+
                 let ctx_call = CtxCall {
                     chain: chain.clone(),
                     line,
@@ -1148,6 +1572,11 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                     enclosing_function_name,
                     enclosing_function_has_internal_secret,
                     first_arg_chain,
+                    span: ByteRange {
+                        start: it.span.start,
+                        end: it.span.end,
+                    },
+                    first_arg_root,
                 };
                 self.analysis.ctx_calls.push(ctx_call);
 
@@ -1307,11 +1736,20 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                 builder.has_auth_check = true;
             }
         }
-        if Self::call_uses_auth_helper(it) {
+        if Self::call_uses_auth_helper(it, self.auth) {
             if let Some(builder) = self.current_builder_mut() {
                 builder.has_auth_check = true;
             }
         }
+        // Record helper calls so auth checks inside them can be resolved later.
+        if let Some(callee) = Self::helper_callee(&it.callee).filter(|_| !started_exported_function)
+        {
+            if let Some(builder) = self.current_builder_mut() {
+                if !builder.helper_calls.contains(&callee) {
+                    builder.helper_calls.push(callee);
+                }
+            }
+        }
 
         // --- NEW DETECTION PATTERNS ---
 
@@ -1340,6 +1778,13 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                     if let Some(function_ref_arg) = it.arguments.get(2) {
                         if let Some(expr) = function_ref_arg.as_expression() {
                             if let Some(chain) = Self::resolve_member_chain(expr) {
+                                if chain.starts_with("internal.") || chain.starts_with("api.") {
+                                    self.analysis.cron_function_refs.push(CallLocation {
+                                        line,
+                                        col,
+                                        detail: chain.clone(),
+                                    });
+                                }
                                 if !chain.starts_with("internal.") && !chain.starts_with("api.") {
                                     self.analysis.cron_non_reference_calls.push(CallLocation {
                                         line,
@@ -1478,6 +1923,13 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                     if let Some(first_arg) = it.arguments.first() {
                         if let Some(Expression::StringLiteral(s)) = first_arg.as_expression() {
                             let table_ref = s.value.as_str().to_string();
+                            self.analysis.table_name_refs.push(TableNameRef {
+                                table: table_ref.clone(),
+                                usage: "v.id".to_string(),
+                                file: self.analysis.file_path.clone(),
+                                line,
+                                col,
+                            });
                             if self.schema_table_id_stack.last().is_some() {
                                 self.analysis.schema_id_fields.push(SchemaIdField {
                                     field_name: self
@@ -1599,11 +2051,20 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
                 }
 
                 if chain.starts_with("ctx.storage.getMetadata") {
-                    self.analysis.storage_metadata_calls.push(CallLocation {
-                        line,
-                        col,
-                        detail: chain,
-                    });
+                    let callee = it.callee.span();
+                    self.analysis
+                        .storage_metadata_calls
+                        .push(StorageMetadataCall {
+                            line,
+                            col,
+                            detail: chain,
+                            callee: ByteRange {
+                                start: callee.start,
+                                end: callee.end,
+                            },
+                            first_arg_start: it.arguments.first().map(|arg| arg.span().start),
+                            enclosing_function_kind: self.current_function_kind.clone(),
+                        });
                 }
             }
         }
@@ -1633,14 +2094,49 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
         if let Some(hook_name) = self.resolve_convex_hook_name(&it.callee) {
             let in_render_body = hook_name == "useMutation"
                 && Self::is_immediately_invoked(self.source_text, it.span.end);
+            let function_ref = it
+                .arguments
+                .first()
+                .and_then(|arg| arg.as_expression())
+                .and_then(Self::resolve_member_chain);
             self.analysis.convex_hook_calls.push(ConvexHookCall {
                 hook_name,
                 line,
                 col,
                 in_render_body,
+                function_ref,
             });
         }
 
+        // 16. Detect .withIndex("name", q => ...) on ctx.db.query("table") chains
+        if let Expression::StaticMemberExpression(mem) = &it.callee {
+            if mem.property.name.as_str() == "withIndex" {
+                if let Some(index) = string_arg(it, 0) {
+                    let table = match &mem.object {
+                        Expression::CallExpression(query)
+                            if Self::resolve_member_chain(&query.callee).as_deref()
+                                == Some("ctx.db.query") =>
+                        {
+                            string_arg(query, 0)
+                        }
+                        _ => None,
+                    };
+                    let range = match it.arguments.get(1) {
+                        None => Some(vec![]),
+                        Some(arg) => arg.as_expression().and_then(Self::index_range_steps),
+                    };
+                    self.analysis.with_index_calls.push(WithIndexCall {
+                        table,
+                        index,
+                        range,
+                        file: self.analysis.file_path.clone(),
+                        line,
+                        col,
+                    });
+                }
+            }
+        }
+
         walk::walk_call_expression(self, it);
         if schema_table_id.is_some() {
             self.schema_table_id_stack.pop();
@@ -1829,23 +2325,25 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
     fn visit_statement(&mut self, it: &Statement<'a>) {
         match it {
             // Standalone function declaration (not inside export)
-            Statement::FunctionDeclaration(_) => {
-                if self.current_export_names.is_empty() && self.function_builder_stack.is_empty() {
-                    self.analysis.unexported_function_count += 1;
-                }
+            Statement::FunctionDeclaration(_)
+                if self.current_export_names.is_empty()
+                    && self.function_builder_stack.is_empty() =>
+            {
+                self.analysis.unexported_function_count += 1;
             }
             // Standalone variable declaration with arrow/function expression init
-            Statement::VariableDeclaration(var_decl) => {
-                if self.current_export_names.is_empty() && self.function_builder_stack.is_empty() {
-                    for declarator in &var_decl.declarations {
-                        if let Some(init) = &declarator.init {
-                            if matches!(
-                                init,
-                                Expression::ArrowFunctionExpression(_)
-                                    | Expression::FunctionExpression(_)
-                            ) {
-                                self.analysis.unexported_function_count += 1;
-                            }
+            Statement::VariableDeclaration(var_decl)
+                if self.current_export_names.is_empty()
+                    && self.function_builder_stack.is_empty() =>
+            {
+                for declarator in &var_decl.declarations {
+                    if let Some(init) = &declarator.init {
+                        if matches!(
+                            init,
+                            Expression::ArrowFunctionExpression(_)
+                                | Expression::FunctionExpression(_)
+                        ) {
+                            self.analysis.unexported_function_count += 1;
                         }
                     }
                 }
@@ -1855,3 +2353,674 @@ impl<'a> Visit<'a> for ConvexVisitor<'a> {
         walk::walk_statement(self, it);
     }
 }
+
+/// Summarize the file's top-level helper functions: whether each reads
+/// `ctx.auth` and which functions it calls.
+fn extract_helper_functions(program: &Program<'_>, auth: &AuthConfig) -> Vec<HelperFunction> {
+    let extractor = HelperExtractor { auth };
+    let mut helpers = Vec::new();
+    for stmt in &program.body {
+        match stmt {
+            Statement::FunctionDeclaration(func) => extractor.function(func, None, &mut helpers),
+            Statement::VariableDeclaration(decl) => extractor.variables(decl, &mut helpers),
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::FunctionDeclaration(func)) => {
+                    extractor.function(func, None, &mut helpers)
+                }
+                Some(Declaration::VariableDeclaration(decl)) => {
+                    extractor.variables(decl, &mut helpers)
+                }
+                _ => {}
+            },
+            Statement::ExportDefaultDeclaration(export) => {
+                if let ExportDefaultDeclarationKind::FunctionDeclaration(func) = &export.declaration
+                {
+                    extractor.function(func, Some("default"), &mut helpers);
+                    extractor.function(func, None, &mut helpers);
+                }
+            }
+            _ => {}
+        }
+    }
+    helpers
+}
+
+struct HelperExtractor<'a> {
+    auth: &'a AuthConfig,
+}
+
+impl HelperExtractor<'_> {
+    fn function(&self, func: &Function<'_>, name: Option<&str>, helpers: &mut Vec<HelperFunction>) {
+        let (Some(name), Some(body)) = (
+            name.or_else(|| func.id.as_ref().map(|id| id.name.as_str())),
+            &func.body,
+        ) else {
+            return;
+        };
+        let mut scan = HelperScan::new(&func.params, self.auth);
+        scan.visit_function_body(body);
+        helpers.push(scan.into_helper(name));
+    }
+
+    fn variables(&self, decl: &VariableDeclaration<'_>, helpers: &mut Vec<HelperFunction>) {
+        for declarator in &decl.declarations {
+            let (BindingPattern::BindingIdentifier(ident), Some(init)) =
+                (&declarator.id, &declarator.init)
+            else {
+                continue;
+            };
+            let name = ident.name.as_str();
+            match init.without_parentheses() {
+                Expression::ArrowFunctionExpression(arrow) => {
+                    let mut scan = HelperScan::new(&arrow.params, self.auth);
+                    scan.visit_function_body(&arrow.body);
+                    helpers.push(scan.into_helper(name));
+                }
+                Expression::FunctionExpression(func) => self.function(func, Some(name), helpers),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// Walks one helper body for `ctx.auth` reads and outgoing calls.
+struct HelperScan<'a> {
+    auth: &'a AuthConfig,
+    /// `ctx` plus the name of the helper's first parameter.
+    context_names: Vec<String>,
+    reads_auth: bool,
+    calls: Vec<String>,
+}
+
+impl<'a> HelperScan<'a> {
+    fn new(params: &FormalParameters<'_>, auth: &'a AuthConfig) -> Self {
+        let mut context_names = vec!["ctx".to_string()];
+        if let Some(BindingPattern::BindingIdentifier(ident)) =
+            params.items.first().map(|param| &param.pattern)
+        {
+            context_names.push(ident.name.as_str().to_string());
+        }
+        Self {
+            auth,
+            context_names,
+            reads_auth: false,
+            calls: Vec::new(),
+        }
+    }
+
+    fn into_helper(self, name: &str) -> HelperFunction {
+        HelperFunction {
+            name: name.to_string(),
+            reads_auth: self.reads_auth,
+            calls: self.calls,
+        }
+    }
+}
+
+impl<'a> Visit<'a> for HelperScan<'_> {
+    fn visit_call_expression(&mut self, it: &CallExpression<'a>) {
+        if ConvexVisitor::call_uses_auth_helper(it, self.auth) {
+            self.reads_auth = true;
+        }
+        if let Some(callee) = ConvexVisitor::helper_callee(&it.callee) {
+            if !self.calls.contains(&callee) {
+                self.calls.push(callee);
+            }
+        }
+        walk::walk_call_expression(self, it);
+    }
+
+    fn visit_static_member_expression(&mut self, it: &StaticMemberExpression<'a>) {
+        if it.property.name.as_str() == "auth" {
+            if let Expression::Identifier(object) = &it.object {
+                if self.context_names.iter().any(|n| n == object.name.as_str()) {
+                    self.reads_auth = true;
+                }
+            }
+        }
+        walk::walk_static_member_expression(self, it);
+    }
+}
+
+/// Names of the file's top-level function, class and variable declarations.
+fn top_level_bindings(program: &Program<'_>) -> HashSet<String> {
+    let mut names = HashSet::new();
+    let mut declaration = |decl: &Declaration<'_>| match decl {
+        Declaration::FunctionDeclaration(func) => {
+            names.extend(func.id.as_ref().map(|id| id.name.to_string()));
+        }
+        Declaration::ClassDeclaration(class) => {
+            names.extend(class.id.as_ref().map(|id| id.name.to_string()));
+        }
+        Declaration::VariableDeclaration(decl) => {
+            for declarator in &decl.declarations {
+                if let BindingPattern::BindingIdentifier(ident) = &declarator.id {
+                    names.insert(ident.name.to_string());
+                }
+            }
+        }
+        _ => {}
+    };
+    for stmt in &program.body {
+        match stmt {
+            Statement::ExportNamedDeclaration(export) => {
+                if let Some(decl) = &export.declaration {
+                    declaration(decl);
+                }
+            }
+            _ => {
+                if let Some(decl) = stmt.as_declaration() {
+                    declaration(decl);
+                }
+            }
+        }
+    }
+    names
+}
+
+/// Find the file's top-level custom function builders:
+/// `const userQuery = customQuery(query, customCtx(...))`, the `zCustom*`
+/// variants, and aliases such as `const authed = userQuery`.
+fn extract_custom_builders(
+    program: &Program<'_>,
+    imports: &[SchemaImport],
+    auth: &AuthConfig,
+) -> Vec<CustomBuilder> {
+    let mut builders: Vec<CustomBuilder> = Vec::new();
+    let is_builder = |name: &str, builders: &[CustomBuilder]| {
+        FunctionKind::from_callee(name).is_some()
+            || imports.iter().any(|i| i.local == name)
+            || builders.iter().any(|b| b.name == name)
+    };
+    for stmt in &program.body {
+        let decl = match stmt {
+            Statement::VariableDeclaration(decl) => decl,
+            Statement::ExportNamedDeclaration(export) => match &export.declaration {
+                Some(Declaration::VariableDeclaration(decl)) => decl,
+                // `export { userQuery as authed }`
+                None if export.source.is_none() => {
+                    for spec in &export.specifiers {
+                        let (local, exported) = (spec.local.name(), spec.exported.name());
+                        if local != exported && is_builder(&local, &builders) {
+                            builders.push(CustomBuilder {
+                                name: exported.to_string(),
+                                base: local.to_string(),
+                                kind: None,
+                                reads_auth: false,
+                                calls: Vec::new(),
+                            });
+                        }
+                    }
+                    continue;
+                }
+                _ => continue,
+            },
+            _ => continue,
+        };
+        for declarator in &decl.declarations {
+            let (BindingPattern::BindingIdentifier(ident), Some(init)) =
+                (&declarator.id, &declarator.init)
+            else {
+                continue;
+            };
+            let name = ident.name.as_str().to_string();
+            match strip_ts_wrappers(init) {
+                Expression::CallExpression(call) => {
+                    let Expression::Identifier(callee) = &call.callee else {
+                        continue;
+                    };
+                    let local = callee.name.as_str();
+                    let imported = imports
+                        .iter()
+                        .find(|i| i.local == local)
+                        .map_or(local, |i| i.imported.as_str());
+                    let kind = match imported {
+                        "customQuery" | "zCustomQuery" => FunctionKind::Query,
+                        "customMutation" | "zCustomMutation" => FunctionKind::Mutation,
+                        "customAction" | "zCustomAction" => FunctionKind::Action,
+                        _ => continue,
+                    };
+                    let mut builder = CustomBuilder {
+                        name,
+                        base: call
+                            .arguments
+                            .first()
+                            .and_then(|arg| arg.as_expression())
+                            .and_then(ConvexVisitor::resolve_member_chain)
+                            .unwrap_or_default(),
+                        kind: Some(kind),
+                        reads_auth: false,
+                        calls: Vec::new(),
+                    };
+                    for arg in call.arguments.iter().skip(1) {
+                        if let Some(expr) = arg.as_expression() {
+                            scan_customization(expr, auth, &mut builder);
+                        }
+                    }
+                    builders.push(builder);
+                }
+                Expression::Identifier(base) => {
+                    let base = base.name.as_str();
+                    if is_builder(base, &builders) {
+                        builders.push(CustomBuilder {
+                            name,
+                            base: base.to_string(),
+                            kind: None,
+                            reads_auth: false,
+                            calls: Vec::new(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    builders
+}
+
+/// Record what a customization such as `customCtx(async (ctx) => ...)`,
+/// `{ args, input }` or a named `customCtx(withUser)` does with auth.
+fn scan_customization(expr: &Expression<'_>, auth: &AuthConfig, builder: &mut CustomBuilder) {
+    let (params, body) = match strip_ts_wrappers(expr) {
+        Expression::CallExpression(call) => {
+            for arg in &call.arguments {
+                if let Some(expr) = arg.as_expression() {
+                    scan_customization(expr, auth, builder);
+                }
+            }
+            return;
+        }
+        Expression::ObjectExpression(obj) => {
+            if let Some(input) = object_value(obj, "input") {
+                scan_customization(input, auth, builder);
+            }
+            return;
+        }
+        Expression::Identifier(ident) => {
+            let callee = ident.name.as_str().to_string();
+            if !builder.calls.contains(&callee) {
+                builder.calls.push(callee);
+            }
+            return;
+        }
+        Expression::ArrowFunctionExpression(arrow) => (&arrow.params, &arrow.body),
+        Expression::FunctionExpression(func) => match &func.body {
+            Some(body) => (&func.params, body),
+            None => return,
+        },
+        _ => return,
+    };
+    let mut scan = HelperScan::new(params, auth);
+    scan.visit_function_body(body);
+    builder.reads_auth |= scan.reads_auth;
+    for callee in scan.calls {
+        if !builder.calls.contains(&callee) {
+            builder.calls.push(callee);
+        }
+    }
+}
+
+/// Extract schema tables, validator constants and imports from a file's
+/// top-level statements. Resolution across files happens in
+/// [`SchemaModel::build`](super::schema_model::SchemaModel::build).
+fn extract_schema_facts(program: &Program<'_>, source_text: &str, path: &Path) -> SchemaFacts {
+    let extractor = SchemaExtractor {
+        source_text,
+        file: path.display().to_string(),
+    };
+    let mut facts = SchemaFacts::default();
+
+    for stmt in &program.body {
+        match stmt {
+            Statement::ImportDeclaration(import) => {
+                for spec in import.specifiers.iter().flatten() {
+                    let (local, imported) = match spec {
+                        ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                            (s.local.name.as_str(), s.imported.name().to_string())
+                        }
+                        ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                            (s.local.name.as_str(), "default".to_string())
+                        }
+                        ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
+                            (s.local.name.as_str(), "*".to_string())
+                        }
+                    };
+                    facts.imports.push(SchemaImport {
+                        local: local.to_string(),
+                        imported,
+                        source: import.source.value.as_str().to_string(),
+                    });
+                }
+            }
+            Statement::VariableDeclaration(decl) => {
+                extractor.variable_declaration(decl, &mut facts)
+            }
+            Statement::ExportNamedDeclaration(export) => {
+                if let Some(Declaration::VariableDeclaration(decl)) = &export.declaration {
+                    extractor.variable_declaration(decl, &mut facts);
+                }
+                if let Some(source) = &export.source {
+                    for spec in &export.specifiers {
+                        facts.reexports.push(SchemaImport {
+                            local: spec.exported.name().to_string(),
+                            imported: spec.local.name().to_string(),
+                            source: source.value.as_str().to_string(),
+                        });
+                    }
+                }
+            }
+            Statement::ExportAllDeclaration(export) => {
+                facts.reexports.push(SchemaImport {
+                    local: export
+                        .exported
+                        .as_ref()
+                        .map_or_else(|| "*".to_string(), |name| name.name().to_string()),
+                    imported: "*".to_string(),
+                    source: export.source.value.as_str().to_string(),
+                });
+            }
+            Statement::ExportDefaultDeclaration(export) => {
+                if let Some(expr) = export.declaration.as_expression() {
+                    if let Some(definition) = extractor.schema_definition(expr) {
+                        facts.definition = Some(definition);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    facts
+}
+
+struct SchemaExtractor<'s> {
+    source_text: &'s str,
+    file: String,
+}
+
+impl SchemaExtractor<'_> {
+    fn span(&self, offset: u32) -> SourceSpan {
+        let offset = (offset as usize).min(self.source_text.len());
+        let slice = &self.source_text[..offset];
+        SourceSpan {
+            file: self.file.clone(),
+            line: slice.matches('\n').count() as u32 + 1,
+            col: match slice.rfind('\n') {
+                Some(pos) => (offset - pos) as u32,
+                None => offset as u32 + 1,
+            },
+        }
+    }
+
+    fn variable_declaration(&self, decl: &VariableDeclaration<'_>, facts: &mut SchemaFacts) {
+        for declarator in &decl.declarations {
+            let (BindingPattern::BindingIdentifier(ident), Some(init)) =
+                (&declarator.id, &declarator.init)
+            else {
+                continue;
+            };
+            if let Some(definition) = self.schema_definition(init) {
+                facts.definition = Some(definition);
+                continue;
+            }
+            let value = match self.table(init) {
+                Some(table) if table.base.is_none() => SchemaConstantValue::Table(table),
+                _ => match self.validator(init) {
+                    Validator::Unknown => continue,
+                    Validator::Object { fields, spreads }
+                        if spreads.is_empty()
+                            && fields.iter().all(|f| f.validator == Validator::Unknown) =>
+                    {
+                        continue
+                    }
+                    validator => SchemaConstantValue::Validator(validator),
+                },
+            };
+            facts.constants.push(SchemaConstant {
+                name: ident.name.as_str().to_string(),
+                value,
+            });
+        }
+    }
+
+    fn schema_definition(&self, expr: &Expression<'_>) -> Option<SchemaDefinition> {
+        let Expression::CallExpression(call) = strip_ts_wrappers(expr) else {
+            return None;
+        };
+        if !matches!(&call.callee, Expression::Identifier(ident) if ident.name.as_str() == "defineSchema")
+        {
+            return None;
+        }
+        let Some(Expression::ObjectExpression(obj)) =
+            call.arguments.first().and_then(|arg| arg.as_expression())
+        else {
+            return None;
+        };
+        let mut definition = SchemaDefinition {
+            span: self.span(call.span.start),
+            ..SchemaDefinition::default()
+        };
+        for prop in &obj.properties {
+            match prop {
+                ObjectPropertyKind::ObjectProperty(prop) => {
+                    let Some(name) = prop.key.static_name().filter(|_| !prop.computed) else {
+                        continue;
+                    };
+                    match self.table(&prop.value) {
+                        Some(mut table) => {
+                            table.span = self.span(prop.key.span().start);
+                            definition.tables.push((name.to_string(), table));
+                        }
+                        None => definition.spreads.push(name.to_string()),
+                    }
+                }
+                ObjectPropertyKind::SpreadProperty(spread) => definition.spreads.push(
+                    ConvexVisitor::resolve_member_chain(&spread.argument).unwrap_or_default(),
+                ),
+            }
+        }
+        Some(definition)
+    }
+
+    /// A `defineTable(...)` chain, or an identifier naming one.
+    fn table(&self, expr: &Expression<'_>) -> Option<TableDef> {
+        match strip_ts_wrappers(expr) {
+            Expression::Identifier(ident) => Some(TableDef {
+                base: Some(ident.name.as_str().to_string()),
+                span: self.span(ident.span.start),
+                ..TableDef::default()
+            }),
+            Expression::CallExpression(call) => match &call.callee {
+                Expression::Identifier(ident) if ident.name.as_str() == "defineTable" => {
+                    Some(TableDef {
+                        document: call
+                            .arguments
+                            .first()
+                            .and_then(|arg| arg.as_expression())
+                            .map(|arg| self.validator(arg)),
+                        span: self.span(call.span.start),
+                        ..TableDef::default()
+                    })
+                }
+                Expression::StaticMemberExpression(mem) => {
+                    let mut table = self.table(&mem.object)?;
+                    let name = string_arg(call, 0)?;
+                    let span = self.span(mem.property.span.start);
+                    let config = call.arguments.get(1).and_then(|arg| arg.as_expression());
+                    match mem.property.name.as_str() {
+                        "index" => {
+                            let fields = match config {
+                                Some(Expression::ArrayExpression(arr)) => string_elements(arr),
+                                Some(Expression::ObjectExpression(obj)) => {
+                                    object_string_array(obj, "fields")
+                                }
+                                _ => vec![],
+                            };
+                            table.indexes.push(Index { name, fields, span });
+                        }
+                        "searchIndex" => {
+                            let obj = config.and_then(as_object_expression);
+                            table.search_indexes.push(SearchIndex {
+                                name,
+                                search_field: obj.and_then(|o| object_string(o, "searchField")),
+                                filter_fields: obj
+                                    .map(|o| object_string_array(o, "filterFields"))
+                                    .unwrap_or_default(),
+                                span,
+                            });
+                        }
+                        "vectorIndex" => {
+                            let obj = config.and_then(as_object_expression);
+                            table.vector_indexes.push(VectorIndex {
+                                name,
+                                vector_field: obj.and_then(|o| object_string(o, "vectorField")),
+                                dimensions: obj.and_then(|o| {
+                                    match object_value(o, "dimensions")? {
+                                        Expression::NumericLiteral(n) => Some(n.value as u32),
+                                        _ => None,
+                                    }
+                                }),
+                                filter_fields: obj
+                                    .map(|o| object_string_array(o, "filterFields"))
+                                    .unwrap_or_default(),
+                                span,
+                            });
+                        }
+                        _ => return None,
+                    }
+                    Some(table)
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    fn validator(&self, expr: &Expression<'_>) -> Validator {
+        match strip_ts_wrappers(expr) {
+            Expression::ObjectExpression(obj) => self.object_validator(obj),
+            Expression::Identifier(ident) => Validator::Reference(ident.name.as_str().to_string()),
+            Expression::StaticMemberExpression(_) => ConvexVisitor::resolve_member_chain(expr)
+                .map_or(Validator::Unknown, Validator::Reference),
+            Expression::CallExpression(call) => {
+                let Expression::StaticMemberExpression(mem) = &call.callee else {
+                    return Validator::Unknown;
+                };
+                if !matches!(&mem.object, Expression::Identifier(ident) if ident.name.as_str() == "v")
+                {
+                    return Validator::Unknown;
+                }
+                let arg = |index: usize| {
+                    call.arguments
+                        .get(index)
+                        .and_then(|arg| arg.as_expression())
+                        .map_or(Validator::Unknown, |arg| self.validator(arg))
+                };
+                match mem.property.name.as_str() {
+                    "string" => Validator::String,
+                    "number" | "float64" => Validator::Number,
+                    "int64" | "bigint" => Validator::Int64,
+                    "boolean" => Validator::Boolean,
+                    "null" => Validator::Null,
+                    "bytes" => Validator::Bytes,
+                    "any" => Validator::Any,
+                    "id" => Validator::Id(string_arg(call, 0).unwrap_or_default()),
+                    "literal" => call.arguments.first().map_or(Validator::Unknown, |arg| {
+                        let span = arg.span();
+                        Validator::Literal(
+                            self.source_text[span.start as usize..span.end as usize].to_string(),
+                        )
+                    }),
+                    "optional" => Validator::Optional(Box::new(arg(0))),
+                    "nullable" => Validator::Union(vec![arg(0), Validator::Null]),
+                    "array" => Validator::Array(Box::new(arg(0))),
+                    "record" => Validator::Record(Box::new(arg(0)), Box::new(arg(1))),
+                    "object" => arg(0),
+                    "union" => Validator::Union((0..call.arguments.len()).map(arg).collect()),
+                    _ => Validator::Unknown,
+                }
+            }
+            _ => Validator::Unknown,
+        }
+    }
+
+    fn object_validator(&self, obj: &ObjectExpression<'_>) -> Validator {
+        let mut fields = Vec::new();
+        let mut spreads = Vec::new();
+        for prop in &obj.properties {
+            match prop {
+                ObjectPropertyKind::ObjectProperty(prop) => {
+                    if let Some(name) = prop.key.static_name().filter(|_| !prop.computed) {
+                        fields.push(Field {
+                            name: name.to_string(),
+                            validator: self.validator(&prop.value),
+                            span: self.span(prop.key.span().start),
+                        });
+                    }
+                }
+                ObjectPropertyKind::SpreadProperty(spread) => spreads.push(
+                    ConvexVisitor::resolve_member_chain(&spread.argument).unwrap_or_default(),
+                ),
+            }
+        }
+        Validator::Object { fields, spreads }
+    }
+}
+
+/// Look through `x as T` and `x satisfies T`.
+fn strip_ts_wrappers<'e, 'a>(expr: &'e Expression<'a>) -> &'e Expression<'a> {
+    match expr {
+        Expression::TSAsExpression(e) => strip_ts_wrappers(&e.expression),
+        Expression::TSSatisfiesExpression(e) => strip_ts_wrappers(&e.expression),
+        Expression::ParenthesizedExpression(e) => strip_ts_wrappers(&e.expression),
+        other => other,
+    }
+}
+
+fn as_object_expression<'e, 'a>(expr: &'e Expression<'a>) -> Option<&'e ObjectExpression<'a>> {
+    match expr {
+        Expression::ObjectExpression(obj) => Some(obj),
+        _ => None,
+    }
+}
+
+fn string_arg(call: &CallExpression<'_>, index: usize) -> Option<String> {
+    match call.arguments.get(index)?.as_expression()? {
+        Expression::StringLiteral(s) => Some(s.value.as_str().to_string()),
+        _ => None,
+    }
+}
+
+fn string_elements(arr: &ArrayExpression<'_>) -> Vec<String> {
+    arr.elements
+        .iter()
+        .filter_map(|el| match el.as_expression()? {
+            Expression::StringLiteral(s) => Some(s.value.as_str().to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn object_value<'e, 'a>(obj: &'e ObjectExpression<'a>, key: &str) -> Option<&'e Expression<'a>> {
+    obj.properties.iter().find_map(|prop| match prop {
+        ObjectPropertyKind::ObjectProperty(prop)
+            if prop.key.static_name().is_some_and(|name| name == key) =>
+        {
+            Some(&prop.value)
+        }
+        _ => None,
+    })
+}
+
+fn object_string(obj: &ObjectExpression<'_>, key: &str) -> Option<String> {
+    match object_value(obj, key)? {
+        Expression::StringLiteral(s) => Some(s.value.as_str().to_string()),
+        _ => None,
+    }
+}
+
+fn object_string_array(obj: &ObjectExpression<'_>, key: &str) -> Vec<String> {
+    match object_value(obj, key) {
+        Some(Expression::ArrayExpression(arr)) => string_elements(arr),
+        _ => vec![],
+    }
+}
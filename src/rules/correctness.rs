@@ -1,5 +1,9 @@
-use crate::diagnostic::{Category, Diagnostic, Severity};
-use crate::rules::{FileAnalysis, ProjectContext, Rule};
+use crate::diagnostic::{Category, Diagnostic, Severity, TextEdit};
+use crate::rules::function_graph::{FunctionReference, Resolution};
+use crate::rules::{
+    FileAnalysis, FunctionKind, HandlerArgs, OldSyntaxFunction, ProjectContext, Rule,
+    StorageMetadataCall,
+};
 
 /// Patterns that should be awaited when used with ctx.
 const AWAITABLE_CTX_PREFIXES: &[&str] = &[
@@ -21,6 +25,15 @@ impl Rule for UnwaitedPromise {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "This call returns a Promise that must be awaited. Without `await`, the operation may not complete before the function returns."
+    }
+    fn fixable(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -38,13 +51,15 @@ impl Rule for UnwaitedPromise {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` is not awaited", c.chain),
-                help: "This call returns a Promise that must be awaited. Without `await`, the operation may not complete before the function returns.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: (c.span.end > c.span.start)
+                    .then(|| vec![TextEdit::insert(c.span.start, "await ")]),
             })
             .collect()
     }
@@ -58,24 +73,92 @@ impl Rule for OldFunctionSyntax {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Use `query({ args: ..., handler: async (ctx, args) => ... })` instead of `query(async (ctx) => ...)`."
+    }
+    fn fixable(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .old_syntax_functions
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Old function syntax: {}", c.detail),
-                help: "Use `query({ args: ..., handler: async (ctx, args) => ... })` instead of `query(async (ctx) => ...)`.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: object_syntax_fix(analysis, c),
             })
             .collect()
     }
 }
 
+/// Wrap an old-syntax handler as `{ args, handler }`, leaving the handler text
+/// untouched. Destructured args become `v.any()` placeholders marked with a
+/// TODO; handlers whose args cannot be inspected get no `args` at all.
+fn object_syntax_fix(analysis: &FileAnalysis, f: &OldSyntaxFunction) -> Option<Vec<TextEdit>> {
+    let mut edits = Vec::new();
+    let args = match &f.handler_args {
+        HandlerArgs::None => Some("{}".to_string()),
+        HandlerArgs::Destructured(fields) if fields.is_empty() => Some("{}".to_string()),
+        HandlerArgs::Destructured(fields) => {
+            let imports_v = analysis.imports.iter().any(|import| {
+                import.source == "convex/values" && import.specifiers.iter().any(|s| s == "v")
+            });
+            if !imports_v {
+                edits.push(TextEdit::insert(
+                    analysis.last_import_end?,
+                    "\nimport { v } from \"convex/values\";",
+                ));
+            }
+            let validators: Vec<String> = fields
+                .iter()
+                .map(|(name, has_default)| {
+                    let key = if is_identifier(name) {
+                        name.clone()
+                    } else {
+                        format!("{name:?}")
+                    };
+                    if *has_default {
+                        format!("{key}: v.optional(v.any())")
+                    } else {
+                        format!("{key}: v.any()")
+                    }
+                })
+                .collect();
+            Some(format!(
+                "{{ /* TODO: replace v.any() placeholders with real validators */ {} }}",
+                validators.join(", ")
+            ))
+        }
+        HandlerArgs::Unknown => None,
+    };
+
+    let prefix = match args {
+        Some(args) => format!("{{ args: {args}, handler: "),
+        None => "{ handler: ".to_string(),
+    };
+    edits.push(TextEdit::insert(f.handler.start, prefix));
+    edits.push(TextEdit::insert(f.handler.end, " }"));
+    Some(edits)
+}
+
+fn is_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_' || c == '$')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
 pub struct DbInAction;
 impl Rule for DbInAction {
     fn id(&self) -> &'static str {
@@ -84,6 +167,12 @@ impl Rule for DbInAction {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Actions cannot directly access the database. Use `ctx.runQuery` or `ctx.runMutation` to read/write data from an action."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -96,13 +185,14 @@ impl Rule for DbInAction {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` used in an action", c.chain),
-                help: "Actions cannot directly access the database. Use `ctx.runQuery` or `ctx.runMutation` to read/write data from an action.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -116,19 +206,26 @@ impl Rule for DeprecatedApi {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Replace deprecated Convex APIs with their documented replacements."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .deprecated_calls
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` is deprecated", c.name),
                 help: c.replacement.clone(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -143,6 +240,12 @@ impl Rule for WrongRuntimeImport {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Keep Node-only imports in files marked `\"use node\";` and keep browser client imports out of server files."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         const NODE_BUILTINS: &[&str] = &["fs", "path", "crypto", "child_process", "os", "stream"];
 
@@ -159,26 +262,28 @@ impl Rule for WrongRuntimeImport {
                 {
                     Some(Diagnostic {
                         rule: self.id().to_string(),
-                        severity: Severity::Warning,
+                        severity: self.default_severity(),
                         category: self.category(),
                         message: format!("Import `{source}` requires Node runtime"),
                         help: "Add `\"use node\";` at the top of this file or replace Node-only imports with Convex runtime-compatible APIs.".to_string(),
                         file: analysis.file_path.clone(),
                         line: import.line,
                         column: 1,
+                        fix: None,
                     })
                 } else if analysis.has_use_node
                     && (source == "convex/browser" || source == "convex/react")
                 {
                     Some(Diagnostic {
                         rule: self.id().to_string(),
-                        severity: Severity::Warning,
+                        severity: self.default_severity(),
                         category: self.category(),
                         message: format!("Node runtime file imports browser runtime package `{source}`"),
                         help: "Avoid browser/runtime client imports in server files. Use server-side Convex modules instead.".to_string(),
                         file: analysis.file_path.clone(),
                         line: import.line,
                         column: 1,
+                        fix: None,
                     })
                 } else {
                     None
@@ -196,6 +301,12 @@ impl Rule for DirectFunctionRef {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Use generated API references like `api.module.fn` or `internal.module.fn` instead of passing direct function identifiers."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -215,13 +326,17 @@ impl Rule for DirectFunctionRef {
 
                 Some(Diagnostic {
                     rule: self.id().to_string(),
-                    severity: Severity::Warning,
+                    severity: self.default_severity(),
                     category: self.category(),
-                    message: format!("`{}` called with direct function reference `{arg}`", call.chain),
-                    help: "Use generated API references like `api.module.fn` or `internal.module.fn` instead of passing direct function identifiers.".to_string(),
+                    message: format!(
+                        "`{}` called with direct function reference `{arg}`",
+                        call.chain
+                    ),
+                    help: self.help().to_string(),
                     file: analysis.file_path.clone(),
                     line: call.line,
                     column: call.col,
+                    fix: None,
                 })
             })
             .collect()
@@ -237,22 +352,29 @@ impl Rule for MissingUnique {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "If you expect exactly one result, use `.unique()` instead of `.first()` to get a runtime error when the assumption is violated."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .first_calls
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: format!(
-                    "Using `.first()` on an indexed query: {}",
-                    c.detail
-                ),
-                help: "If you expect exactly one result, use `.unique()` instead of `.first()` to get a runtime error when the assumption is violated.".to_string(),
+                message: format!("Using `.first()` on an indexed query: {}", c.detail),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -267,6 +389,12 @@ impl Rule for QuerySideEffect {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Queries must be deterministic and side-effect-free. Move writes to a mutation."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         const WRITE_PREFIXES: &[&str] = &[
             "ctx.db.insert",
@@ -286,18 +414,17 @@ impl Rule for QuerySideEffect {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "`{}` in a query function — queries must be read-only",
                     c.chain
                 ),
-                help:
-                    "Queries must be deterministic and side-effect-free. Move writes to a mutation."
-                        .to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -312,6 +439,12 @@ impl Rule for MutationInQuery {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Queries cannot call mutations. Move mutation calls to a mutation or action."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -324,14 +457,14 @@ impl Rule for MutationInQuery {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` called from a query function", c.chain),
-                help: "Queries cannot call mutations. Move mutation calls to a mutation or action."
-                    .to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -346,19 +479,26 @@ impl Rule for CronUsesPublicApi {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Use `internal.*` instead of `api.*` in cron job definitions."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .cron_api_refs
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Cron job uses public API reference `{}`", c.detail),
-                help: "Use `internal.*` instead of `api.*` in cron job definitions.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -373,6 +513,12 @@ impl Rule for NodeQueryMutation {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Only actions can use the Node.js runtime."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         if !analysis.has_use_node {
             return vec![];
@@ -391,13 +537,14 @@ impl Rule for NodeQueryMutation {
             })
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("{} `{}` in a \"use node\" file", f.kind_str(), f.name),
-                help: "Only actions can use the Node.js runtime.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -412,6 +559,12 @@ impl Rule for SchedulerReturnIgnored {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Capture the returned scheduled function ID if you need to cancel or monitor it."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -423,13 +576,14 @@ impl Rule for SchedulerReturnIgnored {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` return value not captured", c.chain),
-                help: "Capture the returned scheduled function ID if you need to cancel or monitor it.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -444,20 +598,26 @@ impl Rule for NonDeterministicInQuery {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Queries must be deterministic. Pass values as arguments or use a mutation."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .non_deterministic_calls
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` in a query function breaks determinism", c.detail),
-                help: "Queries must be deterministic. Pass values as arguments or use a mutation."
-                    .to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -472,6 +632,15 @@ impl Rule for ReplaceVsPatch {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Use `replace` for full document replacement and `patch` for partial updates. `replace` will remove omitted fields."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -479,13 +648,14 @@ impl Rule for ReplaceVsPatch {
             .filter(|c| c.chain.starts_with("ctx.db.replace"))
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`ctx.db.replace` fully replaces the existing document".to_string(),
-                help: "Use `replace` for full document replacement and `patch` for partial updates. `replace` will remove omitted fields.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -500,19 +670,26 @@ impl Rule for UnsupportedValidatorType {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Convex does not support `v.map()` or `v.set()`. Use `v.record()` for map-like structures."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .unsupported_validator_calls
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Unsupported validator usage: {}", loc.detail),
-                help: "Convex does not support `v.map()` or `v.set()`. Use `v.record()` for map-like structures.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -527,19 +704,26 @@ impl Rule for QueryDeleteUnsupported {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Collect matching rows, then call `ctx.db.delete(row._id)` for each document in a mutation."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .query_delete_calls
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Query chain uses unsupported `.delete()`: {}", loc.detail),
-                help: "Collect matching rows, then call `ctx.db.delete(row._id)` for each document in a mutation.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -554,19 +738,26 @@ impl Rule for CronHelperMethodUsage {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Use `crons.interval(...)` or `crons.cron(...)` instead of `hourly`/`daily`/`weekly` helpers."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .cron_helper_calls
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Avoid deprecated cron helper method: {}", loc.detail),
-                help: "Use `crons.interval(...)` or `crons.cron(...)` instead of `hourly`/`daily`/`weekly` helpers.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -581,22 +772,29 @@ impl Rule for CronDirectFunctionReference {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Cron schedules must receive a generated `FunctionReference` (for example `internal.jobs.run`) instead of a direct function identifier."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .cron_non_reference_calls
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Cron schedule uses direct function reference `{}`",
                     loc.detail
                 ),
-                help: "Cron schedules must receive a generated `FunctionReference` (for example `internal.jobs.run`) instead of a direct function identifier.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -611,24 +809,47 @@ impl Rule for StorageGetMetadataDeprecated {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Use the `_storage` system table (for example `ctx.db.system.get(\"_storage\", id)`) instead of `ctx.storage.getMetadata`."
+    }
+    fn fixable(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .storage_metadata_calls
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Deprecated storage API call: {}", loc.detail),
-                help: "Use the `_storage` system table (for example `ctx.db.system.get(\"_storage\", id)`) instead of `ctx.storage.getMetadata`.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: storage_metadata_fix(loc),
             })
             .collect()
     }
 }
 
+/// Rewrite `ctx.storage.getMetadata(id)` to `ctx.db.system.get("_storage", id)`.
+/// Actions have no `ctx.db`, so calls there are left for a manual migration.
+fn storage_metadata_fix(call: &StorageMetadataCall) -> Option<Vec<TextEdit>> {
+    let kind = call.enclosing_function_kind.as_ref()?;
+    if !(kind.is_query() || kind.is_mutation()) {
+        return None;
+    }
+    Some(vec![
+        TextEdit::replace(call.callee.start, call.callee.end, "ctx.db.system.get"),
+        TextEdit::insert(call.first_arg_start?, "\"_storage\", "),
+    ])
+}
+
 /// Project-level rule: detect modifications to convex/_generated/ files.
 pub struct GeneratedCodeModified;
 impl Rule for GeneratedCodeModified {
@@ -638,6 +859,15 @@ impl Rule for GeneratedCodeModified {
     fn category(&self) -> Category {
         Category::Correctness
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Files in _generated/ are auto-generated and will be overwritten. Revert manual changes."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -645,16 +875,240 @@ impl Rule for GeneratedCodeModified {
         if ctx.generated_files_modified {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: "Modified files detected in convex/_generated/".to_string(),
-                help: "Files in _generated/ are auto-generated and will be overwritten. Revert manual changes.".to_string(),
-                file: "convex/_generated/".to_string(),
+                message: format!(
+                    "Modified files detected in {}",
+                    ctx.convex_path("_generated/")
+                ),
+                help: self.help().to_string(),
+                file: ctx.convex_path("_generated/"),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
         }
     }
 }
+
+/// Project-level rule: `api.*` / `internal.*` references that name no
+/// reachable Convex function.
+pub struct UnresolvedFunctionReference;
+impl Rule for UnresolvedFunctionReference {
+    fn id(&self) -> &'static str {
+        "correctness/unresolved-function-reference"
+    }
+    fn category(&self) -> Category {
+        Category::Correctness
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Point the reference at an exported Convex function. `api.*` only contains public functions and `internal.*` only internal ones, routed by file path under `convex/`."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
+    fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
+        vec![]
+    }
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Diagnostic> {
+        let graph = &ctx.function_graph;
+        graph
+            .calls
+            .iter()
+            .filter_map(|edge| {
+                let reference = FunctionReference::parse(&edge.target)?;
+                let message = match graph.resolve(&edge.target) {
+                    Resolution::Unknown => return None,
+                    Resolution::MissingModule => format!(
+                        "`{}` references module `{}`, which does not exist",
+                        edge.target, reference.module
+                    ),
+                    Resolution::MissingExport => format!(
+                        "`{}` references `{}`, which module `{}` does not export",
+                        edge.target, reference.name, reference.module
+                    ),
+                    Resolution::Found(func) if func.kind == FunctionKind::HttpAction => format!(
+                        "`{}` references httpAction `{}`, which has no function reference",
+                        edge.target,
+                        func.id()
+                    ),
+                    Resolution::Found(func) if reference.internal && func.is_public() => format!(
+                        "`{}` references public {} `{}`; use `api.*` instead",
+                        edge.target,
+                        func.kind.as_str(),
+                        func.id()
+                    ),
+                    Resolution::Found(func) if !reference.internal && !func.is_public() => {
+                        format!(
+                            "`{}` references {} `{}`; use `internal.*` instead",
+                            edge.target,
+                            func.kind.as_str(),
+                            func.id()
+                        )
+                    }
+                    Resolution::Found(_) => return None,
+                };
+                Some(Diagnostic {
+                    rule: self.id().to_string(),
+                    severity: self.default_severity(),
+                    category: self.category(),
+                    message,
+                    help: self.help().to_string(),
+                    file: edge.file.clone(),
+                    line: edge.line,
+                    column: edge.col,
+                    fix: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Project-level rule: `ctx.run*`, scheduler, cron and client hook calls whose
+/// target is the wrong kind of function.
+pub struct FunctionKindMismatch;
+impl Rule for FunctionKindMismatch {
+    fn id(&self) -> &'static str {
+        "correctness/function-kind-mismatch"
+    }
+    fn category(&self) -> Category {
+        Category::Correctness
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Call queries with `ctx.runQuery`/`useQuery`, mutations with `ctx.runMutation`/`useMutation` and actions with `ctx.runAction`/`useAction`. The scheduler and crons can only run mutations and actions."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
+    fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
+        vec![]
+    }
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Diagnostic> {
+        let graph = &ctx.function_graph;
+        graph
+            .calls
+            .iter()
+            .filter_map(|edge| {
+                let Resolution::Found(func) = graph.resolve(&edge.target) else {
+                    return None;
+                };
+                // HTTP actions are reported by unresolved-function-reference.
+                if func.kind == FunctionKind::HttpAction || edge.kind.accepts(&func.kind) {
+                    return None;
+                }
+                Some(Diagnostic {
+                    rule: self.id().to_string(),
+                    severity: self.default_severity(),
+                    category: self.category(),
+                    message: format!(
+                        "`{}` expects {}, but `{}` is defined with `{}`",
+                        edge.kind.call_name(),
+                        edge.kind.expected(),
+                        edge.target,
+                        func.kind.as_str()
+                    ),
+                    help: self.help().to_string(),
+                    file: edge.file.clone(),
+                    line: edge.line,
+                    column: edge.col,
+                    fix: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Project-level rule: table names passed to `ctx.db.*` and `v.id(...)` that
+/// the schema does not define.
+pub struct UnknownTable;
+impl Rule for UnknownTable {
+    fn id(&self) -> &'static str {
+        "correctness/unknown-table"
+    }
+    fn category(&self) -> Category {
+        Category::Correctness
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Use a table name defined in the schema file, or add the table to the schema."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
+    fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
+        vec![]
+    }
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Diagnostic> {
+        let Some(schema) = &ctx.schema else {
+            return vec![];
+        };
+        // Tables pulled in through unresolved spreads (e.g. `...authTables`)
+        // make any unknown name plausible.
+        if schema.has_unresolved_tables {
+            return vec![];
+        }
+        ctx.all_table_name_refs
+            .iter()
+            // System tables such as `_storage` and `_scheduled_functions`.
+            .filter(|r| !r.table.starts_with('_') && schema.table(&r.table).is_none())
+            .map(|r| {
+                let suggestion = closest_match(&r.table, schema.table_names())
+                    .map(|name| format!("; did you mean `{name}`?"))
+                    .unwrap_or_default();
+                Diagnostic {
+                    rule: self.id().to_string(),
+                    severity: self.default_severity(),
+                    category: self.category(),
+                    message: format!(
+                        "`{}` references table `{}`, which is not defined in the schema{suggestion}",
+                        r.usage, r.table
+                    ),
+                    help: format!(
+                        "Use a table name defined in `{}`, or add the table to the schema.",
+                        ctx.convex_path("schema.ts")
+                    ),
+                    file: r.file.clone(),
+                    line: r.line,
+                    column: r.col,
+                    fix: None,
+                }
+            })
+            .collect()
+    }
+}
+
+/// The candidate closest to `name` by edit distance, if it is close enough to
+/// plausibly be a typo.
+fn closest_match<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let max_distance = (name.chars().count() / 3).max(2);
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
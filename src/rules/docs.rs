@@ -0,0 +1,94 @@
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+/// Where rule documentation links point by default.
+pub const RULE_REFERENCE_URL: &str = concat!(env!("CARGO_PKG_REPOSITORY"), "#rule-reference");
+
+/// Per-rule documentation, one entry per rule id.
+const RULE_DOCS: &str = include_str!("docs.toml");
+
+/// Which Convex guideline bullets each rule enforces.
+const COVERAGE_MATRIX: &str = include_str!("../../reference/convex/coverage_matrix.toml");
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct RuleDoc {
+    pub id: String,
+    /// One line, as in the README rule reference.
+    pub summary: String,
+    /// Why the flagged pattern is a problem.
+    pub rationale: String,
+    pub bad: String,
+    pub good: String,
+    /// Settings that only affect this rule.
+    #[serde(default)]
+    pub options: Vec<String>,
+}
+
+/// A bullet from the Convex guidelines and the rule enforcing it, if any.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Guideline {
+    pub id: String,
+    pub source: String,
+    pub source_line: u32,
+    pub statement: String,
+    pub rule_id: Option<String>,
+}
+
+impl Guideline {
+    /// The statement without its trailing "For example:" lead-in.
+    pub fn summary(&self) -> &str {
+        self.statement
+            .trim_end_matches(':')
+            .trim_end_matches("For example")
+            .trim_end()
+    }
+}
+
+#[derive(Deserialize)]
+struct RuleDocs {
+    rules: Vec<RuleDoc>,
+}
+
+#[derive(Deserialize)]
+struct CoverageMatrix {
+    version: String,
+    guidelines: Vec<Guideline>,
+}
+
+fn rule_docs() -> &'static [RuleDoc] {
+    static DOCS: OnceLock<Vec<RuleDoc>> = OnceLock::new();
+    DOCS.get_or_init(|| {
+        toml::from_str::<RuleDocs>(RULE_DOCS)
+            .expect("rules/docs.toml is valid")
+            .rules
+    })
+}
+
+fn coverage_matrix() -> &'static CoverageMatrix {
+    static MATRIX: OnceLock<CoverageMatrix> = OnceLock::new();
+    MATRIX.get_or_init(|| toml::from_str(COVERAGE_MATRIX).expect("coverage matrix is valid"))
+}
+
+/// Documentation for `rule_id`, including engine-emitted rules.
+pub fn rule_doc(rule_id: &str) -> Option<&'static RuleDoc> {
+    rule_docs().iter().find(|doc| doc.id == rule_id)
+}
+
+/// Guideline bullets enforced by `rule_id`, in source order.
+pub fn guidelines_for(rule_id: &str) -> Vec<&'static Guideline> {
+    coverage_matrix()
+        .guidelines
+        .iter()
+        .filter(|g| g.rule_id.as_deref() == Some(rule_id))
+        .collect()
+}
+
+pub fn guideline(id: &str) -> Option<&'static Guideline> {
+    coverage_matrix().guidelines.iter().find(|g| g.id == id)
+}
+
+/// Version of the Convex guidelines the coverage matrix was generated from.
+pub fn guidance_version() -> &'static str {
+    &coverage_matrix().version
+}
@@ -0,0 +1,339 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use super::{FileAnalysis, FunctionKind};
+
+/// Exported Convex functions keyed by their file-based route, plus the
+/// `ctx.run*`, `ctx.scheduler.*`, cron and client hook edges that reference
+/// them.
+///
+/// `convex/messages/access.ts` exporting `h` is module `messages/access`,
+/// reachable as `api.messages.access.h` or `internal.messages.access.h`.
+#[derive(Debug, Clone, Default)]
+pub struct FunctionGraph {
+    functions: BTreeMap<(String, String), IndexedFunction>,
+    analyzed_modules: BTreeSet<String>,
+    /// Every name each analyzed module exports, including ones built by
+    /// wrappers the analysis does not recognize.
+    exports: BTreeMap<String, BTreeSet<String>>,
+    /// Modules that exist on disk but were not analyzed (ignored or unparsable).
+    opaque_modules: BTreeSet<String>,
+    pub calls: Vec<CallEdge>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexedFunction {
+    pub module: String,
+    pub name: String,
+    pub kind: FunctionKind,
+    pub file: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+impl IndexedFunction {
+    /// Function identifier in Convex's `module:name` form.
+    pub fn id(&self) -> String {
+        format!("{}:{}", self.module, self.name)
+    }
+
+    /// `true` for functions reachable through `api.*`.
+    pub fn is_public(&self) -> bool {
+        matches!(
+            self.kind,
+            FunctionKind::Query | FunctionKind::Mutation | FunctionKind::Action
+        )
+    }
+}
+
+/// A parsed `api.*` / `internal.*` reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionReference {
+    pub internal: bool,
+    pub module: String,
+    pub name: String,
+}
+
+impl FunctionReference {
+    /// Parse a member chain such as `internal.users.getById`.
+    pub fn parse(chain: &str) -> Option<Self> {
+        let mut segments: Vec<&str> = chain.split('.').collect();
+        let internal = match *segments.first()? {
+            "api" => false,
+            "internal" => true,
+            _ => return None,
+        };
+        if segments.len() < 3 {
+            return None;
+        }
+        let name = segments.pop()?.to_string();
+        Some(FunctionReference {
+            internal,
+            module: segments[1..].join("/"),
+            name,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CallEdgeKind {
+    RunQuery,
+    RunMutation,
+    RunAction,
+    /// `ctx.scheduler.runAfter` / `ctx.scheduler.runAt`.
+    Schedule,
+    /// `crons.interval(...)`, `crons.cron(...)`, etc.
+    Cron,
+    /// Client `useQuery(...)`.
+    UseQuery,
+    /// Client `useMutation(...)`.
+    UseMutation,
+    /// Client `useAction(...)`.
+    UseAction,
+}
+
+impl CallEdgeKind {
+    fn from_chain(chain: &str) -> Option<Self> {
+        match chain {
+            "ctx.runQuery" => Some(CallEdgeKind::RunQuery),
+            "ctx.runMutation" => Some(CallEdgeKind::RunMutation),
+            "ctx.runAction" => Some(CallEdgeKind::RunAction),
+            "ctx.scheduler.runAfter" | "ctx.scheduler.runAt" => Some(CallEdgeKind::Schedule),
+            _ => None,
+        }
+    }
+
+    fn from_hook(hook_name: &str) -> Option<Self> {
+        match hook_name {
+            "useQuery" => Some(CallEdgeKind::UseQuery),
+            "useMutation" => Some(CallEdgeKind::UseMutation),
+            "useAction" => Some(CallEdgeKind::UseAction),
+            _ => None,
+        }
+    }
+
+    /// The call as written in source, for messages.
+    pub fn call_name(&self) -> &'static str {
+        match self {
+            CallEdgeKind::RunQuery => "ctx.runQuery",
+            CallEdgeKind::RunMutation => "ctx.runMutation",
+            CallEdgeKind::RunAction => "ctx.runAction",
+            CallEdgeKind::Schedule => "ctx.scheduler.*",
+            CallEdgeKind::Cron => "crons.*",
+            CallEdgeKind::UseQuery => "useQuery",
+            CallEdgeKind::UseMutation => "useMutation",
+            CallEdgeKind::UseAction => "useAction",
+        }
+    }
+
+    /// Whether a function of `kind` can be invoked this way. Schedulers and
+    /// crons only run mutations and actions.
+    pub fn accepts(&self, kind: &FunctionKind) -> bool {
+        match self {
+            CallEdgeKind::RunQuery | CallEdgeKind::UseQuery => kind.is_query(),
+            CallEdgeKind::RunMutation | CallEdgeKind::UseMutation => kind.is_mutation(),
+            CallEdgeKind::RunAction | CallEdgeKind::UseAction => kind.is_action(),
+            CallEdgeKind::Schedule | CallEdgeKind::Cron => kind.is_mutation() || kind.is_action(),
+        }
+    }
+
+    /// The kind of function this call expects, for messages.
+    pub fn expected(&self) -> &'static str {
+        match self {
+            CallEdgeKind::RunQuery | CallEdgeKind::UseQuery => "a query",
+            CallEdgeKind::RunMutation | CallEdgeKind::UseMutation => "a mutation",
+            CallEdgeKind::RunAction | CallEdgeKind::UseAction => "an action",
+            CallEdgeKind::Schedule | CallEdgeKind::Cron => "a mutation or action",
+        }
+    }
+}
+
+/// One call from a function, a cron definition or a client hook to a
+/// referenced function.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CallEdge {
+    pub kind: CallEdgeKind,
+    /// `module:name` of the enclosing exported function, when known.
+    pub caller: Option<String>,
+    /// The reference as written, e.g. `internal.users.getById`.
+    pub target: String,
+    pub file: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// Outcome of looking up a reference in the graph.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution<'a> {
+    Found(&'a IndexedFunction),
+    /// No file backs the referenced module.
+    MissingModule,
+    /// The module exists but exports nothing with that name.
+    MissingExport,
+    /// The module was not analyzed, or exports the name but not as a
+    /// recognized Convex function, so the reference cannot be checked.
+    Unknown,
+}
+
+impl FunctionGraph {
+    /// Build the graph from analyzed files under `convex_dir` and client files
+    /// elsewhere, which only contribute hook edges.
+    ///
+    /// `module_files` lists every source file in the functions directory,
+    /// including ones skipped by `[ignore]`, so references into them are
+    /// reported as [`Resolution::Unknown`] rather than missing.
+    pub fn build<'a>(
+        convex_dir: &Path,
+        analyses: impl IntoIterator<Item = &'a FileAnalysis>,
+        module_files: &[PathBuf],
+    ) -> Self {
+        let mut graph = FunctionGraph::default();
+
+        for analysis in analyses {
+            // Client hooks may live outside the functions directory.
+            for hook in &analysis.convex_hook_calls {
+                let (Some(kind), Some(target)) =
+                    (CallEdgeKind::from_hook(&hook.hook_name), &hook.function_ref)
+                else {
+                    continue;
+                };
+                if FunctionReference::parse(target).is_none() {
+                    continue;
+                }
+                graph.calls.push(CallEdge {
+                    kind,
+                    caller: None,
+                    target: target.clone(),
+                    file: analysis.file_path.clone(),
+                    line: hook.line,
+                    col: hook.col,
+                });
+            }
+
+            let Some(module) = module_path(convex_dir, Path::new(&analysis.file_path)) else {
+                continue;
+            };
+            graph.analyzed_modules.insert(module.clone());
+            graph
+                .exports
+                .entry(module.clone())
+                .or_default()
+                .extend(analysis.exported_names.iter().cloned());
+
+            for func in &analysis.functions {
+                graph.functions.insert(
+                    (module.clone(), func.name.clone()),
+                    IndexedFunction {
+                        module: module.clone(),
+                        name: func.name.clone(),
+                        kind: func.kind.clone(),
+                        file: analysis.file_path.clone(),
+                        line: func.span_line,
+                        col: func.span_col,
+                    },
+                );
+            }
+
+            let exported: BTreeSet<&str> =
+                analysis.functions.iter().map(|f| f.name.as_str()).collect();
+            for call in &analysis.ctx_calls {
+                let (Some(kind), Some(target)) =
+                    (CallEdgeKind::from_chain(&call.chain), &call.first_arg_chain)
+                else {
+                    continue;
+                };
+                if FunctionReference::parse(target).is_none() {
+                    continue;
+                }
+                graph.calls.push(CallEdge {
+                    kind,
+                    caller: call
+                        .enclosing_function_name
+                        .as_deref()
+                        .filter(|name| exported.contains(name))
+                        .map(|name| format!("{module}:{name}")),
+                    target: target.clone(),
+                    file: analysis.file_path.clone(),
+                    line: call.line,
+                    col: call.col,
+                });
+            }
+
+            for cron in &analysis.cron_function_refs {
+                graph.calls.push(CallEdge {
+                    kind: CallEdgeKind::Cron,
+                    caller: None,
+                    target: cron.detail.clone(),
+                    file: analysis.file_path.clone(),
+                    line: cron.line,
+                    col: cron.col,
+                });
+            }
+        }
+
+        graph.opaque_modules = module_files
+            .iter()
+            .filter_map(|file| module_path(convex_dir, file))
+            .filter(|module| !graph.analyzed_modules.contains(module))
+            .collect();
+        graph
+    }
+
+    pub fn functions(&self) -> impl Iterator<Item = &IndexedFunction> {
+        self.functions.values()
+    }
+
+    pub fn get(&self, module: &str, name: &str) -> Option<&IndexedFunction> {
+        self.functions.get(&(module.to_string(), name.to_string()))
+    }
+
+    /// Resolve a reference chain such as `api.messages.access.h`.
+    pub fn resolve(&self, chain: &str) -> Resolution<'_> {
+        let Some(reference) = FunctionReference::parse(chain) else {
+            return Resolution::Unknown;
+        };
+        if let Some(func) = self.get(&reference.module, &reference.name) {
+            return Resolution::Found(func);
+        }
+        let exported = self
+            .exports
+            .get(&reference.module)
+            .is_some_and(|names| names.contains(&reference.name) || names.contains("*"));
+        if self.opaque_modules.contains(&reference.module) || exported {
+            Resolution::Unknown
+        } else if self.analyzed_modules.contains(&reference.module) {
+            Resolution::MissingExport
+        } else {
+            Resolution::MissingModule
+        }
+    }
+
+    /// Edges whose caller is the function `module:name`.
+    pub fn calls_from<'a>(&'a self, caller: &'a str) -> impl Iterator<Item = &'a CallEdge> {
+        self.calls
+            .iter()
+            .filter(move |edge| edge.caller.as_deref() == Some(caller))
+    }
+}
+
+/// Route of a source file relative to the functions directory, without its
+/// extension: `convex/messages/access.ts` → `messages/access`.
+pub fn module_path(convex_dir: &Path, file: &Path) -> Option<String> {
+    let relative = match file.strip_prefix(convex_dir) {
+        Ok(relative) => relative.to_path_buf(),
+        Err(_) => file
+            .canonicalize()
+            .ok()?
+            .strip_prefix(convex_dir.canonicalize().ok()?)
+            .ok()?
+            .to_path_buf(),
+    };
+    let module = relative
+        .with_extension("")
+        .to_string_lossy()
+        .replace('\\', "/");
+    if module.is_empty() || module.starts_with("_generated/") {
+        return None;
+    }
+    Some(module)
+}
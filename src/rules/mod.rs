@@ -1,15 +1,41 @@
 pub mod architecture;
+pub mod auth_summary;
 pub mod client;
 pub mod configuration;
 pub mod context;
 pub mod correctness;
+pub mod docs;
+pub mod function_graph;
 pub mod performance;
 pub mod schema;
+pub mod schema_model;
 pub mod security;
 
-use crate::diagnostic::{Category, Diagnostic};
+use crate::diagnostic::{Category, Diagnostic, Severity};
+use crate::suppression::SuppressionDirective;
+use auth_summary::{CustomBuilder, HelperFunction};
+use function_graph::FunctionGraph;
+use schema_model::{SchemaFacts, SchemaModel};
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Default)]
+/// Rule id for the engine-emitted diagnostic when a file fails to parse.
+/// It is not a registered rule but can be configured like one.
+pub const FILE_PARSE_ERROR_RULE: &str = "correctness/file-parse-error";
+
+/// Rule id for the engine-emitted diagnostic reported by
+/// `--report-unused-suppressions` for directives that suppress nothing.
+pub const UNUSED_SUPPRESSION_RULE: &str = "config/unused-suppression";
+
+/// Which per-file rules apply to a file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileScope {
+    /// A file under the functions directory: every rule except `client/*`.
+    Server,
+    /// A file under a client root: only `client/*` rules.
+    Client,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FileAnalysis {
     pub file_path: String,
     pub has_use_node: bool,
@@ -22,14 +48,19 @@ pub struct FileAnalysis {
     pub loop_ctx_calls: Vec<CallLocation>,
     pub deprecated_calls: Vec<DeprecatedCall>,
     pub hardcoded_secrets: Vec<CallLocation>,
-    pub old_syntax_functions: Vec<CallLocation>,
+    pub old_syntax_functions: Vec<OldSyntaxFunction>,
     pub exported_function_count: u32,
+    /// Every name the module exports, Convex function or not; `*` for an
+    /// `export * from` whose names are unknown.
+    pub exported_names: Vec<String>,
     pub schema_nesting_depth: u32,
     pub schema_array_id_fields: Vec<CallLocation>,
     pub index_definitions: Vec<IndexDef>,
     pub first_calls: Vec<CallLocation>,
     pub awaited_identifiers: Vec<String>,
     pub cron_api_refs: Vec<CallLocation>,
+    /// `api.*` / `internal.*` references passed to cron schedule methods.
+    pub cron_function_refs: Vec<CallLocation>,
     pub generic_id_validators: Vec<CallLocation>,
     pub conditional_exports: Vec<CallLocation>,
     pub non_deterministic_calls: Vec<CallLocation>,
@@ -46,15 +77,29 @@ pub struct FileAnalysis {
     pub query_delete_calls: Vec<CallLocation>,
     pub cron_helper_calls: Vec<CallLocation>,
     pub cron_non_reference_calls: Vec<CallLocation>,
-    pub storage_metadata_calls: Vec<CallLocation>,
+    pub storage_metadata_calls: Vec<StorageMetadataCall>,
     pub paginated_functions: Vec<CallLocation>,
     pub pagination_validator_functions: Vec<String>,
     pub unexported_function_count: u32,
     pub convex_hook_calls: Vec<ConvexHookCall>,
+    pub with_index_calls: Vec<WithIndexCall>,
+    pub table_name_refs: Vec<TableNameRef>,
     pub has_convex_provider: bool,
+    pub suppressions: Vec<SuppressionDirective>,
+    /// End offset of the `api` specifier in `import { api } from ".../_generated/api"`.
+    pub api_import_end: Option<u32>,
+    /// End offset of the last top-level import declaration.
+    pub last_import_end: Option<u32>,
+    /// Tables, validator constants and imports used to build the [`SchemaModel`].
+    pub schema_facts: SchemaFacts,
+    /// Top-level non-Convex functions, summarized by
+    /// [`resolve_auth_helpers`](auth_summary::resolve_auth_helpers).
+    pub helper_functions: Vec<HelperFunction>,
+    /// Top-level `customQuery`/`customMutation`/`customAction` builders.
+    pub custom_builders: Vec<CustomBuilder>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConvexFunction {
     pub name: String,
     pub kind: FunctionKind,
@@ -68,6 +113,16 @@ pub struct ConvexFunction {
     pub handler_line_count: u32,
     pub span_line: u32,
     pub span_col: u32,
+    /// Last line of the definition call, e.g. the closing `})`.
+    pub end_line: u32,
+    /// Plain calls made by the definition, e.g. `getViewer` or `auth.requireMember`.
+    pub helper_calls: Vec<String>,
+    /// The helper call through which the handler checks auth, set by
+    /// [`resolve_auth_helpers`](auth_summary::resolve_auth_helpers).
+    pub auth_helper: Option<String>,
+    /// Imported custom builder the function was defined with, e.g.
+    /// `userQuery`. Until it is resolved, `kind` is inferred from its name.
+    pub imported_builder: Option<String>,
 }
 
 impl ConvexFunction {
@@ -82,19 +137,16 @@ impl ConvexFunction {
     }
 
     pub fn kind_str(&self) -> &'static str {
-        match self.kind {
-            FunctionKind::Query => "query",
-            FunctionKind::Mutation => "mutation",
-            FunctionKind::Action => "action",
-            FunctionKind::HttpAction => "httpAction",
-            FunctionKind::InternalQuery => "internalQuery",
-            FunctionKind::InternalMutation => "internalMutation",
-            FunctionKind::InternalAction => "internalAction",
-        }
+        self.kind.as_str()
+    }
+
+    /// Checks auth in the handler itself or through a helper it calls.
+    pub fn checks_auth(&self) -> bool {
+        self.has_auth_check || self.auth_helper.is_some()
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FunctionKind {
     Query,
     Mutation,
@@ -119,6 +171,65 @@ impl FunctionKind {
         }
     }
 
+    /// The kind built by `imported` from `source`: the builders of
+    /// `./_generated/server`, or the `queryGeneric` family of `convex/server`.
+    pub fn from_import(source: &str, imported: &str) -> Option<Self> {
+        let source = source
+            .strip_suffix(".js")
+            .or_else(|| source.strip_suffix(".ts"))
+            .unwrap_or(source);
+        if source.ends_with("_generated/server") {
+            Self::from_callee(imported)
+        } else if source == "convex/server" {
+            Self::from_callee(imported.strip_suffix("Generic").unwrap_or(imported))
+        } else {
+            None
+        }
+    }
+
+    /// The kind built by a project-defined wrapper, from its name:
+    /// `authedQuery` builds queries, `internalAdminMutation` internal mutations.
+    pub fn from_wrapper_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase();
+        let internal = name.contains("internal");
+        if name.contains("httpaction") {
+            Some(FunctionKind::HttpAction)
+        } else if name.contains("action") {
+            Some(if internal {
+                FunctionKind::InternalAction
+            } else {
+                FunctionKind::Action
+            })
+        } else if name.contains("mutation") {
+            Some(if internal {
+                FunctionKind::InternalMutation
+            } else {
+                FunctionKind::Mutation
+            })
+        } else if name.contains("query") {
+            Some(if internal {
+                FunctionKind::InternalQuery
+            } else {
+                FunctionKind::Query
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The constructor name, e.g. `internalQuery`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FunctionKind::Query => "query",
+            FunctionKind::Mutation => "mutation",
+            FunctionKind::Action => "action",
+            FunctionKind::HttpAction => "httpAction",
+            FunctionKind::InternalQuery => "internalQuery",
+            FunctionKind::InternalMutation => "internalMutation",
+            FunctionKind::InternalAction => "internalAction",
+        }
+    }
+
     pub fn is_action(&self) -> bool {
         matches!(self, FunctionKind::Action | FunctionKind::InternalAction)
     }
@@ -135,14 +246,14 @@ impl FunctionKind {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportInfo {
     pub source: String,
     pub specifiers: Vec<String>,
     pub line: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CtxCall {
     pub chain: String,
     pub line: u32,
@@ -155,16 +266,60 @@ pub struct CtxCall {
     pub enclosing_function_name: Option<String>,
     pub enclosing_function_has_internal_secret: bool,
     pub first_arg_chain: Option<String>,
+    /// Span of the whole call expression.
+    pub span: ByteRange,
+    /// Span of the root identifier of `first_arg_chain` (e.g. `api` in `api.foo.bar`).
+    pub first_arg_root: Option<ByteRange>,
+}
+
+/// Byte offsets into a file's source text.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ByteRange {
+    pub start: u32,
+    pub end: u32,
+}
+
+/// A Convex function defined as `query(async (ctx, args) => ...)`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OldSyntaxFunction {
+    pub line: u32,
+    pub col: u32,
+    pub detail: String,
+    /// Span of the handler passed directly to the constructor.
+    pub handler: ByteRange,
+    pub handler_args: HandlerArgs,
+}
+
+/// What an old-syntax handler's second parameter tells us about its arguments.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum HandlerArgs {
+    /// The handler only takes `ctx`.
+    None,
+    /// `{ a, b = 1 }` destructuring: field names and whether each has a default.
+    Destructured(Vec<(String, bool)>),
+    /// An identifier, rest element, or a handler we cannot inspect.
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StorageMetadataCall {
+    pub line: u32,
+    pub col: u32,
+    pub detail: String,
+    /// Span of the `ctx.storage.getMetadata` callee.
+    pub callee: ByteRange,
+    pub first_arg_start: Option<u32>,
+    pub enclosing_function_kind: Option<FunctionKind>,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CallLocation {
     pub line: u32,
     pub col: u32,
     pub detail: String,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DeprecatedCall {
     pub name: String,
     pub replacement: String,
@@ -172,7 +327,7 @@ pub struct DeprecatedCall {
     pub col: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexDef {
     pub table: String,
     pub name: String,
@@ -180,7 +335,7 @@ pub struct IndexDef {
     pub line: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HttpRoute {
     pub method: String,
     pub path: String,
@@ -188,7 +343,7 @@ pub struct HttpRoute {
     pub line: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SchemaIdField {
     pub field_name: String,
     pub table_ref: String,
@@ -198,14 +353,14 @@ pub struct SchemaIdField {
     pub col: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct FilterField {
     pub field_name: String,
     pub line: u32,
     pub col: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SearchIndexDef {
     pub table: String,
     pub name: String,
@@ -213,16 +368,46 @@ pub struct SearchIndexDef {
     pub line: u32,
 }
 
-#[derive(Debug, Clone, Default)]
+/// A table name string literal, e.g. `"messages"` in `ctx.db.query("messages")`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TableNameRef {
+    pub table: String,
+    /// Where the literal is used: `ctx.db.query`, `v.id`, ...
+    pub usage: String,
+    pub file: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// `ctx.db.query("table").withIndex("index", (q) => q.eq(...).gt(...))`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WithIndexCall {
+    /// Table passed to `ctx.db.query`, when it is a string literal.
+    pub table: Option<String>,
+    pub index: String,
+    /// `(method, field)` pairs of the index range in call order, e.g.
+    /// `("eq", "author")`. `None` when the range expression cannot be read.
+    pub range: Option<Vec<(String, String)>>,
+    pub file: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ConvexHookCall {
     pub hook_name: String,
     pub line: u32,
     pub col: u32,
     pub in_render_body: bool,
+    /// First argument as a member chain, e.g. `api.messages.list`.
+    pub function_ref: Option<String>,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub struct ProjectContext {
+    /// Functions directory relative to the project root: `convex`, or the
+    /// `functions` path from `convex.json` such as `src/convex`.
+    pub convex_dir: String,
     pub has_schema: bool,
     pub has_auth_config: bool,
     pub has_convex_json: bool,
@@ -236,17 +421,168 @@ pub struct ProjectContext {
     pub all_index_definitions: Vec<IndexDef>,
     pub all_schema_id_fields: Vec<SchemaIdField>,
     pub all_filter_field_names: Vec<FilterField>,
+    pub all_with_index_calls: Vec<WithIndexCall>,
+    pub all_table_name_refs: Vec<TableNameRef>,
+    /// Exported functions by route and the calls between them.
+    pub function_graph: FunctionGraph,
+    /// Resolved `defineSchema` tables; `None` without a schema definition.
+    pub schema: Option<SchemaModel>,
+}
+
+impl ProjectContext {
+    /// A path inside the functions directory, as reported in diagnostics:
+    /// `convex_path("schema.ts")` is `convex/schema.ts`.
+    pub fn convex_path(&self, relative: &str) -> String {
+        format!("{}/{relative}", self.convex_dir)
+    }
+}
+
+impl Default for ProjectContext {
+    fn default() -> Self {
+        ProjectContext {
+            convex_dir: "convex".to_string(),
+            has_schema: false,
+            has_auth_config: false,
+            has_convex_json: false,
+            has_env_local: false,
+            env_gitignored: false,
+            uses_auth: false,
+            has_generated_dir: false,
+            has_tsconfig: false,
+            node_version_from_config: None,
+            generated_files_modified: false,
+            all_index_definitions: Vec::new(),
+            all_schema_id_fields: Vec::new(),
+            all_filter_field_names: Vec::new(),
+            all_with_index_calls: Vec::new(),
+            all_table_name_refs: Vec::new(),
+            function_graph: FunctionGraph::default(),
+            schema: None,
+        }
+    }
 }
 
 pub trait Rule: Send + Sync {
     fn id(&self) -> &'static str;
     fn category(&self) -> Category;
+    /// Severity assigned to this rule's diagnostics before config overrides.
+    fn default_severity(&self) -> Severity;
+    /// General remediation guidance shown alongside this rule's diagnostics.
+    fn help(&self) -> &'static str;
+    /// One-line summary of what the rule detects.
+    fn description(&self) -> &'static str {
+        docs::rule_doc(self.id()).map_or("", |doc| doc.summary.as_str())
+    }
+    /// Why the detected pattern is a problem.
+    fn docs(&self) -> &'static str {
+        docs::rule_doc(self.id()).map_or("", |doc| doc.rationale.trim())
+    }
+    fn docs_url(&self) -> String {
+        docs::RULE_REFERENCE_URL.to_string()
+    }
+    /// Whether diagnostics carry edits that `--fix` can apply.
+    fn fixable(&self) -> bool {
+        false
+    }
+    /// Whether the rule reports from `check_project` rather than `check`.
+    fn project_level(&self) -> bool {
+        false
+    }
+    /// Whether findings describe a whole file rather than one line. They are
+    /// reported at 1:1, which is not a meaningful source line.
+    fn file_level(&self) -> bool {
+        false
+    }
+    /// Whether `strictness = "low_noise"` hides this rule's warnings.
+    fn low_noise_hidden(&self) -> bool {
+        false
+    }
+    /// Ids of the Convex guideline bullets this rule enforces.
+    fn guidance_ids(&self) -> Vec<&'static str> {
+        docs::guidelines_for(self.id())
+            .into_iter()
+            .map(|g| g.id.as_str())
+            .collect()
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic>;
     /// Project-level check, called once after all files are analyzed.
     /// Default returns empty.
     fn check_project(&self, _ctx: &ProjectContext) -> Vec<Diagnostic> {
         vec![]
     }
+
+    fn metadata(&self) -> RuleMetadata {
+        RuleMetadata {
+            id: self.id(),
+            category: self.category(),
+            default_severity: self.default_severity(),
+            description: self.description(),
+            help: self.help(),
+            docs: self.docs(),
+            docs_url: self.docs_url(),
+            fixable: self.fixable(),
+            project_level: self.project_level(),
+            engine_emitted: false,
+            low_noise_hidden: self.low_noise_hidden(),
+            guidance_ids: self.guidance_ids(),
+        }
+    }
+}
+
+/// Everything known about a rule apart from its checks, for reporters and
+/// tooling.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMetadata {
+    pub id: &'static str,
+    pub category: Category,
+    pub default_severity: Severity,
+    pub description: &'static str,
+    pub help: &'static str,
+    pub docs: &'static str,
+    pub docs_url: String,
+    pub fixable: bool,
+    pub project_level: bool,
+    /// Emitted by the engine rather than by a registered rule.
+    pub engine_emitted: bool,
+    pub low_noise_hidden: bool,
+    pub guidance_ids: Vec<&'static str>,
+}
+
+/// Metadata for the rule ids the engine emits itself.
+fn engine_rule_metadata() -> Vec<RuleMetadata> {
+    [
+        (
+            FILE_PARSE_ERROR_RULE,
+            Category::Correctness,
+            Severity::Error,
+            "Fix syntax or parser-incompatible constructs in this file so all rules can run.",
+        ),
+        (
+            UNUSED_SUPPRESSION_RULE,
+            Category::Configuration,
+            Severity::Warning,
+            "Nothing is suppressed by this directive anymore. Remove it so stale ignores don't hide future findings.",
+        ),
+    ]
+    .into_iter()
+    .map(|(id, category, default_severity, help)| {
+        let doc = docs::rule_doc(id);
+        RuleMetadata {
+            id,
+            category,
+            default_severity,
+            description: doc.map_or("", |doc| doc.summary.as_str()),
+            help,
+            docs: doc.map_or("", |doc| doc.rationale.trim()),
+            docs_url: docs::RULE_REFERENCE_URL.to_string(),
+            fixable: false,
+            project_level: false,
+            engine_emitted: true,
+            low_noise_hidden: false,
+            guidance_ids: Vec::new(),
+        }
+    })
+    .collect()
 }
 
 pub struct RuleRegistry {
@@ -290,7 +626,7 @@ impl RuleRegistry {
             Box::new(performance::LargeDocumentWrite),
             Box::new(performance::NoPaginationForList),
             Box::new(performance::MissingPaginationOptsValidator),
-            // Correctness (20)
+            // Correctness (23)
             Box::new(correctness::UnwaitedPromise),
             Box::new(correctness::OldFunctionSyntax),
             Box::new(correctness::DbInAction),
@@ -311,7 +647,10 @@ impl RuleRegistry {
             Box::new(correctness::CronHelperMethodUsage),
             Box::new(correctness::CronDirectFunctionReference),
             Box::new(correctness::StorageGetMetadataDeprecated),
-            // Schema (9)
+            Box::new(correctness::UnresolvedFunctionReference),
+            Box::new(correctness::FunctionKindMismatch),
+            Box::new(correctness::UnknownTable),
+            // Schema (10)
             Box::new(schema::MissingSchema),
             Box::new(schema::DeepNesting),
             Box::new(schema::ArrayRelationships),
@@ -321,6 +660,7 @@ impl RuleRegistry {
             Box::new(schema::OptionalFieldNoDefaultHandling),
             Box::new(schema::MissingIndexForQuery),
             Box::new(schema::IndexNameIncludesFields),
+            Box::new(schema::InvalidIndexQuery),
             // Architecture (8)
             Box::new(architecture::LargeHandler),
             Box::new(architecture::MonolithicFile),
@@ -350,9 +690,25 @@ impl RuleRegistry {
         &self.rules
     }
 
-    pub fn run(&self, analysis: &FileAnalysis, enabled: &dyn Fn(&str) -> bool) -> Vec<Diagnostic> {
+    /// Metadata for every configurable rule id: registered rules in
+    /// registration order, then the engine-emitted ones.
+    pub fn metadata(&self) -> Vec<RuleMetadata> {
+        self.rules
+            .iter()
+            .map(|rule| rule.metadata())
+            .chain(engine_rule_metadata())
+            .collect()
+    }
+
+    pub fn run(
+        &self,
+        analysis: &FileAnalysis,
+        scope: FileScope,
+        enabled: &dyn Fn(&str) -> bool,
+    ) -> Vec<Diagnostic> {
         self.rules
             .iter()
+            .filter(|r| (r.category() == Category::ClientSide) == (scope == FileScope::Client))
             .filter(|r| enabled(r.id()))
             .flat_map(|r| r.check(analysis))
             .collect()
@@ -10,6 +10,12 @@ impl Rule for UnboundedCollect {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Use `.take(n)` to limit results or implement pagination. All results count toward database bandwidth."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .collect_calls
@@ -17,13 +23,14 @@ impl Rule for UnboundedCollect {
             .filter(|c| !c.detail.contains(".take."))
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "Unbounded `.collect()` call".to_string(),
-                help: "Use `.take(n)` to limit results or implement pagination. All results count toward database bandwidth.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -37,19 +44,26 @@ impl Rule for FilterWithoutIndex {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Define an index on the filtered field and use `.withIndex()` instead for better performance."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .filter_calls
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`.filter()` without an index scans the entire table".to_string(),
-                help: "Define an index on the filtered field and use `.withIndex()` instead for better performance.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -63,6 +77,12 @@ impl Rule for DateNowInQuery {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Queries must be deterministic. Pass the timestamp as an argument from the client or use a mutation instead."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         // Date.now() calls are already filtered to query functions at analysis time
         analysis
@@ -70,13 +90,14 @@ impl Rule for DateNowInQuery {
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`Date.now()` in a query function breaks caching".to_string(),
-                help: "Queries must be deterministic. Pass the timestamp as an argument from the client or use a mutation instead.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -90,19 +111,26 @@ impl Rule for LoopRunMutation {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Calling ctx.runMutation/ctx.runQuery in a loop causes N+1 round trips. Consider batching operations or restructuring."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .loop_ctx_calls
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("ctx call `{}` inside a loop", c.detail),
-                help: "Calling ctx.runMutation/ctx.runQuery in a loop causes N+1 round trips. Consider batching operations or restructuring.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -118,6 +146,15 @@ impl Rule for SequentialRunCalls {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Multiple sequential ctx.runQuery/ctx.runMutation calls each start a separate transaction. Consider combining related reads/writes into a single mutation."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         use std::collections::HashMap;
 
@@ -141,16 +178,18 @@ impl Rule for SequentialRunCalls {
                 if calls.len() >= 3 {
                     vec![Diagnostic {
                         rule: self.id().to_string(),
-                        severity: Severity::Warning,
+                        severity: self.default_severity(),
                         category: self.category(),
                         message: format!(
                             "Action `{}` has {} sequential ctx.run* calls — consider batching",
-                            function_name, calls.len()
+                            function_name,
+                            calls.len()
                         ),
-                        help: "Multiple sequential ctx.runQuery/ctx.runMutation calls each start a separate transaction. Consider combining related reads/writes into a single mutation.".to_string(),
+                        help: self.help().to_string(),
                         file: analysis.file_path.clone(),
                         line: calls[0].line,
                         column: calls[0].col,
+                        fix: None,
                     }]
                 } else {
                     Vec::new()
@@ -169,6 +208,12 @@ impl Rule for UnnecessaryRunAction {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "If both actions are in the same runtime, call the helper function directly instead of using ctx.runAction."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -181,13 +226,14 @@ impl Rule for UnnecessaryRunAction {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "`ctx.runAction` called from within an action".to_string(),
-                help: "If both actions are in the same runtime, call the helper function directly instead of using ctx.runAction.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -203,6 +249,15 @@ impl Rule for HelperVsRun {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Use a helper function instead of ctx.runQuery/ctx.runMutation within queries/mutations. Helper functions share the same transaction."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .ctx_calls
@@ -215,13 +270,14 @@ impl Rule for HelperVsRun {
             })
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("`{}` used inside a query/mutation", c.chain),
-                help: "Use a helper function instead of ctx.runQuery/ctx.runMutation within queries/mutations. Helper functions share the same transaction.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -237,6 +293,15 @@ impl Rule for MissingIndexOnForeignKey {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Fields with `v.id()` references are commonly queried. Add an index to avoid full table scans."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -245,7 +310,9 @@ impl Rule for MissingIndexOnForeignKey {
         ctx.all_schema_id_fields
             .iter()
             .filter(|id_field| {
-                if id_field.field_name.is_empty() || id_field.table_id.is_empty() || id_field.file.is_empty()
+                if id_field.field_name.is_empty()
+                    || id_field.table_id.is_empty()
+                    || id_field.file.is_empty()
                 {
                     return false;
                 }
@@ -275,13 +342,14 @@ impl Rule for MissingIndexOnForeignKey {
                 );
                 Diagnostic {
                     rule: self.id().to_string(),
-                    severity: Severity::Warning,
+                    severity: self.default_severity(),
                     category: self.category(),
                     message,
-                    help: "Fields with `v.id()` references are commonly queried. Add an index to avoid full table scans.".to_string(),
+                    help: self.help().to_string(),
                     file: id_field.file.clone(),
                     line: id_field.line,
                     column: id_field.col,
+                    fix: None,
                 }
             })
             .collect()
@@ -297,6 +365,15 @@ impl Rule for ActionFromClient {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Calling actions from the browser is an anti-pattern. Use a mutation that schedules the action via `ctx.scheduler.runAfter(0, ...)`."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .functions
@@ -304,13 +381,17 @@ impl Rule for ActionFromClient {
             .filter(|f| f.kind == FunctionKind::Action)
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: format!("Public action `{}` can be called directly from client", f.name),
-                help: "Calling actions from the browser is an anti-pattern. Use a mutation that schedules the action via `ctx.scheduler.runAfter(0, ...)`.".to_string(),
+                message: format!(
+                    "Public action `{}` can be called directly from client",
+                    f.name
+                ),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -326,19 +407,26 @@ impl Rule for CollectThenFilter {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Collecting all results then filtering in JavaScript wastes bandwidth and breaks query caching. Use `.withIndex()` or `.filter()` on the query instead."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .collect_variable_filters
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: c.detail.clone(),
-                help: "Collecting all results then filtering in JavaScript wastes bandwidth and breaks query caching. Use `.withIndex()` or `.filter()` on the query instead.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -353,19 +441,26 @@ impl Rule for LargeDocumentWrite {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Documents approaching the 1 MiB limit may fail at runtime. Consider breaking large documents into related smaller ones."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .large_writes
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Large inline document write: {}", c.detail),
-                help: "Documents approaching the 1 MiB limit may fail at runtime. Consider breaking large documents into related smaller ones.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -381,6 +476,12 @@ impl Rule for NoPaginationForList {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Consider using `.paginate()` or `.take(n)` for public queries to limit data sent to clients."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let public_query_collect_calls: Vec<_> = analysis
             .ctx_calls
@@ -398,14 +499,15 @@ impl Rule for NoPaginationForList {
             let first_collect = public_query_collect_calls[0];
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "Public query with `.collect()` may return unbounded results to client"
                     .to_string(),
-                help: "Consider using `.paginate()` or `.take(n)` for public queries to limit data sent to clients.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: first_collect.line,
                 column: first_collect.col,
+                fix: None,
             }]
         } else {
             vec![]
@@ -423,6 +525,12 @@ impl Rule for MissingPaginationOptsValidator {
     fn category(&self) -> Category {
         Category::Performance
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Add `args: { paginationOpts: paginationOptsValidator, ... }` so clients can pass typed pagination options safely."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let functions_with_validator: HashSet<&str> = analysis
             .pagination_validator_functions
@@ -439,16 +547,17 @@ impl Rule for MissingPaginationOptsValidator {
             .filter(|loc| seen.insert(loc.detail.as_str()))
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Paginated query `{}` is missing `paginationOptsValidator` in args",
                     loc.detail
                 ),
-                help: "Add `args: { paginationOpts: paginationOptsValidator, ... }` so clients can pass typed pagination options safely.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
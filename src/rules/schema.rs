@@ -2,7 +2,8 @@ use std::collections::{HashMap, HashSet};
 use std::path::Path;
 
 use crate::diagnostic::{Category, Diagnostic, Severity};
-use crate::rules::{FileAnalysis, ProjectContext, Rule};
+use crate::rules::schema_model::{SchemaModel, Table};
+use crate::rules::{FileAnalysis, ProjectContext, Rule, WithIndexCall};
 
 const SCHEMA_FILENAMES: &[&str] = &[
     "schema.ts",
@@ -13,7 +14,7 @@ const SCHEMA_FILENAMES: &[&str] = &[
     "schema.cjs",
 ];
 
-/// Project-level rule: warn when no schema file exists in convex/ directory.
+/// Project-level rule: warn when no schema file exists in the functions directory.
 pub struct MissingSchema;
 impl Rule for MissingSchema {
     fn id(&self) -> &'static str {
@@ -22,6 +23,15 @@ impl Rule for MissingSchema {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Create a schema file in the functions directory to define your database schema with type safety."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -29,15 +39,17 @@ impl Rule for MissingSchema {
         if !ctx.has_schema {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: "No schema file found in convex/ directory".to_string(),
-                help:
-                    "Create a convex/schema* file to define your database schema with type safety."
-                        .to_string(),
-                file: "convex/".to_string(),
+                message: format!("No schema file found in {} directory", ctx.convex_path("")),
+                help: format!(
+                    "Create {} to define your database schema with type safety.",
+                    ctx.convex_path("schema.ts")
+                ),
+                file: ctx.convex_path(""),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
@@ -54,20 +66,30 @@ impl Rule for DeepNesting {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Consider flattening deeply nested validators by splitting into separate tables or using v.any() for complex data."
+    }
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         if analysis.schema_nesting_depth > 3 {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Schema validators nested {} levels deep",
                     analysis.schema_nesting_depth
                 ),
-                help: "Consider flattening deeply nested validators by splitting into separate tables or using v.any() for complex data.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: 1,
                 column: 1,
+                fix: None,
             }]
         } else {
             vec![]
@@ -84,19 +106,26 @@ impl Rule for ArrayRelationships {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Arrays of v.id() for relationships can grow unbounded. Consider using a separate join table instead."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .schema_array_id_fields
             .iter()
             .map(|c| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Array of document references: {}", c.detail),
-                help: "Arrays of v.id() for relationships can grow unbounded. Consider using a separate join table instead.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: c.line,
                 column: c.col,
+                fix: None,
             })
             .collect()
     }
@@ -111,6 +140,12 @@ impl Rule for RedundantIndex {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "A compound index can serve queries on its prefix fields. Remove the shorter index to reduce storage overhead."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let mut diagnostics = vec![];
         for (i, idx) in analysis.index_definitions.iter().enumerate() {
@@ -126,16 +161,17 @@ impl Rule for RedundantIndex {
                     {
                         diagnostics.push(Diagnostic {
                             rule: self.id().to_string(),
-                            severity: Severity::Warning,
+                            severity: self.default_severity(),
                             category: self.category(),
                             message: format!(
                                 "Index '{}' is redundant — it's a prefix of index '{}'",
                                 idx.name, other.name
                             ),
-                            help: "A compound index can serve queries on its prefix fields. Remove the shorter index to reduce storage overhead.".to_string(),
+                            help: self.help().to_string(),
                             file: analysis.file_path.clone(),
                             line: idx.line,
                             column: 1,
+                            fix: None,
                         });
                     }
                     // Check reverse: other.fields is a strict prefix of idx.fields
@@ -148,16 +184,17 @@ impl Rule for RedundantIndex {
                     {
                         diagnostics.push(Diagnostic {
                             rule: self.id().to_string(),
-                            severity: Severity::Warning,
+                            severity: self.default_severity(),
                             category: self.category(),
                             message: format!(
                                 "Index '{}' is redundant — it's a prefix of index '{}'",
                                 other.name, idx.name
                             ),
-                            help: "A compound index can serve queries on its prefix fields. Remove the shorter index to reduce storage overhead.".to_string(),
+                            help: self.help().to_string(),
                             file: analysis.file_path.clone(),
                             line: other.line,
                             column: 1,
+                            fix: None,
                         });
                     }
                 }
@@ -176,6 +213,12 @@ impl Rule for TooManyIndexes {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Each index adds storage overhead and slows writes. Consider consolidating or removing unused indexes."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let mut by_table: HashMap<&str, Vec<&crate::rules::IndexDef>> = HashMap::new();
         for idx in &analysis.index_definitions {
@@ -188,17 +231,18 @@ impl Rule for TooManyIndexes {
             if indexes.len() >= 8 {
                 diagnostics.push(Diagnostic {
                     rule: self.id().to_string(),
-                    severity: Severity::Info,
+                    severity: self.default_severity(),
                     category: self.category(),
                     message: format!(
                         "Table '{}' has {} indexes (soft warning threshold is 8, hard limit is 32)",
                         table,
                         indexes.len()
                     ),
-                    help: "Each index adds storage overhead and slows writes. Consider consolidating or removing unused indexes.".to_string(),
+                    help: self.help().to_string(),
                     file: analysis.file_path.clone(),
                     line: indexes[0].line,
                     column: 1,
+                    fix: None,
                 });
             }
         }
@@ -215,6 +259,15 @@ impl Rule for MissingSearchIndexFilter {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Info
+    }
+    fn help(&self) -> &'static str {
+        "Adding filterFields to search indexes improves query performance by narrowing results before full-text search."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .search_index_definitions
@@ -222,13 +275,14 @@ impl Rule for MissingSearchIndexFilter {
             .filter(|s| !s.has_filter_fields)
             .map(|s| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Info,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Search index `{}` has no filterFields", s.name),
-                help: "Adding filterFields to search indexes improves query performance by narrowing results before full-text search.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: s.line,
                 column: 1,
+                fix: None,
             })
             .collect()
     }
@@ -244,6 +298,18 @@ impl Rule for OptionalFieldNoDefaultHandling {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Optional fields return `undefined` when not set. Ensure all access sites handle the missing case."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let is_schema_file = Path::new(&analysis.file_path)
             .file_name()
@@ -253,16 +319,17 @@ impl Rule for OptionalFieldNoDefaultHandling {
         if is_schema_file && analysis.optional_schema_fields.len() >= 5 {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "{} optional fields in schema — ensure undefined is handled",
                     analysis.optional_schema_fields.len()
                 ),
-                help: "Optional fields return `undefined` when not set. Ensure all access sites handle the missing case.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: 1,
                 column: 1,
+                fix: None,
             }]
         } else {
             vec![]
@@ -285,6 +352,18 @@ impl Rule for MissingIndexForQuery {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Define indexes on fields you query frequently to avoid full table scans."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
+    fn file_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -297,14 +376,15 @@ impl Rule for MissingIndexForQuery {
         if ctx.all_index_definitions.is_empty() && !ctx.all_filter_field_names.is_empty() {
             return vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "Schema exists but no database indexes are defined".to_string(),
                 help: "Define indexes on fields you query frequently to avoid full table scans."
                     .to_string(),
-                file: "convex/schema.ts".to_string(),
+                file: ctx.convex_path("schema.ts"),
                 line: 1,
                 column: 1,
+                fix: None,
             }];
         }
 
@@ -323,7 +403,7 @@ impl Rule for MissingIndexForQuery {
             .filter(|ff| !indexed_fields.contains(ff.field_name.as_str()))
             .map(|ff| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Query filters on field `{}` but no index covers that field",
@@ -331,9 +411,10 @@ impl Rule for MissingIndexForQuery {
                 ),
                 help: "Add an index starting with this field to avoid full table scans."
                     .to_string(),
-                file: "convex/schema.ts".to_string(),
+                file: ctx.convex_path("schema.ts"),
                 line: ff.line,
                 column: ff.col,
+                fix: None,
             })
             .collect()
     }
@@ -381,6 +462,15 @@ impl Rule for IndexNameIncludesFields {
     fn category(&self) -> Category {
         Category::Schema
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Convex index naming convention is `by_field1_and_field2` for fields `[\"field1\", \"field2\"]`."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .index_definitions
@@ -392,18 +482,150 @@ impl Rule for IndexNameIncludesFields {
                 }
                 Some(Diagnostic {
                     rule: self.id().to_string(),
-                    severity: Severity::Warning,
+                    severity: self.default_severity(),
                     category: self.category(),
                     message: format!(
                         "Index name `{}` should include all fields in order (expected `{}`)",
                         idx.name, expected
                     ),
-                    help: "Convex index naming convention is `by_field1_and_field2` for fields `[\"field1\", \"field2\"]`.".to_string(),
+                    help: self.help().to_string(),
                     file: analysis.file_path.clone(),
                     line: idx.line,
                     column: 1,
+                    fix: None,
                 })
             })
             .collect()
     }
 }
+
+/// Project-level rule: `withIndex` calls that name an index the table does not
+/// have, or constrain its fields out of order.
+pub struct InvalidIndexQuery;
+impl Rule for InvalidIndexQuery {
+    fn id(&self) -> &'static str {
+        "schema/invalid-index-query"
+    }
+    fn category(&self) -> Category {
+        Category::Schema
+    }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Index ranges must use `.eq()` on index fields in the order they are defined, optionally followed by `.gt()`/`.gte()`/`.lt()`/`.lte()` on the next field. Define a separate index for other field orders."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
+    fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
+        vec![]
+    }
+    fn check_project(&self, ctx: &ProjectContext) -> Vec<Diagnostic> {
+        let Some(schema) = &ctx.schema else {
+            return vec![];
+        };
+        ctx.all_with_index_calls
+            .iter()
+            .filter_map(|call| {
+                // Tables missing from the schema are not this rule's concern.
+                let table = schema.table(call.table.as_deref()?)?;
+                let message = index_query_problem(schema, table, call)?;
+                Some(Diagnostic {
+                    rule: self.id().to_string(),
+                    severity: self.default_severity(),
+                    category: self.category(),
+                    message,
+                    help: self.help().to_string(),
+                    file: call.file.clone(),
+                    line: call.line,
+                    column: call.col,
+                    fix: None,
+                })
+            })
+            .collect()
+    }
+}
+
+/// The first problem with `call` against `table`'s index definition.
+fn index_query_problem(
+    schema: &SchemaModel,
+    table: &Table,
+    call: &WithIndexCall,
+) -> Option<String> {
+    let index = call.index.as_str();
+    let mut fields: Vec<&str> = match index {
+        "by_creation_time" => vec!["_creationTime"],
+        "by_id" => vec!["_id"],
+        _ => match table.index(index) {
+            Some(def) => def.fields.iter().map(String::as_str).collect(),
+            None => {
+                let owners: Vec<&str> = schema
+                    .tables
+                    .iter()
+                    .filter(|t| t.index(index).is_some())
+                    .map(|t| t.name.as_str())
+                    .collect();
+                return Some(if owners.is_empty() {
+                    format!("Index `{index}` is not defined on table `{}`", table.name)
+                } else {
+                    format!(
+                        "Index `{index}` is defined on `{}`, not on table `{}`",
+                        owners.join("`, `"),
+                        table.name
+                    )
+                });
+            }
+        },
+    };
+    // Convex appends `_creationTime` to every index as a tiebreaker.
+    if fields.last() != Some(&"_creationTime") {
+        fields.push("_creationTime");
+    }
+
+    let out_of_order = |field: &str, position: usize| {
+        if !fields.contains(&field) {
+            format!(
+                "Field `{field}` is not part of index `{index}` on `{}` (fields: {})",
+                table.name,
+                fields.join(", ")
+            )
+        } else if fields[..position].contains(&field) {
+            format!("Field `{field}` is constrained more than once in index `{index}`")
+        } else {
+            format!(
+                "Index `{index}` must be queried in field order: `{}` comes before `{field}`",
+                fields[position]
+            )
+        }
+    };
+
+    let mut position = 0;
+    let mut range_field: Option<&str> = None;
+    for (op, field) in call.range.as_ref()? {
+        let field = field.as_str();
+        if op == "eq" {
+            if let Some(range_field) = range_field {
+                return Some(format!(
+                    "`.eq(\"{field}\")` follows a range condition on `{range_field}` in index `{index}`; equality conditions must come first"
+                ));
+            }
+            if fields.get(position) != Some(&field) {
+                return Some(out_of_order(field, position));
+            }
+            position += 1;
+        } else {
+            match range_field {
+                Some(range_field) if range_field == field => {}
+                Some(range_field) => {
+                    return Some(format!(
+                        "Index `{index}` allows range conditions on a single field, found `{range_field}` and `{field}`"
+                    ));
+                }
+                None if fields.get(position) == Some(&field) => range_field = Some(field),
+                None => return Some(out_of_order(field, position)),
+            }
+        }
+    }
+    None
+}
@@ -0,0 +1,453 @@
+use std::collections::HashMap;
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::function_graph::module_path;
+use super::FileAnalysis;
+
+/// Nesting limit when following validator constants and table aliases.
+const MAX_RESOLVE_DEPTH: u32 = 16;
+
+/// Where a table, field or index is defined.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SourceSpan {
+    pub file: String,
+    pub line: u32,
+    pub col: u32,
+}
+
+/// A `v.*` validator, or a plain object of validators.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Validator {
+    String,
+    /// `v.number()` / `v.float64()`.
+    Number,
+    /// `v.int64()` / `v.bigint()`.
+    Int64,
+    Boolean,
+    Null,
+    Bytes,
+    Any,
+    /// `v.id("table")`; empty when the table is not a string literal.
+    Id(String),
+    /// `v.literal(...)` with the literal as written, e.g. `"active"` or `3`.
+    Literal(String),
+    Optional(Box<Validator>),
+    Array(Box<Validator>),
+    Record(Box<Validator>, Box<Validator>),
+    /// `v.object({...})` or a plain `{...}` of validators. `spreads` lists
+    /// `...other` entries that have not been (or could not be) resolved.
+    Object {
+        fields: Vec<Field>,
+        spreads: Vec<String>,
+    },
+    Union(Vec<Validator>),
+    /// An identifier or member chain naming another validator, e.g.
+    /// `statusValidator` or `validators.address`. Left in place when the
+    /// model cannot resolve it.
+    Reference(String),
+    Unknown,
+}
+
+impl Validator {
+    /// Fields of an object validator.
+    pub fn fields(&self) -> Option<&[Field]> {
+        match self {
+            Validator::Object { fields, .. } => Some(fields),
+            _ => None,
+        }
+    }
+
+    /// Strip `v.optional(...)`.
+    pub fn unwrap_optional(&self) -> &Validator {
+        match self {
+            Validator::Optional(inner) => inner.unwrap_optional(),
+            other => other,
+        }
+    }
+
+    /// `true` when every nested part of the validator was understood.
+    pub fn is_fully_resolved(&self) -> bool {
+        match self {
+            Validator::Reference(_) | Validator::Unknown => false,
+            Validator::Optional(inner) | Validator::Array(inner) => inner.is_fully_resolved(),
+            Validator::Record(key, value) => key.is_fully_resolved() && value.is_fully_resolved(),
+            Validator::Object { fields, spreads } => {
+                spreads.is_empty() && fields.iter().all(|f| f.validator.is_fully_resolved())
+            }
+            Validator::Union(members) => members.iter().all(Validator::is_fully_resolved),
+            _ => true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Field {
+    pub name: String,
+    pub validator: Validator,
+    pub span: SourceSpan,
+}
+
+/// `.index("by_author", ["author", "_creationTime"])`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Index {
+    pub name: String,
+    pub fields: Vec<String>,
+    pub span: SourceSpan,
+}
+
+/// `.searchIndex("search_body", { searchField, filterFields })`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchIndex {
+    pub name: String,
+    pub search_field: Option<String>,
+    pub filter_fields: Vec<String>,
+    pub span: SourceSpan,
+}
+
+/// `.vectorIndex("by_embedding", { vectorField, dimensions, filterFields })`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VectorIndex {
+    pub name: String,
+    pub vector_field: Option<String>,
+    pub dimensions: Option<u32>,
+    pub filter_fields: Vec<String>,
+    pub span: SourceSpan,
+}
+
+/// A `defineTable(...)` chain as written in one file, before resolution.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TableDef {
+    /// Identifier the chain starts from instead of `defineTable`, e.g.
+    /// `users` in `users.index(...)` or a shorthand `{ users }` entry.
+    pub base: Option<String>,
+    pub document: Option<Validator>,
+    pub indexes: Vec<Index>,
+    pub search_indexes: Vec<SearchIndex>,
+    pub vector_indexes: Vec<VectorIndex>,
+    pub span: SourceSpan,
+}
+
+/// Value of a top-level `const` that schema definitions may refer to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SchemaConstantValue {
+    Validator(Validator),
+    Table(TableDef),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaConstant {
+    pub name: String,
+    pub value: SchemaConstantValue,
+}
+
+/// `import { imported as local } from "source"`; `imported` is `*` for
+/// namespace imports.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaImport {
+    pub local: String,
+    pub imported: String,
+    pub source: String,
+}
+
+/// The argument of `defineSchema({...})`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaDefinition {
+    pub tables: Vec<(String, TableDef)>,
+    /// `...authTables` spreads and entries that are not table chains.
+    pub spreads: Vec<String>,
+    pub span: SourceSpan,
+}
+
+/// Schema-related facts extracted from a single file.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SchemaFacts {
+    pub imports: Vec<SchemaImport>,
+    /// `export { a as b } from "..."` as `local: b, imported: a`;
+    /// `export * as ns from` has `imported: *`, `export * from` also `local: *`.
+    pub reexports: Vec<SchemaImport>,
+    pub constants: Vec<SchemaConstant>,
+    pub definition: Option<SchemaDefinition>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Table {
+    pub name: String,
+    /// `None` when the document validator could not be resolved.
+    pub document: Option<Validator>,
+    pub indexes: Vec<Index>,
+    pub search_indexes: Vec<SearchIndex>,
+    pub vector_indexes: Vec<VectorIndex>,
+    pub span: SourceSpan,
+}
+
+impl Table {
+    /// Top-level document fields, when the document is an object validator.
+    pub fn fields(&self) -> Option<&[Field]> {
+        self.document.as_ref()?.fields()
+    }
+
+    pub fn field(&self, name: &str) -> Option<&Field> {
+        self.fields()?.iter().find(|f| f.name == name)
+    }
+
+    pub fn index(&self, name: &str) -> Option<&Index> {
+        self.indexes.iter().find(|i| i.name == name)
+    }
+}
+
+/// Tables defined by `defineSchema` in `convex/schema.ts`, with validator
+/// constants and table aliases resolved across files.
+#[derive(Debug, Clone, Default)]
+pub struct SchemaModel {
+    pub file: String,
+    pub tables: Vec<Table>,
+    /// `defineSchema` spreads or table entries that could not be resolved,
+    /// so tables may exist beyond `tables`.
+    pub has_unresolved_tables: bool,
+}
+
+impl SchemaModel {
+    /// Build the model from the analyzed files. Returns `None` when no
+    /// `defineSchema` call was found in the schema module.
+    pub fn build(convex_dir: &Path, analyses: &[FileAnalysis]) -> Option<Self> {
+        let schema_file = analyses.iter().find(|a| {
+            a.schema_facts.definition.is_some()
+                && module_path(convex_dir, Path::new(&a.file_path)).as_deref() == Some("schema")
+        })?;
+        let definition = schema_file.schema_facts.definition.as_ref()?;
+        let resolver = Resolver::new(analyses);
+        let schema_path = normalize(Path::new(&schema_file.file_path));
+
+        let mut model = SchemaModel {
+            file: schema_file.file_path.clone(),
+            tables: Vec::new(),
+            has_unresolved_tables: !definition.spreads.is_empty(),
+        };
+        for (name, def) in &definition.tables {
+            match resolver.table(&schema_path, def, 0) {
+                Some(resolved) => model.tables.push(Table {
+                    name: name.clone(),
+                    document: resolved.document,
+                    indexes: resolved.indexes,
+                    search_indexes: resolved.search_indexes,
+                    vector_indexes: resolved.vector_indexes,
+                    span: def.span.clone(),
+                }),
+                None => model.has_unresolved_tables = true,
+            }
+        }
+        Some(model)
+    }
+
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    pub fn table_names(&self) -> impl Iterator<Item = &str> {
+        self.tables.iter().map(|t| t.name.as_str())
+    }
+}
+
+struct Resolver<'a> {
+    files: HashMap<PathBuf, &'a SchemaFacts>,
+}
+
+impl<'a> Resolver<'a> {
+    fn new(analyses: &'a [FileAnalysis]) -> Self {
+        Self {
+            files: analyses
+                .iter()
+                .map(|a| (normalize(Path::new(&a.file_path)), &a.schema_facts))
+                .collect(),
+        }
+    }
+
+    /// Find the constant `name` as seen from `file`, following imports and
+    /// re-exports.
+    fn lookup(
+        &self,
+        file: &Path,
+        name: &str,
+        depth: u32,
+    ) -> Option<(PathBuf, &'a SchemaConstantValue)> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        let facts = self.files.get(file)?;
+        let mut bindings = facts.imports.iter().chain(&facts.reexports);
+        if let Some((namespace, member)) = name.split_once('.') {
+            let import = bindings.find(|i| i.local == namespace && i.imported == "*")?;
+            let target = self.resolve_import(file, &import.source)?;
+            return self.lookup(&target, member, depth + 1);
+        }
+        if let Some(constant) = facts.constants.iter().find(|c| c.name == name) {
+            return Some((file.to_path_buf(), &constant.value));
+        }
+        if let Some(import) = bindings.find(|i| i.local == name && i.imported != "*") {
+            let target = self.resolve_import(file, &import.source)?;
+            return self.lookup(&target, &import.imported, depth + 1);
+        }
+        // `export * from "./fields"` makes every constant of `fields` visible here.
+        facts
+            .reexports
+            .iter()
+            .filter(|i| i.local == "*")
+            .find_map(|i| {
+                let target = self.resolve_import(file, &i.source)?;
+                self.lookup(&target, name, depth + 1)
+            })
+    }
+
+    /// Map a relative import specifier to an analyzed file.
+    fn resolve_import(&self, from: &Path, source: &str) -> Option<PathBuf> {
+        import_candidates(from, source)
+            .into_iter()
+            .find(|candidate| self.files.contains_key(candidate))
+    }
+
+    fn validator(&self, file: &Path, validator: &Validator, depth: u32) -> Validator {
+        if depth > MAX_RESOLVE_DEPTH {
+            return validator.clone();
+        }
+        match validator {
+            Validator::Reference(name) => {
+                // `v.object({...}).fields` spreads the same fields as the object itself.
+                let target = name.strip_suffix(".fields").unwrap_or(name);
+                match self.lookup(file, target, depth) {
+                    Some((target_file, SchemaConstantValue::Validator(inner))) => {
+                        self.validator(&target_file, inner, depth + 1)
+                    }
+                    _ => validator.clone(),
+                }
+            }
+            Validator::Optional(inner) => {
+                Validator::Optional(Box::new(self.validator(file, inner, depth + 1)))
+            }
+            Validator::Array(inner) => {
+                Validator::Array(Box::new(self.validator(file, inner, depth + 1)))
+            }
+            Validator::Record(key, value) => Validator::Record(
+                Box::new(self.validator(file, key, depth + 1)),
+                Box::new(self.validator(file, value, depth + 1)),
+            ),
+            Validator::Union(members) => Validator::Union(
+                members
+                    .iter()
+                    .map(|m| self.validator(file, m, depth + 1))
+                    .collect(),
+            ),
+            Validator::Object { fields, spreads } => {
+                let mut resolved_fields: Vec<Field> = Vec::new();
+                let mut unresolved = Vec::new();
+                for spread in spreads {
+                    match self.validator(file, &Validator::Reference(spread.clone()), depth + 1) {
+                        Validator::Object {
+                            fields: spread_fields,
+                            spreads: nested,
+                        } => {
+                            resolved_fields.extend(spread_fields);
+                            unresolved.extend(nested);
+                        }
+                        _ => unresolved.push(spread.clone()),
+                    }
+                }
+                for field in fields {
+                    resolved_fields.retain(|f| f.name != field.name);
+                    resolved_fields.push(Field {
+                        name: field.name.clone(),
+                        validator: self.validator(file, &field.validator, depth + 1),
+                        span: field.span.clone(),
+                    });
+                }
+                Validator::Object {
+                    fields: resolved_fields,
+                    spreads: unresolved,
+                }
+            }
+            other => other.clone(),
+        }
+    }
+
+    /// Resolve a table chain, merging indexes declared on aliases.
+    fn table(&self, file: &Path, def: &TableDef, depth: u32) -> Option<TableDef> {
+        if depth > MAX_RESOLVE_DEPTH {
+            return None;
+        }
+        let mut resolved = match &def.base {
+            None => TableDef {
+                document: def
+                    .document
+                    .as_ref()
+                    .map(|doc| self.validator(file, doc, depth + 1)),
+                ..TableDef::default()
+            },
+            Some(base) => match self.lookup(file, base, depth)? {
+                (target_file, SchemaConstantValue::Table(table)) => {
+                    self.table(&target_file, table, depth + 1)?
+                }
+                (target_file, SchemaConstantValue::Validator(Validator::Reference(alias))) => {
+                    let alias_def = TableDef {
+                        base: Some(alias.clone()),
+                        ..TableDef::default()
+                    };
+                    self.table(&target_file, &alias_def, depth + 1)?
+                }
+                (_, SchemaConstantValue::Validator(_)) => return None,
+            },
+        };
+        resolved.indexes.extend(def.indexes.iter().cloned());
+        resolved
+            .search_indexes
+            .extend(def.search_indexes.iter().cloned());
+        resolved
+            .vector_indexes
+            .extend(def.vector_indexes.iter().cloned());
+        Some(resolved)
+    }
+}
+
+/// Files a relative import specifier may refer to, most likely first.
+/// Bare package specifiers have none.
+pub(crate) fn import_candidates(from: &Path, source: &str) -> Vec<PathBuf> {
+    if !source.starts_with('.') {
+        return Vec::new();
+    }
+    let Some(dir) = from.parent() else {
+        return Vec::new();
+    };
+    let base = normalize(&dir.join(source));
+    let mut candidates = vec![base.clone()];
+    // TypeScript sources are often imported with a `.js` suffix.
+    if base.extension().is_some_and(|ext| ext == "js") {
+        candidates.push(base.with_extension("ts"));
+    }
+    for ext in ["ts", "tsx", "js", "jsx", "mts", "mjs", "cts", "cjs"] {
+        let mut with_ext = base.clone().into_os_string();
+        with_ext.push(format!(".{ext}"));
+        candidates.push(PathBuf::from(with_ext));
+        candidates.push(base.join(format!("index.{ext}")));
+    }
+    candidates
+}
+
+/// Lexically normalize a path so `./convex/../convex/a.ts` and `convex/a.ts`
+/// compare equal without touching the filesystem.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if matches!(out.components().next_back(), Some(Component::Normal(_))) {
+                    out.pop();
+                } else {
+                    out.push("..");
+                }
+            }
+            other => out.push(other.as_os_str()),
+        }
+    }
+    out
+}
@@ -1,8 +1,9 @@
 use std::collections::BTreeMap;
 use std::path::Path;
 
-use crate::diagnostic::{Category, Diagnostic, Severity};
-use crate::rules::{FileAnalysis, FunctionKind, ProjectContext, Rule};
+use crate::diagnostic::{Category, Diagnostic, Severity, TextEdit};
+use crate::rules::function_graph::{FunctionGraph, Resolution};
+use crate::rules::{CtxCall, FileAnalysis, FunctionKind, ProjectContext, Rule};
 
 fn path_has_segment(path: &str, segment: &str) -> bool {
     let normalized = path.replace('\\', "/");
@@ -19,26 +20,27 @@ impl Rule for MissingArgValidators {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Add `args: { ... }` with validators for all parameters. Convex guidance requires validators for query/mutation/action and internal variants."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .functions
             .iter()
-            .filter(|f| {
-                f.kind != FunctionKind::HttpAction && !f.has_args_validator
-            })
+            .filter(|f| f.kind != FunctionKind::HttpAction && !f.has_args_validator)
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: format!(
-                    "{} `{}` has no argument validators",
-                    f.kind_str(),
-                    f.name
-                ),
-                help: "Add `args: { ... }` with validators for all parameters. Convex guidance requires validators for query/mutation/action and internal variants.".to_string(),
+                message: format!("{} `{}` has no argument validators", f.kind_str(), f.name),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -52,6 +54,15 @@ impl Rule for MissingReturnValidators {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Add `returns: v.object({...})` to validate the return type and prevent accidental data leaks."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .functions
@@ -59,17 +70,18 @@ impl Rule for MissingReturnValidators {
             .filter(|f| f.is_public() && !f.has_return_validator)
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "{} `{}` has no return value validator",
                     f.kind_str(),
                     f.name
                 ),
-                help: "Add `returns: v.object({...})` to validate the return type and prevent accidental data leaks.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -83,6 +95,15 @@ impl Rule for MissingAuthCheck {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Consider adding `const identity = await ctx.auth.getUserIdentity()` to verify the caller is authenticated."
+    }
+    fn low_noise_hidden(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         // Skip conventional admin/migration directories — public functions in
         // _scripts/ or _internal/ are typically only called from admin tooling.
@@ -97,23 +118,24 @@ impl Rule for MissingAuthCheck {
             .iter()
             .filter(|f| {
                 f.is_public()
-                    && !f.has_auth_check
+                    && !f.checks_auth()
                     && !f.has_internal_secret
                     && !f.is_intentionally_public
             })
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Public {} `{}` does not check authentication",
                     f.kind_str(),
                     f.name
                 ),
-                help: "Consider adding `const identity = await ctx.auth.getUserIdentity()` to verify the caller is authenticated.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -127,6 +149,15 @@ impl Rule for InternalApiMisuse {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Use `internal.` instead of `api.` for server-to-server calls. Public API references expose endpoints that bypass internal access controls."
+    }
+    fn fixable(&self) -> bool {
+        true
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         let scheduler_or_run_prefixes = [
             "ctx.scheduler",
@@ -150,22 +181,72 @@ impl Rule for InternalApiMisuse {
             })
             .map(|call| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "`{}` is called with public API reference `{}`",
                     call.chain,
                     call.first_arg_chain.as_deref().unwrap_or("unknown")
                 ),
-                help: "Use `internal.` instead of `api.` for server-to-server calls. Public API references expose endpoints that bypass internal access controls.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: call.line,
                 column: call.col,
+                // Attached by `attach_internal_reference_fixes` once the
+                // target is known to be internal.
+                fix: None,
             })
             .collect()
     }
 }
 
+/// Give `security/internal-api-misuse` findings their fix when `graph`
+/// shows the referenced function is internal. Rewriting a reference to a
+/// public function would point at an `internal.*` path that does not exist.
+pub fn attach_internal_reference_fixes(
+    diagnostics: &mut [Diagnostic],
+    analyses: &[FileAnalysis],
+    graph: &FunctionGraph,
+) {
+    for diagnostic in diagnostics
+        .iter_mut()
+        .filter(|d| d.rule == "security/internal-api-misuse" && d.fix.is_none())
+    {
+        let Some(analysis) = analyses.iter().find(|a| a.file_path == diagnostic.file) else {
+            continue;
+        };
+        let Some(call) = analysis
+            .ctx_calls
+            .iter()
+            .find(|c| c.line == diagnostic.line && c.col == diagnostic.column)
+        else {
+            continue;
+        };
+        let target_is_internal = call.first_arg_chain.as_deref().is_some_and(|target| {
+            matches!(graph.resolve(target), Resolution::Found(func)
+                if !func.is_public() && func.kind != FunctionKind::HttpAction)
+        });
+        if target_is_internal {
+            diagnostic.fix = internal_reference_fix(analysis, call);
+        }
+    }
+}
+
+/// Swap the `api` root of a function reference for `internal`, importing
+/// `internal` next to `api` when the file does not import it yet.
+fn internal_reference_fix(analysis: &FileAnalysis, call: &CtxCall) -> Option<Vec<TextEdit>> {
+    let root = call.first_arg_root?;
+    let mut edits = vec![TextEdit::replace(root.start, root.end, "internal")];
+    let imports_internal = analysis.imports.iter().any(|import| {
+        import.source.ends_with("_generated/api")
+            && import.specifiers.iter().any(|s| s == "internal")
+    });
+    if !imports_internal {
+        edits.push(TextEdit::insert(analysis.api_import_end?, ", internal"));
+    }
+    Some(edits)
+}
+
 pub struct HardcodedSecrets;
 impl Rule for HardcodedSecrets {
     fn id(&self) -> &'static str {
@@ -174,19 +255,26 @@ impl Rule for HardcodedSecrets {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Use environment variables via `process.env.SECRET_NAME` instead of hardcoding secrets in source code."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .hardcoded_secrets
             .iter()
             .map(|secret| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!("Hardcoded secret detected: {}", secret.detail),
-                help: "Use environment variables via `process.env.SECRET_NAME` instead of hardcoding secrets in source code.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: secret.line,
                 column: secret.col,
+                fix: None,
             })
             .collect()
     }
@@ -201,6 +289,15 @@ impl Rule for EnvNotGitignored {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Add `.env.local` to your .gitignore to prevent committing secrets."
+    }
+    fn project_level(&self) -> bool {
+        true
+    }
     fn check(&self, _analysis: &FileAnalysis) -> Vec<Diagnostic> {
         vec![]
     }
@@ -208,14 +305,14 @@ impl Rule for EnvNotGitignored {
         if ctx.has_env_local && !ctx.env_gitignored {
             vec![Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: ".env.local exists but is not in .gitignore".to_string(),
-                help: "Add `.env.local` to your .gitignore to prevent committing secrets."
-                    .to_string(),
+                help: self.help().to_string(),
                 file: ".env.local".to_string(),
                 line: 0,
                 column: 0,
+                fix: None,
             }]
         } else {
             vec![]
@@ -231,6 +328,12 @@ impl Rule for SpoofableAccessControl {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Avoid authorizing requests using client-provided role/user identifiers. Verify access with `ctx.auth.getUserIdentity()` and server-side ownership checks."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         const SENSITIVE_ARG_NAMES: &[&str] = &[
             "userId",
@@ -252,7 +355,7 @@ impl Rule for SpoofableAccessControl {
             .iter()
             .filter(|f| {
                 f.is_public()
-                    && !f.has_auth_check
+                    && !f.checks_auth()
                     && !f.has_internal_secret
                     && !f.is_intentionally_public
             })
@@ -275,7 +378,7 @@ impl Rule for SpoofableAccessControl {
 
                 Some(Diagnostic {
                     rule: self.id().to_string(),
-                    severity: Severity::Warning,
+                    severity: self.default_severity(),
                     category: self.category(),
                     message: format!(
                         "Public {} `{}` appears to use spoofable access-control args: {}",
@@ -283,10 +386,11 @@ impl Rule for SpoofableAccessControl {
                         f.name,
                         risky_args.join(", ")
                     ),
-                    help: "Avoid authorizing requests using client-provided role/user identifiers. Verify access with `ctx.auth.getUserIdentity()` and server-side ownership checks.".to_string(),
+                    help: self.help().to_string(),
                     file: analysis.file_path.clone(),
                     line: f.span_line,
                     column: f.span_col,
+                    fix: None,
                 })
             })
             .collect()
@@ -305,22 +409,29 @@ impl Rule for MissingTableId {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Use `v.id(\"tableName\")` to prevent cross-table ID confusion. Matches the ESLint `explicit-table-ids` rule."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .generic_id_validators
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Argument validator uses `v.id()` without explicit table: {}",
                     loc.detail
                 ),
-                help: "Use `v.id(\"tableName\")` to prevent cross-table ID confusion. Matches the ESLint `explicit-table-ids` rule.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -334,6 +445,12 @@ impl Rule for MissingHttpAuth {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "HTTP actions are publicly accessible. Add `ctx.auth.getUserIdentity()` or check the Authorization header."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .functions
@@ -346,16 +463,14 @@ impl Rule for MissingHttpAuth {
             })
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
-                message: format!(
-                    "httpAction `{}` does not check authentication",
-                    f.name
-                ),
-                help: "HTTP actions are publicly accessible. Add `ctx.auth.getUserIdentity()` or check the Authorization header.".to_string(),
+                message: format!("httpAction `{}` does not check authentication", f.name),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -369,19 +484,26 @@ impl Rule for ConditionalFunctionExport {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Error
+    }
+    fn help(&self) -> &'static str {
+        "Do not condition Convex function exports on environment variables. This can cause inconsistent behavior between deployments."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .conditional_exports
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Error,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: "Conditional function export based on environment variable".to_string(),
-                help: "Do not condition Convex function exports on environment variables. This can cause inconsistent behavior between deployments.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -395,6 +517,12 @@ impl Rule for GenericMutationArgs {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Using `v.any()` defeats the purpose of validation. Use specific validators for type safety and security."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .functions
@@ -402,17 +530,18 @@ impl Rule for GenericMutationArgs {
             .filter(|f| f.is_public() && f.has_any_validator_in_args)
             .map(|f| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: format!(
                     "Public {} `{}` uses `v.any()` in argument validators",
                     f.kind_str(),
                     f.name
                 ),
-                help: "Using `v.any()` defeats the purpose of validation. Use specific validators for type safety and security.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: f.span_line,
                 column: f.span_col,
+                fix: None,
             })
             .collect()
     }
@@ -426,19 +555,26 @@ impl Rule for OverlyBroadPatch {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Passing raw client args to `ctx.db.patch` is a mass-assignment vulnerability. Destructure and pass only the allowed fields."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         analysis
             .raw_arg_patches
             .iter()
             .map(|loc| Diagnostic {
                 rule: self.id().to_string(),
-                severity: Severity::Warning,
+                severity: self.default_severity(),
                 category: self.category(),
                 message: loc.detail.clone(),
-                help: "Passing raw client args to `ctx.db.patch` is a mass-assignment vulnerability. Destructure and pass only the allowed fields.".to_string(),
+                help: self.help().to_string(),
                 file: analysis.file_path.clone(),
                 line: loc.line,
                 column: loc.col,
+                fix: None,
             })
             .collect()
     }
@@ -452,6 +588,12 @@ impl Rule for HttpMissingCors {
     fn category(&self) -> Category {
         Category::Security
     }
+    fn default_severity(&self) -> Severity {
+        Severity::Warning
+    }
+    fn help(&self) -> &'static str {
+        "Add an OPTIONS handler to support CORS preflight requests. See the Convex CORS guide."
+    }
     fn check(&self, analysis: &FileAnalysis) -> Vec<Diagnostic> {
         // Group routes by path
         let mut routes_by_path: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
@@ -483,16 +625,14 @@ impl Rule for HttpMissingCors {
                     .unwrap_or(0);
                 diagnostics.push(Diagnostic {
                     rule: self.id().to_string(),
-                    severity: Severity::Warning,
+                    severity: self.default_severity(),
                     category: self.category(),
-                    message: format!(
-                        "HTTP route `{}` has no OPTIONS handler for CORS",
-                        path
-                    ),
-                    help: "Add an OPTIONS handler to support CORS preflight requests. See the Convex CORS guide.".to_string(),
+                    message: format!("HTTP route `{}` has no OPTIONS handler for CORS", path),
+                    help: self.help().to_string(),
                     file: analysis.file_path.clone(),
                     line,
                     column: 0,
+                    fix: None,
                 });
             }
         }
@@ -33,12 +33,38 @@ pub fn compute_score(diagnostics: &[Diagnostic]) -> ScoreResult {
     let score_f64 = (100.0 - total_deduction).clamp(0.0, 100.0);
     let value = score_f64.round() as u32;
 
-    let label = match value {
+    ScoreResult {
+        value,
+        label: score_label(value),
+    }
+}
+
+/// Combine per-project scores into one, weighting each by the files it scanned.
+pub fn aggregate_score(scores: &[(ScoreResult, usize)]) -> ScoreResult {
+    let total_files: usize = scores.iter().map(|(_, files)| files).sum();
+    let value = if scores.is_empty() {
+        100
+    } else if total_files == 0 {
+        let sum: u32 = scores.iter().map(|(score, _)| score.value).sum();
+        (f64::from(sum) / scores.len() as f64).round() as u32
+    } else {
+        let weighted: f64 = scores
+            .iter()
+            .map(|(score, files)| f64::from(score.value) * *files as f64)
+            .sum();
+        (weighted / total_files as f64).round() as u32
+    };
+    ScoreResult {
+        value,
+        label: score_label(value),
+    }
+}
+
+fn score_label(value: u32) -> &'static str {
+    match value {
         85..=100 => "Healthy",
         70..=84 => "Needs attention",
         50..=69 => "Unhealthy",
         _ => "Critical",
-    };
-
-    ScoreResult { value, label }
+    }
 }
@@ -0,0 +1,255 @@
+use std::collections::HashMap;
+
+use oxc_ast::Comment;
+use serde::{Deserialize, Serialize};
+
+use crate::diagnostic::{Category, Diagnostic, Severity};
+use crate::rules::UNUSED_SUPPRESSION_RULE;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SuppressionKind {
+    /// `// convex-doctor-disable-next-line rule-id -- reason`
+    NextLine,
+    /// `// convex-doctor-disable rule-id` — open until a matching `enable` or end of file.
+    Disable,
+    /// `// convex-doctor-enable rule-id` — closes a preceding `disable` block.
+    Enable,
+    /// `/* convex-doctor-disable-file rule-id */`
+    File,
+}
+
+impl SuppressionKind {
+    fn from_keyword(keyword: &str) -> Option<Self> {
+        match keyword {
+            "convex-doctor-disable-next-line" => Some(SuppressionKind::NextLine),
+            "convex-doctor-disable" => Some(SuppressionKind::Disable),
+            "convex-doctor-enable" => Some(SuppressionKind::Enable),
+            "convex-doctor-disable-file" => Some(SuppressionKind::File),
+            _ => None,
+        }
+    }
+
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            SuppressionKind::NextLine => "convex-doctor-disable-next-line",
+            SuppressionKind::Disable => "convex-doctor-disable",
+            SuppressionKind::Enable => "convex-doctor-enable",
+            SuppressionKind::File => "convex-doctor-disable-file",
+        }
+    }
+}
+
+/// A suppression comment found in a source file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuppressionDirective {
+    pub kind: SuppressionKind,
+    /// Rule ids the directive applies to. Empty means every rule.
+    pub rules: Vec<String>,
+    pub reason: Option<String>,
+    pub line: u32,
+    pub col: u32,
+    /// Last line of the comment; `NextLine` targets the line after it.
+    pub end_line: u32,
+}
+
+/// Extract suppression directives from the parsed comments of a file.
+pub fn parse_directives(source_text: &str, comments: &[Comment]) -> Vec<SuppressionDirective> {
+    comments
+        .iter()
+        .filter_map(|comment| {
+            let span = comment.content_span();
+            let text = source_text.get(span.start as usize..span.end as usize)?;
+            let (kind, rules, reason) = parse_directive_text(text)?;
+            let (line, col) = line_col(source_text, comment.span.start);
+            let (end_line, _) = line_col(source_text, comment.span.end);
+            Some(SuppressionDirective {
+                kind,
+                rules,
+                reason,
+                line,
+                col,
+                end_line,
+            })
+        })
+        .collect()
+}
+
+fn parse_directive_text(text: &str) -> Option<(SuppressionKind, Vec<String>, Option<String>)> {
+    let text = text.trim().trim_start_matches('*').trim();
+    let (body, reason) = match text.split_once("--") {
+        Some((body, reason)) => {
+            let reason = reason.trim().trim_end_matches('*').trim();
+            (body, (!reason.is_empty()).then(|| reason.to_string()))
+        }
+        None => (text, None),
+    };
+
+    let mut tokens = body
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty());
+    let kind = SuppressionKind::from_keyword(tokens.next()?)?;
+    let rules = tokens.map(ToString::to_string).collect();
+    Some((kind, rules, reason))
+}
+
+fn line_col(source_text: &str, offset: u32) -> (u32, u32) {
+    let offset = (offset as usize).min(source_text.len());
+    let slice = &source_text[..offset];
+    let line = slice.matches('\n').count() as u32 + 1;
+    let col = match slice.rfind('\n') {
+        Some(pos) => (offset - pos) as u32,
+        None => offset as u32 + 1,
+    };
+    (line, col)
+}
+
+/// One rule (or all rules, when `rule` is `None`) suppressed over a line range.
+#[derive(Debug)]
+struct SuppressedRange {
+    rule: Option<String>,
+    start_line: u32,
+    end_line: u32,
+    directive: usize,
+    used: bool,
+}
+
+impl SuppressedRange {
+    fn matches(&self, diagnostic: &Diagnostic) -> bool {
+        self.rule
+            .as_deref()
+            .map_or(true, |rule| rule == diagnostic.rule)
+            && (self.start_line..=self.end_line).contains(&diagnostic.line)
+    }
+}
+
+fn resolve_ranges(directives: &[SuppressionDirective]) -> Vec<SuppressedRange> {
+    let rule_slots = |directive: &SuppressionDirective| -> Vec<Option<String>> {
+        if directive.rules.is_empty() {
+            vec![None]
+        } else {
+            directive.rules.iter().cloned().map(Some).collect()
+        }
+    };
+
+    let mut ranges = Vec::new();
+    let mut open: Vec<usize> = Vec::new();
+    for (index, directive) in directives.iter().enumerate() {
+        match directive.kind {
+            SuppressionKind::NextLine => {
+                let target = directive.end_line + 1;
+                for rule in rule_slots(directive) {
+                    ranges.push(SuppressedRange {
+                        rule,
+                        start_line: target,
+                        end_line: target,
+                        directive: index,
+                        used: false,
+                    });
+                }
+            }
+            SuppressionKind::File => {
+                for rule in rule_slots(directive) {
+                    ranges.push(SuppressedRange {
+                        rule,
+                        start_line: 0,
+                        end_line: u32::MAX,
+                        directive: index,
+                        used: false,
+                    });
+                }
+            }
+            SuppressionKind::Disable => {
+                for rule in rule_slots(directive) {
+                    open.push(ranges.len());
+                    ranges.push(SuppressedRange {
+                        rule,
+                        start_line: directive.end_line + 1,
+                        end_line: u32::MAX,
+                        directive: index,
+                        used: false,
+                    });
+                }
+            }
+            SuppressionKind::Enable => {
+                open.retain(|&range_index| {
+                    let range = &mut ranges[range_index];
+                    let closes = directive.rules.is_empty()
+                        || range
+                            .rule
+                            .as_ref()
+                            .is_some_and(|rule| directive.rules.contains(rule));
+                    if closes {
+                        range.end_line = directive.line.saturating_sub(1);
+                    }
+                    !closes
+                });
+            }
+        }
+    }
+    ranges
+}
+
+/// Drop diagnostics covered by suppression directives.
+///
+/// `directives_by_file` is keyed by the same path string rules use for
+/// `Diagnostic::file`. When `report_unused` is set, a diagnostic is emitted for
+/// every directive entry that did not suppress anything; `is_rule_enabled`
+/// keeps directives for rules switched off in config from being reported.
+pub fn apply_suppressions(
+    diagnostics: &mut Vec<Diagnostic>,
+    directives_by_file: &HashMap<String, Vec<SuppressionDirective>>,
+    report_unused: bool,
+    is_rule_enabled: &dyn Fn(&str) -> bool,
+) {
+    let mut ranges_by_file: HashMap<&str, Vec<SuppressedRange>> = directives_by_file
+        .iter()
+        .filter(|(_, directives)| !directives.is_empty())
+        .map(|(file, directives)| (file.as_str(), resolve_ranges(directives)))
+        .collect();
+
+    diagnostics.retain(|diagnostic| {
+        let Some(ranges) = ranges_by_file.get_mut(diagnostic.file.as_str()) else {
+            return true;
+        };
+        let mut suppressed = false;
+        for range in ranges.iter_mut().filter(|range| range.matches(diagnostic)) {
+            range.used = true;
+            suppressed = true;
+        }
+        !suppressed
+    });
+
+    if !report_unused {
+        return;
+    }
+
+    let mut files: Vec<&str> = ranges_by_file.keys().copied().collect();
+    files.sort_unstable();
+    for file in files {
+        let directives = &directives_by_file[file];
+        for range in &ranges_by_file[file] {
+            if range.used || range.rule.as_deref().is_some_and(|r| !is_rule_enabled(r)) {
+                continue;
+            }
+            let directive = &directives[range.directive];
+            let target = match &range.rule {
+                Some(rule) => format!("`{rule}`"),
+                None => "all rules".to_string(),
+            };
+            diagnostics.push(Diagnostic {
+                rule: UNUSED_SUPPRESSION_RULE.to_string(),
+                severity: Severity::Warning,
+                category: Category::Configuration,
+                message: format!(
+                    "Unused `{}` directive for {target}",
+                    directive.kind.keyword()
+                ),
+                help: "Nothing is suppressed by this directive anymore. Remove it so stale ignores don't hide future findings.".to_string(),
+                file: file.to_string(),
+                line: directive.line,
+                column: directive.col,
+                fix: None,
+            });
+        }
+    }
+}
@@ -0,0 +1,246 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::baseline::Baseline;
+use crate::cache::AnalysisCache;
+use crate::config::Config;
+use crate::diagnostic::Diagnostic;
+use crate::engine::{diagnose, discover_scoped_files, open_cache, Analyses, RunOptions};
+use crate::project::ProjectInfo;
+use crate::rules::FileScope;
+use crate::scoring::{compute_score, ScoreResult};
+
+/// Project files whose changes reload the config and re-analyze everything.
+pub(crate) const CONFIG_FILES: &[&str] = &["convex-doctor.toml", "convex.json", "package.json"];
+
+/// What a poll compares to decide whether a file changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Stamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl Stamp {
+    fn read(path: &Path) -> Option<Self> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(Stamp {
+            modified: metadata.modified().ok(),
+            len: metadata.len(),
+        })
+    }
+}
+
+/// Per-file analyses of one project kept in memory by `--watch`.
+///
+/// Changes are found by polling file modification times and sizes, so the
+/// session works on filesystems without change notifications.
+pub struct WatchSession {
+    root: PathBuf,
+    options: RunOptions,
+    project: ProjectInfo,
+    config: Config,
+    cache: Option<AnalysisCache>,
+    baseline: Option<Baseline>,
+    files: BTreeMap<PathBuf, (FileScope, Stamp)>,
+    config_stamps: Vec<Option<Stamp>>,
+    analyses: Analyses,
+    diagnostics: Vec<Diagnostic>,
+    score: ScoreResult,
+}
+
+/// The result of a poll that found changes.
+pub struct WatchUpdate {
+    /// Files added, modified or removed since the previous poll.
+    pub changed_files: Vec<PathBuf>,
+    pub new: Vec<Diagnostic>,
+    pub resolved: Vec<Diagnostic>,
+    pub previous_score: ScoreResult,
+    pub score: ScoreResult,
+}
+
+impl WatchSession {
+    /// Analyze the project at `root` and remember the results.
+    ///
+    /// `options.diff_base` is ignored: every file is watched.
+    pub fn start(root: &Path, options: &RunOptions) -> Result<Self, String> {
+        let project = ProjectInfo::detect(root)?;
+        let config = Config::load(root)?;
+        let baseline = options
+            .baseline
+            .as_deref()
+            .map(Baseline::load)
+            .transpose()?;
+        let cache = open_cache(root, options, &config);
+
+        // Stamp before analyzing so edits made during analysis show up next poll.
+        let scoped = discover_scoped_files(&project, &config);
+        let files = stamp_files(scoped.clone());
+        let analyses = Analyses::analyze(&scoped, &config, cache.as_ref());
+
+        let mut session = WatchSession {
+            root: root.to_path_buf(),
+            options: options.clone(),
+            project,
+            config,
+            cache,
+            baseline,
+            files,
+            config_stamps: config_stamps(root),
+            analyses,
+            diagnostics: Vec::new(),
+            score: compute_score(&[]),
+        };
+        session.rediagnose();
+        Ok(session)
+    }
+
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+
+    pub fn score(&self) -> &ScoreResult {
+        &self.score
+    }
+
+    pub fn files_scanned(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Re-analyze files that changed since the last poll and re-run the
+    /// project-level rules. Returns `None` when nothing changed.
+    ///
+    /// A change to `convex-doctor.toml`, `convex.json` or `package.json`
+    /// restarts the session. On error the previous results are kept, and the
+    /// same error is not returned again until the file changes once more.
+    pub fn poll(&mut self) -> Result<Option<WatchUpdate>, String> {
+        let previous_diagnostics = self.diagnostics.clone();
+        let previous_score = self.score.clone();
+
+        let changed_files = self.refresh()?;
+        if changed_files.is_empty() {
+            return Ok(None);
+        }
+
+        let (new, resolved) = diagnostic_delta(&previous_diagnostics, &self.diagnostics);
+        Ok(Some(WatchUpdate {
+            changed_files,
+            new,
+            resolved,
+            previous_score,
+            score: self.score.clone(),
+        }))
+    }
+
+    /// Bring the analyses up to date, returning the files that changed.
+    fn refresh(&mut self) -> Result<Vec<PathBuf>, String> {
+        let stamps = config_stamps(&self.root);
+        if stamps != self.config_stamps {
+            let changed = CONFIG_FILES
+                .iter()
+                .zip(stamps.iter().zip(&self.config_stamps))
+                .filter(|(_, (now, before))| now != before)
+                .map(|(file, _)| self.root.join(file))
+                .collect();
+            // Remember the new stamps first, so a config that fails to load
+            // is reported once per edit rather than on every poll.
+            self.config_stamps = stamps;
+            *self = WatchSession::start(&self.root, &self.options)?;
+            return Ok(changed);
+        }
+
+        let current = stamp_files(discover_scoped_files(&self.project, &self.config));
+        let mut changed: Vec<PathBuf> = current
+            .iter()
+            .filter(|(path, state)| self.files.get(*path) != Some(state))
+            .map(|(path, _)| path.clone())
+            .collect();
+        changed.extend(
+            self.files
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        if changed.is_empty() {
+            return Ok(changed);
+        }
+        changed.sort();
+
+        // Schema and auth config presence can change with added or removed files.
+        self.project = ProjectInfo::detect(&self.root)?;
+        for path in &changed {
+            self.analyses.remove(path);
+        }
+        let to_analyze: Vec<(PathBuf, FileScope)> = changed
+            .iter()
+            .filter_map(|path| current.get(path).map(|(scope, _)| (path.clone(), *scope)))
+            .collect();
+        self.analyses.extend(Analyses::analyze(
+            &to_analyze,
+            &self.config,
+            self.cache.as_ref(),
+        ));
+        self.files = current;
+        self.rediagnose();
+        Ok(changed)
+    }
+
+    fn rediagnose(&mut self) {
+        let mut diagnostics = diagnose(
+            &self.project,
+            &self.config,
+            &self.analyses,
+            true,
+            self.options.report_unused_suppressions,
+        );
+        if let Some(baseline) = &self.baseline {
+            baseline.filter(&self.root, &mut diagnostics);
+        }
+        self.score = compute_score(&diagnostics);
+        self.diagnostics = diagnostics;
+    }
+}
+
+fn stamp_files(files: Vec<(PathBuf, FileScope)>) -> BTreeMap<PathBuf, (FileScope, Stamp)> {
+    files
+        .into_iter()
+        .filter_map(|(path, scope)| Stamp::read(&path).map(|stamp| (path, (scope, stamp))))
+        .collect()
+}
+
+pub(crate) fn config_stamps(root: &Path) -> Vec<Option<Stamp>> {
+    CONFIG_FILES
+        .iter()
+        .map(|file| Stamp::read(&root.join(file)))
+        .collect()
+}
+
+/// Findings present only in `after`, and findings present only in `before`.
+///
+/// Findings are matched by rule, file and message, ignoring position, so
+/// edits that only move existing findings to other lines report nothing.
+pub fn diagnostic_delta(
+    before: &[Diagnostic],
+    after: &[Diagnostic],
+) -> (Vec<Diagnostic>, Vec<Diagnostic>) {
+    fn key(d: &Diagnostic) -> (&str, &str, &str) {
+        (&d.rule, &d.file, &d.message)
+    }
+    fn unmatched(from: &[Diagnostic], against: &[Diagnostic]) -> Vec<Diagnostic> {
+        let mut remaining: HashMap<(&str, &str, &str), usize> = HashMap::new();
+        for d in against {
+            *remaining.entry(key(d)).or_default() += 1;
+        }
+        from.iter()
+            .filter(|d| match remaining.get_mut(&key(d)) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    false
+                }
+                _ => true,
+            })
+            .cloned()
+            .collect()
+    }
+    (unmatched(after, before), unmatched(before, after))
+}
@@ -0,0 +1,212 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use crate::diagnostic::Diagnostic;
+use crate::engine::{run_with_options, EngineResult, RunOptions};
+use crate::project::functions_dir;
+use crate::scoring::{aggregate_score, ScoreResult};
+
+/// How deep to look for Convex projects when the root declares no workspaces.
+const MAX_SCAN_DEPTH: usize = 4;
+
+/// One Convex project analyzed as part of a workspace.
+pub struct WorkspaceProject {
+    /// Project directory relative to the workspace root, `.` for the root itself.
+    pub name: String,
+    pub root: PathBuf,
+    pub result: EngineResult,
+    pub elapsed: Duration,
+}
+
+pub struct WorkspaceResult {
+    pub projects: Vec<WorkspaceProject>,
+    /// Per-project scores averaged by files scanned.
+    pub score: ScoreResult,
+    pub files_scanned: usize,
+}
+
+impl WorkspaceResult {
+    pub fn diagnostics(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.projects
+            .iter()
+            .flat_map(|p| p.result.diagnostics.iter())
+    }
+
+    /// Projects scoring below their own `[ci] fail_below`.
+    pub fn failing_projects(&self) -> impl Iterator<Item = &WorkspaceProject> {
+        self.projects
+            .iter()
+            .filter(|p| p.result.fail_below > 0 && p.result.score.value < p.result.fail_below)
+    }
+}
+
+/// Analyze every Convex project under `root`, each with its own config.
+pub fn run_workspace(root: &Path, options: &RunOptions) -> Result<WorkspaceResult, String> {
+    let project_roots = discover_projects(root);
+    if project_roots.is_empty() {
+        return Err(format!(
+            "No Convex projects found in workspace {}",
+            root.display()
+        ));
+    }
+
+    let mut projects = Vec::new();
+    for project_root in project_roots {
+        let name = project_name(root, &project_root);
+        let start = Instant::now();
+        let result =
+            run_with_options(&project_root, options).map_err(|e| format!("{name}: {e}"))?;
+        projects.push(WorkspaceProject {
+            name,
+            root: project_root,
+            result,
+            elapsed: start.elapsed(),
+        });
+    }
+
+    let scores: Vec<(ScoreResult, usize)> = projects
+        .iter()
+        .map(|p| (p.result.score.clone(), p.result.files_scanned))
+        .collect();
+    Ok(WorkspaceResult {
+        score: aggregate_score(&scores),
+        files_scanned: projects.iter().map(|p| p.result.files_scanned).sum(),
+        projects,
+    })
+}
+
+/// Convex projects under `root`, sorted by path.
+///
+/// Packages come from `package.json` workspaces and `pnpm-workspace.yaml`;
+/// without either, the tree is scanned a few levels deep. A package is a
+/// Convex project when its functions directory (see `convex.json`) exists.
+pub fn discover_projects(root: &Path) -> Vec<PathBuf> {
+    let mut candidates = vec![root.to_path_buf()];
+    match workspace_patterns(root) {
+        Some(patterns) => candidates.extend(expand_patterns(root, &patterns)),
+        None => scan_dirs(root, 0, &mut candidates),
+    }
+
+    let mut projects: Vec<PathBuf> = candidates
+        .into_iter()
+        .filter(|dir| functions_dir(dir).is_dir())
+        .collect();
+    projects.sort();
+    projects.dedup();
+    projects
+}
+
+/// Package globs declared by the workspace root, or `None` when it declares none.
+fn workspace_patterns(root: &Path) -> Option<Vec<String>> {
+    let mut patterns = Vec::new();
+    let mut declared = false;
+
+    if let Some(json) = std::fs::read_to_string(root.join("package.json"))
+        .ok()
+        .and_then(|contents| serde_json::from_str::<serde_json::Value>(&contents).ok())
+    {
+        // `"workspaces": [...]` or Yarn's `"workspaces": { "packages": [...] }`.
+        let workspaces = json.get("workspaces");
+        let list = workspaces
+            .and_then(|w| w.as_array())
+            .or_else(|| workspaces.and_then(|w| w.get("packages")?.as_array()));
+        if let Some(list) = list {
+            declared = true;
+            patterns.extend(list.iter().filter_map(|p| p.as_str()).map(String::from));
+        }
+    }
+
+    if let Ok(contents) = std::fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+        declared = true;
+        patterns.extend(pnpm_packages(&contents));
+    }
+
+    declared.then_some(patterns)
+}
+
+/// Entries of the top-level `packages:` list in `pnpm-workspace.yaml`.
+fn pnpm_packages(contents: &str) -> Vec<String> {
+    let mut packages = Vec::new();
+    let mut in_packages = false;
+    for line in contents.lines() {
+        let trimmed = line.split(" #").next().unwrap_or_default().trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = trimmed == "packages:";
+            continue;
+        }
+        if let Some(entry) = trimmed.strip_prefix('-').filter(|_| in_packages) {
+            let entry = entry.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !entry.is_empty() {
+                packages.push(entry.to_string());
+            }
+        }
+    }
+    packages
+}
+
+/// Directories matching the package globs, minus `!`-negated ones.
+fn expand_patterns(root: &Path, patterns: &[String]) -> Vec<PathBuf> {
+    let matches = |pattern: &str| -> Vec<PathBuf> {
+        let pattern = pattern.trim_start_matches("./").trim_end_matches('/');
+        let full = root.join(pattern).to_string_lossy().replace('\\', "/");
+        glob::glob(&full)
+            .map(|paths| {
+                paths
+                    .flatten()
+                    .filter(|path| path.is_dir())
+                    .filter(|path| !path.components().any(|c| c.as_os_str() == "node_modules"))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+
+    let excluded: Vec<PathBuf> = patterns
+        .iter()
+        .filter_map(|p| p.strip_prefix('!'))
+        .flat_map(matches)
+        .collect();
+    patterns
+        .iter()
+        .filter(|p| !p.starts_with('!'))
+        .flat_map(|p| matches(p))
+        .filter(|dir| !excluded.contains(dir))
+        .collect()
+}
+
+fn scan_dirs(dir: &Path, depth: usize, dirs: &mut Vec<PathBuf>) {
+    if depth >= MAX_SCAN_DEPTH {
+        return;
+    }
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    let functions = functions_dir(dir);
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let skipped = path.file_name().is_some_and(|n| {
+            let name = n.to_string_lossy();
+            name == "node_modules" || name.starts_with('.')
+        });
+        if !path.is_dir() || skipped || path == functions {
+            continue;
+        }
+        dirs.push(path.clone());
+        scan_dirs(&path, depth + 1, dirs);
+    }
+}
+
+fn project_name(root: &Path, project_root: &Path) -> String {
+    let relative = project_root
+        .strip_prefix(root)
+        .unwrap_or(project_root)
+        .to_string_lossy()
+        .replace('\\', "/");
+    if relative.is_empty() {
+        ".".to_string()
+    } else {
+        relative
+    }
+}
@@ -99,3 +99,24 @@ fn test_multiple_exported_declarators_are_tracked() {
     assert!(names.contains(&"foo"));
     assert!(names.contains(&"bar"));
 }
+
+#[test]
+fn test_builders_are_resolved_through_import_bindings() {
+    use convex_doctor::rules::FunctionKind;
+
+    let analysis = analyze_file(Path::new("tests/fixtures/renamed_builders.ts")).unwrap();
+    let functions: Vec<_> = analysis
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.kind.clone()))
+        .collect();
+    assert_eq!(
+        functions,
+        vec![
+            ("list", FunctionKind::Query),
+            ("send", FunctionKind::Mutation),
+            ("cleanup", FunctionKind::InternalMutation),
+            ("notify", FunctionKind::Action),
+        ]
+    );
+}
@@ -18,6 +18,10 @@ fn test_large_handler_flagged() {
             handler_line_count: 80,
             span_line: 1,
             span_col: 1,
+            end_line: 1,
+            helper_calls: vec![],
+            auth_helper: None,
+            imported_builder: None,
         }],
         ..Default::default()
     };
@@ -44,6 +48,10 @@ fn test_small_handler_not_flagged() {
             handler_line_count: 20,
             span_line: 1,
             span_col: 1,
+            end_line: 1,
+            helper_calls: vec![],
+            auth_helper: None,
+            imported_builder: None,
         }],
         ..Default::default()
     };
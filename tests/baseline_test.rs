@@ -0,0 +1,172 @@
+use std::process::Command;
+
+use convex_doctor::baseline::Baseline;
+use convex_doctor::engine::{run, run_with_options, RunOptions};
+use tempfile::TempDir;
+
+const MESSAGES: &str = r#"import { query } from "convex/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").collect();
+  },
+});
+"#;
+
+fn project_with(source: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("messages.ts"), source).unwrap();
+    dir
+}
+
+fn write_baseline(dir: &TempDir) -> std::path::PathBuf {
+    let result = run(dir.path(), false, None).unwrap();
+    assert!(!result.diagnostics.is_empty());
+    let baseline_path = dir.path().join("convex-doctor-baseline.json");
+    Baseline::from_diagnostics(dir.path(), &result.diagnostics)
+        .save(&baseline_path)
+        .unwrap();
+    baseline_path
+}
+
+#[test]
+fn test_baseline_entries_are_relative_and_hashed() {
+    let dir = project_with(MESSAGES);
+    let baseline_path = write_baseline(&dir);
+    let baseline = Baseline::load(&baseline_path).unwrap();
+
+    let collect = baseline
+        .entries
+        .iter()
+        .find(|e| e.rule == "perf/unbounded-collect")
+        .expect("unbounded collect should be recorded");
+    assert_eq!(collect.file, "convex/messages.ts");
+    assert_eq!(collect.snippet_hash.len(), 16);
+}
+
+#[test]
+fn test_baseline_hides_existing_findings_after_line_shift() {
+    let dir = project_with(MESSAGES);
+    let baseline_path = write_baseline(&dir);
+    let recorded = Baseline::load(&baseline_path).unwrap().entries.len();
+
+    // Shift every finding down and re-indent; the snippet hash should still match.
+    let shifted = format!(
+        "// header\n\n{}",
+        MESSAGES.replace("    return", "      return")
+    );
+    std::fs::write(dir.path().join("convex/messages.ts"), shifted).unwrap();
+
+    let options = RunOptions {
+        baseline: Some(baseline_path),
+        ..RunOptions::default()
+    };
+    let result = run_with_options(dir.path(), &options).unwrap();
+    assert!(result.diagnostics.is_empty(), "{:?}", result.diagnostics);
+    assert_eq!(result.baseline_suppressed, Some(recorded));
+    assert_eq!(result.score.value, 100);
+}
+
+#[test]
+fn test_baseline_reports_new_findings() {
+    let dir = project_with(MESSAGES);
+    let baseline_path = write_baseline(&dir);
+
+    let with_new = MESSAGES.replace(
+        "    return await ctx.db.query(\"messages\").collect();",
+        "    await ctx.db.query(\"users\").collect();\n    return await ctx.db.query(\"messages\").collect();",
+    );
+    std::fs::write(dir.path().join("convex/messages.ts"), with_new).unwrap();
+
+    let options = RunOptions {
+        baseline: Some(baseline_path),
+        ..RunOptions::default()
+    };
+    let result = run_with_options(dir.path(), &options).unwrap();
+    let collects: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "perf/unbounded-collect")
+        .collect();
+    assert_eq!(collects.len(), 1);
+    assert_eq!(collects[0].line, 6);
+    assert!(result.score.value < 100);
+}
+
+#[test]
+fn test_baseline_keeps_file_level_findings_when_first_line_changes() {
+    let mixed = MESSAGES.replace(
+        "import { query } from \"convex/server\";",
+        "import { internalQuery, query } from \"convex/server\";",
+    ) + "\nexport const count = internalQuery({ args: {}, handler: async () => 0 });\n";
+    let dir = project_with(&mixed);
+    let baseline_path = write_baseline(&dir);
+    let baseline = Baseline::load(&baseline_path).unwrap();
+    assert!(baseline
+        .entries
+        .iter()
+        .any(|e| e.rule == "arch/mixed-function-types"));
+
+    let edited = mixed.replace(
+        "import { internalQuery, query }",
+        "import { internalQuery, mutation, query }",
+    );
+    std::fs::write(dir.path().join("convex/messages.ts"), edited).unwrap();
+
+    let options = RunOptions {
+        baseline: Some(baseline_path),
+        ..RunOptions::default()
+    };
+    let result = run_with_options(dir.path(), &options).unwrap();
+    assert!(
+        result
+            .diagnostics
+            .iter()
+            .all(|d| d.rule != "arch/mixed-function-types"),
+        "{:?}",
+        result.diagnostics
+    );
+}
+
+#[test]
+fn test_missing_baseline_is_an_error() {
+    let dir = project_with(MESSAGES);
+    let options = RunOptions {
+        baseline: Some(dir.path().join("missing.json")),
+        ..RunOptions::default()
+    };
+    let err = run_with_options(dir.path(), &options)
+        .err()
+        .expect("missing baseline should fail");
+    assert!(err.contains("Failed to read baseline"), "{err}");
+}
+
+#[test]
+fn test_cli_write_then_apply_baseline() {
+    let dir = project_with(MESSAGES);
+    let baseline_path = dir.path().join("baseline.json");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .arg("--write-baseline")
+        .arg(&baseline_path)
+        .arg(dir.path())
+        .output()
+        .expect("binary should run");
+    assert!(output.status.success());
+    assert!(baseline_path.exists());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["--format", "json", "--baseline"])
+        .arg(&baseline_path)
+        .arg(dir.path())
+        .output()
+        .expect("binary should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(json["diagnostics"].as_array().unwrap().len(), 0);
+    assert!(json["summary"]["baseline_suppressed"].as_u64().unwrap() > 0);
+    assert_eq!(json["score"]["value"], 100);
+}
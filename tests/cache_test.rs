@@ -0,0 +1,111 @@
+use convex_doctor::cache::AnalysisCache;
+use convex_doctor::config::AuthConfig;
+use convex_doctor::engine::{run_with_options, RunOptions};
+use convex_doctor::rules::context::analyze_file;
+use tempfile::TempDir;
+
+const QUERY: &str = r#"
+import { query } from "./_generated/server";
+
+export const list = query({
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").collect();
+  },
+});
+"#;
+
+#[test]
+fn test_cache_reuses_analysis_of_unchanged_file() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("messages.ts");
+    std::fs::write(&file, QUERY).unwrap();
+    let cache_dir = dir.path().join("cache");
+
+    let first = AnalysisCache::new(&cache_dir, "");
+    let fresh = first.analyze(&file, &AuthConfig::default()).unwrap();
+    assert_eq!((first.hits(), first.misses()), (0, 1));
+
+    let second = AnalysisCache::new(&cache_dir, "");
+    let cached = second.analyze(&file, &AuthConfig::default()).unwrap();
+    assert_eq!((second.hits(), second.misses()), (1, 0));
+    assert_eq!(
+        serde_json::to_value(&cached).unwrap(),
+        serde_json::to_value(&fresh).unwrap()
+    );
+    assert_eq!(
+        serde_json::to_value(&cached).unwrap(),
+        serde_json::to_value(analyze_file(&file).unwrap()).unwrap()
+    );
+    assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 1);
+}
+
+#[test]
+fn test_cache_reanalyzes_changed_file() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("messages.ts");
+    std::fs::write(&file, QUERY).unwrap();
+    let cache_dir = dir.path().join("cache");
+    AnalysisCache::new(&cache_dir, "")
+        .analyze(&file, &AuthConfig::default())
+        .unwrap();
+
+    std::fs::write(&file, QUERY.replace("list", "all")).unwrap();
+    let cache = AnalysisCache::new(&cache_dir, "");
+    let analysis = cache.analyze(&file, &AuthConfig::default()).unwrap();
+    assert_eq!(cache.misses(), 1);
+    assert_eq!(analysis.functions[0].name, "all");
+    // The entry is replaced rather than accumulated.
+    assert_eq!(std::fs::read_dir(&cache_dir).unwrap().count(), 1);
+}
+
+#[test]
+fn test_cache_is_keyed_by_config() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("messages.ts");
+    std::fs::write(&file, QUERY).unwrap();
+    let cache_dir = dir.path().join("cache");
+    AnalysisCache::new(&cache_dir, "a")
+        .analyze(&file, &AuthConfig::default())
+        .unwrap();
+
+    let cache = AnalysisCache::new(&cache_dir, "b");
+    cache.analyze(&file, &AuthConfig::default()).unwrap();
+    assert_eq!(cache.misses(), 1);
+}
+
+#[test]
+fn test_cache_does_not_store_parse_failures() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("broken.unknown");
+    std::fs::write(&file, "export const x = ;").unwrap();
+    let cache_dir = dir.path().join("cache");
+
+    assert!(AnalysisCache::new(&cache_dir, "")
+        .analyze(&file, &AuthConfig::default())
+        .is_err());
+    assert!(!cache_dir.exists());
+}
+
+#[test]
+fn test_engine_with_cache_matches_uncached_run() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("messages.ts"), QUERY).unwrap();
+    let options = RunOptions {
+        cache: true,
+        ..RunOptions::default()
+    };
+
+    let uncached = run_with_options(dir.path(), &RunOptions::default()).unwrap();
+    let first = run_with_options(dir.path(), &options).unwrap();
+    let second = run_with_options(dir.path(), &options).unwrap();
+
+    assert!(dir
+        .path()
+        .join("node_modules/.cache/convex-doctor")
+        .is_dir());
+    assert_eq!(first.diagnostics, uncached.diagnostics);
+    assert_eq!(second.diagnostics, uncached.diagnostics);
+    assert_eq!(second.score.value, uncached.score.value);
+}
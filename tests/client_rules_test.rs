@@ -402,6 +402,7 @@ fn mutation_in_render_severity_is_error_when_triggered() {
             line: 1,
             col: 1,
             in_render_body: true,
+            function_ref: None,
         });
     let diags = MutationInRender.check(&analysis);
     assert!(!diags.is_empty());
@@ -10,6 +10,7 @@ fn test_default_config() {
     assert!(config.rules.is_empty());
     assert_eq!(config.ignore.files, vec!["convex/_generated/**"]);
     assert_eq!(config.convex.guidance_version, "v0.241.0");
+    assert!(config.client.roots.is_empty());
 }
 
 #[test]
@@ -27,6 +28,9 @@ files = ["convex/_generated/**", "convex/test/**"]
 
 [ci]
 fail_below = 70
+
+[client]
+roots = ["web"]
 "#,
     )
     .unwrap();
@@ -35,6 +39,7 @@ fail_below = 70
     assert_eq!(config.rules.get("perf/unbounded-collect").unwrap(), "off");
     assert_eq!(config.ignore.files.len(), 2);
     assert_eq!(config.ci.fail_below, 70);
+    assert_eq!(config.client.roots, vec!["web"]);
 }
 
 #[test]
@@ -141,6 +146,7 @@ strictness = "strict"
         file: "convex/messages.ts".to_string(),
         line: 1,
         column: 1,
+        fix: None,
     }];
     config.apply_strictness(&mut diagnostics);
     assert_eq!(diagnostics[0].severity, Severity::Warning);
@@ -170,6 +176,7 @@ strictness = "low_noise"
             file: "convex/messages.ts".to_string(),
             line: 1,
             column: 1,
+            fix: None,
         },
         Diagnostic {
             rule: "security/missing-arg-validators".to_string(),
@@ -180,6 +187,7 @@ strictness = "low_noise"
             file: "convex/messages.ts".to_string(),
             line: 1,
             column: 1,
+            fix: None,
         },
         Diagnostic {
             rule: "arch/no-convex-error".to_string(),
@@ -190,6 +198,7 @@ strictness = "low_noise"
             file: "convex/messages.ts".to_string(),
             line: 1,
             column: 1,
+            fix: None,
         },
     ];
     config.apply_strictness(&mut diagnostics);
@@ -197,3 +206,169 @@ strictness = "low_noise"
     assert_eq!(diagnostics.len(), 1);
     assert_eq!(diagnostics[0].rule, "security/missing-arg-validators");
 }
+
+#[test]
+fn test_rule_severity_overrides() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[rules]
+"security/missing-auth-check" = "error"
+"arch/large-handler" = "info"
+"perf/unbounded-collect" = "off"
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(dir.path()).unwrap();
+    assert_eq!(
+        config.severity_override("security/missing-auth-check"),
+        Some(Severity::Error)
+    );
+    assert_eq!(
+        config.severity_override("arch/large-handler"),
+        Some(Severity::Info)
+    );
+    assert_eq!(config.severity_override("perf/unbounded-collect"), None);
+    assert_eq!(config.severity_override("perf/filter-without-index"), None);
+
+    let mut diagnostics = vec![Diagnostic {
+        rule: "security/missing-auth-check".to_string(),
+        severity: Severity::Warning,
+        category: Category::Security,
+        message: "msg".to_string(),
+        help: "help".to_string(),
+        file: "convex/messages.ts".to_string(),
+        line: 1,
+        column: 1,
+        fix: None,
+    }];
+    config.apply_severity_overrides(&mut diagnostics);
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+}
+
+#[test]
+fn test_unknown_rule_setting_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[rules]
+"security/missing-auth-check" = "critical"
+"#,
+    )
+    .unwrap();
+
+    let err = Config::load(dir.path()).unwrap_err();
+    assert!(err.contains("security/missing-auth-check"), "{err}");
+    assert!(err.contains("critical"), "{err}");
+    assert!(err.contains("error, warning, info, off"), "{err}");
+}
+
+#[test]
+fn test_unknown_rule_id_is_rejected_with_valid_ids() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[rules]
+"security/missing-auth" = "off"
+"#,
+    )
+    .unwrap();
+
+    let err = Config::load(dir.path()).unwrap_err();
+    assert!(
+        err.contains("unknown rule `security/missing-auth`"),
+        "{err}"
+    );
+    assert!(err.contains("security/missing-auth-check"), "{err}");
+    assert!(err.contains("correctness/file-parse-error"), "{err}");
+}
+
+#[test]
+fn test_severity_override_wins_over_strictness() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[rules]
+"arch/no-convex-error" = "info"
+"arch/large-handler" = "warning"
+
+[convex]
+strictness = "low_noise"
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(dir.path()).unwrap();
+    let make = |rule: &str, severity: Severity| Diagnostic {
+        rule: rule.to_string(),
+        severity,
+        category: Category::Architecture,
+        message: "msg".to_string(),
+        help: "help".to_string(),
+        file: "convex/messages.ts".to_string(),
+        line: 1,
+        column: 1,
+        fix: None,
+    };
+    let mut diagnostics = vec![
+        make("arch/no-convex-error", Severity::Info),
+        make("arch/large-handler", Severity::Warning),
+        make("arch/mixed-function-types", Severity::Info),
+    ];
+    config.apply_strictness(&mut diagnostics);
+    config.apply_severity_overrides(&mut diagnostics);
+
+    let rules: Vec<&str> = diagnostics.iter().map(|d| d.rule.as_str()).collect();
+    assert_eq!(rules, vec!["arch/no-convex-error", "arch/large-handler"]);
+    assert_eq!(diagnostics[0].severity, Severity::Info);
+}
+
+#[test]
+fn test_auth_settings_load_and_change_analysis_key() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[auth]
+helpers = ["getViewer", "mustGetCurrentUser"]
+wrappers = ["authedQuery", "internalAdminMutation"]
+"#,
+    )
+    .unwrap();
+
+    let config = Config::load(dir.path()).unwrap();
+    assert!(config.auth.is_helper("getViewer"));
+    assert!(!config.auth.is_helper("getUser"));
+    assert_eq!(
+        config.auth.wrapper_kind("authedQuery"),
+        Some(convex_doctor::rules::FunctionKind::Query)
+    );
+    assert_eq!(
+        config.auth.wrapper_kind("internalAdminMutation"),
+        Some(convex_doctor::rules::FunctionKind::InternalMutation)
+    );
+    assert_eq!(config.auth.wrapper_kind("authedAction"), None);
+    assert_ne!(config.analysis_key(), Config::default().analysis_key());
+}
+
+#[test]
+fn test_auth_wrapper_without_function_kind_is_rejected() {
+    let dir = TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[auth]
+wrappers = ["withAuth"]
+"#,
+    )
+    .unwrap();
+
+    let err = Config::load(dir.path()).unwrap_err();
+    assert!(err.contains("`withAuth`"), "{err}");
+    assert!(err.contains("query, mutation or action"), "{err}");
+}
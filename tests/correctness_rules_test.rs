@@ -87,6 +87,30 @@ fn test_old_function_syntax_rule_emits_diagnostics() {
     );
 }
 
+#[test]
+fn test_old_function_syntax_ignores_http_action() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("http.ts");
+    std::fs::write(
+        &path,
+        r#"
+import { httpAction } from "./_generated/server";
+
+export const hook = httpAction(async (ctx, request) => {
+  return new Response("ok");
+});
+"#,
+    )
+    .unwrap();
+
+    let analysis = analyze_file(&path).unwrap();
+    let diagnostics = OldFunctionSyntax.check(&analysis);
+    assert!(
+        diagnostics.is_empty(),
+        "httpAction only supports the function form and should not be flagged"
+    );
+}
+
 #[test]
 fn test_unwaited_promise_not_flagged_when_awaited_via_variable() {
     let dir = TempDir::new().unwrap();
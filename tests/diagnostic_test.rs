@@ -11,6 +11,7 @@ fn test_diagnostic_creation() {
         file: "convex/messages.ts".to_string(),
         line: 14,
         column: 1,
+        fix: None,
     };
     assert_eq!(d.rule, "security/missing-arg-validators");
     assert_eq!(d.severity, Severity::Error);
@@ -50,6 +51,7 @@ fn test_diagnostic_serialization() {
         file: "convex/messages.ts".to_string(),
         line: 22,
         column: 10,
+        fix: None,
     };
     let json = serde_json::to_string(&d).unwrap();
     assert!(json.contains("\"rule\":\"perf/unbounded-collect\""));
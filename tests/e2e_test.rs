@@ -28,7 +28,7 @@ fn test_e2e_json_output() {
         convex_doctor::engine::run(Path::new("tests/fixtures/sample_project"), false, None)
             .unwrap();
 
-    let reporter = JsonReporter;
+    let reporter = JsonReporter::default();
     let json_str = reporter.format(
         &result.diagnostics,
         &result.score,
@@ -207,3 +207,323 @@ export const deletedMessage = 1;
     let result = convex_doctor::engine::run(dir.path(), false, Some("HEAD")).unwrap();
     assert_eq!(result.files_scanned, 0);
 }
+
+#[test]
+fn test_engine_applies_rule_severity_overrides_before_scoring() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(
+        convex_dir.join("messages.ts"),
+        r#"
+import { query } from "convex/server";
+import { v } from "convex/values";
+
+export const getMessages = query({
+  args: {},
+  returns: v.null(),
+  handler: async (ctx) => {
+    return null;
+  },
+});
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        r#"
+[rules]
+"security/missing-auth-check" = "error"
+"#,
+    )
+    .unwrap();
+
+    let result = convex_doctor::engine::run(dir.path(), false, None).unwrap();
+    let auth = result
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "security/missing-auth-check")
+        .expect("missing-auth-check should fire");
+    assert_eq!(auth.severity, convex_doctor::diagnostic::Severity::Error);
+    assert_eq!(
+        result.score.value,
+        convex_doctor::scoring::compute_score(&result.diagnostics).value
+    );
+}
+
+#[test]
+fn test_engine_rejects_unknown_rule_in_config() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("convex")).unwrap();
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        "[rules]\n\"perf/not-a-rule\" = \"off\"\n",
+    )
+    .unwrap();
+
+    let err = convex_doctor::engine::run(dir.path(), false, None)
+        .err()
+        .expect("unknown rule ids should fail config loading");
+    assert!(err.contains("perf/not-a-rule"));
+}
+
+#[test]
+fn test_engine_runs_only_client_rules_on_client_files() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(
+        dir.path().join("package.json"),
+        r#"{"devDependencies": {"vite": "5.0.0"}}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        convex_dir.join("messages.ts"),
+        r#"
+import { query, mutation } from "./_generated/server";
+import { v } from "convex/values";
+
+export const list = query({
+  args: {},
+  returns: v.null(),
+  handler: async (ctx) => {
+    return null;
+  },
+});
+
+export const send = mutation({
+  args: {},
+  returns: v.null(),
+  handler: async (ctx) => {
+    return null;
+  },
+});
+"#,
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(
+        dir.path().join("src/Chat.tsx"),
+        r#"
+import { useQuery, useMutation } from "convex/react";
+import { api } from "../convex/_generated/api";
+
+export function Chat() {
+  const messages = useQuery(api.messages.list);
+  const send = useMutation(api.messages.send);
+  send({});
+  console.log(messages);
+  return null;
+}
+"#,
+    )
+    .unwrap();
+
+    let result = convex_doctor::engine::run(dir.path(), false, None).unwrap();
+    assert_eq!(result.files_scanned, 2);
+
+    let client_file_rules: Vec<&str> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.file.ends_with("Chat.tsx"))
+        .map(|d| d.rule.as_str())
+        .collect();
+    assert!(client_file_rules.contains(&"client/unhandled-loading-state"));
+    assert!(client_file_rules.contains(&"client/missing-convex-provider"));
+    assert!(
+        client_file_rules.iter().all(|r| r.starts_with("client/")),
+        "server rules should not run on client files: {client_file_rules:?}"
+    );
+    assert!(result
+        .diagnostics
+        .iter()
+        .filter(|d| d.file.ends_with("messages.ts"))
+        .all(|d| !d.rule.starts_with("client/")));
+}
+
+#[test]
+fn test_engine_uses_functions_dir_from_convex_json() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("src/convex");
+    std::fs::create_dir_all(&convex_dir).unwrap();
+    std::fs::write(
+        dir.path().join("convex.json"),
+        r#"{"functions": "src/convex/"}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        convex_dir.join("messages.ts"),
+        r#"
+import { query } from "./_generated/server";
+
+export const list = query({
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").collect();
+  },
+});
+"#,
+    )
+    .unwrap();
+
+    let result = convex_doctor::engine::run(dir.path(), false, None).unwrap();
+    assert_eq!(result.files_scanned, 1);
+    let generated = result
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "config/missing-generated-code")
+        .expect("missing-generated-code should fire");
+    assert_eq!(generated.file, "src/convex/");
+    assert_eq!(
+        generated.message,
+        "Missing src/convex/_generated/ directory"
+    );
+    let schema = result
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "schema/missing-schema")
+        .expect("missing-schema should fire");
+    assert_eq!(schema.file, "src/convex/");
+    assert!(schema.help.contains("src/convex/schema.ts"));
+}
+
+#[test]
+fn test_unknown_table_help_names_the_configured_schema_file() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("src/convex");
+    std::fs::create_dir_all(&convex_dir).unwrap();
+    std::fs::write(
+        dir.path().join("convex.json"),
+        r#"{"functions": "src/convex/"}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        convex_dir.join("schema.ts"),
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+
+export default defineSchema({
+  messages: defineTable({ body: v.string() }),
+});
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        convex_dir.join("messages.ts"),
+        r#"
+import { query } from "./_generated/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx) => {
+    return await ctx.db.query("mesages").take(10);
+  },
+});
+"#,
+    )
+    .unwrap();
+
+    let result = convex_doctor::engine::run(dir.path(), false, None).unwrap();
+    let unknown = result
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "correctness/unknown-table")
+        .expect("unknown-table should fire");
+    assert!(unknown.help.contains("`src/convex/schema.ts`"));
+    assert!(!unknown.help.contains("`convex/schema.ts`"));
+}
+
+#[test]
+fn test_parse_diff_hunks_reads_new_file_line_ranges() {
+    let diff = r#"diff --git a/convex/a.ts b/convex/a.ts
+index 1111111..2222222 100644
+--- a/convex/a.ts
++++ b/convex/a.ts
+@@ -3 +3 @@ export const a = query({
+-  args: {},
++  args: { id: v.string() },
+@@ -10,2 +9,0 @@ export const b = query({
+-  // removed
+-+++ b/not-a-header.ts
+@@ -20,0 +19,3 @@
++one
++two
++three
+\ No newline at end of file
+diff --git a/convex/gone.ts b/convex/gone.ts
+deleted file mode 100644
+--- a/convex/gone.ts
++++ /dev/null
+@@ -1 +0,0 @@
+-export const gone = 1;
+"#;
+    let hunks = convex_doctor::engine::parse_diff_hunks(diff);
+    assert_eq!(hunks.len(), 1);
+    assert_eq!(hunks["convex/a.ts"], vec![(3, 3), (10, 9), (19, 21)]);
+}
+
+#[test]
+fn test_engine_diff_lines_keeps_only_changed_lines_and_functions() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    let source = r#"import { query } from "convex/server";
+import { v } from "convex/values";
+
+export const legacy = query({
+  args: {},
+  returns: v.any(),
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").collect();
+  },
+});
+
+export const touched = query({
+  args: {},
+  returns: v.any(),
+  handler: async (ctx) => {
+    const limit = 1;
+    return await ctx.db.query("messages").collect();
+  },
+});
+"#;
+    std::fs::write(convex_dir.join("messages.ts"), source).unwrap();
+    init_git_repo(dir.path());
+    std::fs::write(
+        convex_dir.join("messages.ts"),
+        source.replace("const limit = 1;", "const limit = 2;"),
+    )
+    .unwrap();
+
+    let run = |diff_lines| {
+        convex_doctor::engine::run_with_options(
+            dir.path(),
+            &convex_doctor::engine::RunOptions {
+                diff_base: Some("HEAD".to_string()),
+                diff_lines,
+                ..Default::default()
+            },
+        )
+        .unwrap()
+    };
+
+    let whole_file = run(false);
+    assert!(whole_file.diagnostics.iter().any(|d| d.line == 4));
+    assert!(whole_file.diagnostics.iter().any(|d| d.line == 17));
+
+    let lines = run(true);
+    assert_eq!(lines.files_scanned, 1);
+    assert!(!lines.diagnostics.is_empty());
+    assert!(
+        lines.diagnostics.iter().all(|d| d.line >= 12),
+        "findings in the untouched function should be dropped: {:?}",
+        lines.diagnostics
+    );
+    // Reported on `touched`'s definition line, which itself is unchanged.
+    assert!(lines
+        .diagnostics
+        .iter()
+        .any(|d| d.line == 12 && d.rule == "security/missing-auth-check"));
+    // A line-level finding on an unchanged line of the changed function.
+    assert!(!lines.diagnostics.iter().any(|d| d.line == 17));
+}
@@ -0,0 +1,131 @@
+use convex_doctor::diagnostic::{Category, Severity};
+use convex_doctor::explain::{explain, list_rules};
+use convex_doctor::rules::docs::{guidelines_for, rule_doc};
+use convex_doctor::rules::{RuleMetadata, RuleRegistry, UNUSED_SUPPRESSION_RULE};
+
+fn all_rules() -> Vec<RuleMetadata> {
+    RuleRegistry::new().metadata()
+}
+
+#[test]
+fn test_every_rule_is_documented() {
+    let rules = all_rules();
+    for rule in &rules {
+        let doc = rule_doc(rule.id).unwrap_or_else(|| panic!("{} has no docs entry", rule.id));
+        assert!(!doc.summary.is_empty(), "{} has no summary", rule.id);
+        assert!(
+            !doc.rationale.trim().is_empty(),
+            "{} has no rationale",
+            rule.id
+        );
+        assert!(!doc.bad.trim().is_empty(), "{} has no bad example", rule.id);
+        assert!(
+            !doc.good.trim().is_empty(),
+            "{} has no good example",
+            rule.id
+        );
+    }
+
+    let docs: toml::Value = toml::from_str(include_str!("../src/rules/docs.toml")).unwrap();
+    for entry in docs["rules"].as_array().unwrap() {
+        let id = entry["id"].as_str().unwrap();
+        assert!(
+            rules.iter().any(|r| r.id == id),
+            "docs entry for unknown rule {id}"
+        );
+    }
+}
+
+#[test]
+fn test_rule_metadata_flags() {
+    let rules = all_rules();
+    let rule = |id: &str| rules.iter().find(|r| r.id == id).unwrap();
+
+    let unwaited = rule("correctness/unwaited-promise");
+    assert!(unwaited.fixable);
+    assert!(!unwaited.project_level);
+    assert_eq!(unwaited.default_severity, Severity::Error);
+    assert!(rules.iter().any(|r| !r.guidance_ids.is_empty()));
+
+    assert!(rule("correctness/unknown-table").project_level);
+    assert!(rule("schema/missing-schema").project_level);
+    assert!(rule("config/missing-convex-json").project_level);
+    assert!(rule("perf/sequential-run-calls").low_noise_hidden);
+    assert!(!rule("correctness/unwaited-promise").low_noise_hidden);
+    assert!(rule(UNUSED_SUPPRESSION_RULE).engine_emitted);
+    assert!(rules.iter().all(|r| !r.docs_url.is_empty()));
+}
+
+#[test]
+fn test_summaries_match_readme_rule_reference() {
+    let readme = std::fs::read_to_string("README.md").unwrap();
+    // Described in prose below the tables rather than in a row.
+    for rule in all_rules()
+        .into_iter()
+        .filter(|r| r.id != UNUSED_SUPPRESSION_RULE)
+    {
+        let summary = &rule_doc(rule.id).unwrap().summary;
+        let row = format!(
+            "| `{}` | {} | {} |",
+            rule.id, rule.default_severity, summary
+        );
+        assert!(readme.contains(&row), "README row differs for {}", rule.id);
+    }
+}
+
+#[test]
+fn test_explain_prints_rationale_examples_and_options() {
+    let output = explain("correctness/old-function-syntax").unwrap();
+    assert!(output.contains("Legacy function registration syntax"));
+    assert!(output.contains("Correctness (score weight 1.5x)"));
+    assert!(output.contains("Default severity:  warning"));
+    assert!(output.contains("leaves no place for argument and return validators"));
+    assert!(output.contains("export const list = query(async (ctx) => {"));
+    assert!(output.contains("handler: async (ctx) => {"));
+    assert!(output.contains("Convex guidance (v0.241.0)"));
+    assert!(output
+        .contains("ALWAYS use the new function syntax for Convex functions. (convex_rules:4)"));
+    assert!(output.contains(r#"`[rules] "correctness/old-function-syntax" = "off"`"#));
+    assert_eq!(guidelines_for("correctness/old-function-syntax").len(), 1);
+}
+
+#[test]
+fn test_explain_lists_strictness_and_client_options() {
+    let info_rule = explain("client/missing-convex-provider").unwrap();
+    assert!(info_rule.contains(r#"`[convex] strictness = "strict"` reports it as a warning."#));
+    assert!(info_rule.contains(r#"`[convex] strictness = "low_noise"` hides it."#));
+    assert!(info_rule.contains("`[client] roots`"));
+
+    let error_rule = explain("perf/unbounded-collect").unwrap();
+    assert!(!error_rule.contains("strictness"));
+    assert!(error_rule.contains("No Convex guideline bullet maps to this rule."));
+
+    let engine_rule = explain("config/unused-suppression").unwrap();
+    assert!(engine_rule.contains("--report-unused-suppressions"));
+    assert!(!engine_rule.contains("convex-doctor-disable-next-line config/unused-suppression`"));
+}
+
+#[test]
+fn test_explain_unknown_rule_suggests_full_id() {
+    let err = explain("unbounded-collect").unwrap_err();
+    assert!(err.contains("Did you mean `perf/unbounded-collect`?"));
+    assert!(explain("perf/nope").unwrap_err().contains("Unknown rule"));
+}
+
+#[test]
+fn test_list_rules_filters_by_category_and_severity() {
+    let all = list_rules(None, None);
+    assert!(all.contains(&format!("{} rules.", all_rules().len())));
+
+    let security_errors = list_rules(Some(Category::Security), Some(Severity::Error));
+    assert!(security_errors.contains("security/missing-arg-validators"));
+    assert!(!security_errors.contains("security/missing-return-validators"));
+    assert!(!security_errors.contains("perf/unbounded-collect"));
+
+    let client_errors = list_rules(Some(Category::ClientSide), Some(Severity::Error));
+    assert!(client_errors.contains("client/mutation-in-render"));
+    assert!(
+        list_rules(Some(Category::Architecture), Some(Severity::Error))
+            .contains("No rules match the given filters.")
+    );
+}
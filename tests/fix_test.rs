@@ -0,0 +1,310 @@
+use std::process::Command;
+
+use convex_doctor::diagnostic::{Category, Diagnostic, Severity, TextEdit};
+use convex_doctor::engine::run;
+use convex_doctor::fix::{plan_fixes, unified_diff, write_fixes};
+use tempfile::TempDir;
+
+fn project_with(source: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("jobs.ts"), source).unwrap();
+    dir
+}
+
+fn fixed_source(source: &str) -> String {
+    let dir = project_with(source);
+    let result = run(dir.path(), false, None).unwrap();
+    let fixes = plan_fixes(&result.diagnostics);
+    assert_eq!(fixes.len(), 1, "expected fixes for one file");
+    fixes[0].fixed.clone()
+}
+
+#[test]
+fn test_fix_internal_api_misuse_adds_internal_import() {
+    let fixed = fixed_source(
+        r#"import { internalMutation, mutation } from "./_generated/server";
+import { api } from "./_generated/api";
+
+export const work = internalMutation({ args: {}, handler: async () => null });
+export const other = internalMutation({ args: {}, handler: async () => null });
+
+export const kick = mutation({
+  args: {},
+  handler: async (ctx) => {
+    await ctx.scheduler.runAfter(0, api.jobs.work, {});
+    await ctx.runMutation(api.jobs.other, {});
+  },
+});
+"#,
+    );
+    assert!(fixed.contains(r#"import { api, internal } from "./_generated/api";"#));
+    assert!(fixed.contains("runAfter(0, internal.jobs.work, {})"));
+    assert!(fixed.contains("runMutation(internal.jobs.other, {})"));
+}
+
+#[test]
+fn test_fix_internal_api_misuse_reuses_existing_import() {
+    let fixed = fixed_source(
+        r#"import { internalMutation, mutation } from "./_generated/server";
+import { api, internal } from "./_generated/api";
+
+export const other = internalMutation({ args: {}, handler: async () => null });
+
+export const kick = mutation({
+  args: {},
+  handler: async (ctx) => {
+    await ctx.runMutation(api.jobs.other, {});
+  },
+});
+"#,
+    );
+    assert!(fixed.contains(r#"import { api, internal } from "./_generated/api";"#));
+    assert!(fixed.contains("runMutation(internal.jobs.other, {})"));
+}
+
+#[test]
+fn test_no_internal_api_fix_when_target_is_public() {
+    let dir = project_with(
+        r#"import { mutation, query } from "./_generated/server";
+import { api } from "./_generated/api";
+
+export const list = query({ args: {}, handler: async () => null });
+
+export const kick = mutation({
+  args: {},
+  handler: async (ctx) => {
+    await ctx.runQuery(api.jobs.list, {});
+    await ctx.runQuery(api.jobs.missing, {});
+  },
+});
+"#,
+    );
+    let result = run(dir.path(), false, None).unwrap();
+    let misuse: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "security/internal-api-misuse")
+        .collect();
+    assert_eq!(misuse.len(), 2);
+    assert!(misuse.iter().all(|d| d.fix.is_none()));
+}
+
+#[test]
+fn test_fix_unwaited_promise_inserts_await() {
+    let fixed = fixed_source(
+        r#"import { mutation } from "./_generated/server";
+
+export const add = mutation({
+  args: {},
+  handler: async (ctx) => {
+    ctx.db.insert("jobs", {});
+  },
+});
+"#,
+    );
+    assert!(fixed.contains(r#"    await ctx.db.insert("jobs", {});"#));
+}
+
+#[test]
+fn test_fix_storage_get_metadata_in_query() {
+    let fixed = fixed_source(
+        r#"import { query } from "./_generated/server";
+
+export const meta = query({
+  args: {},
+  handler: async (ctx, args) => {
+    return await ctx.storage.getMetadata(args.id);
+  },
+});
+"#,
+    );
+    assert!(fixed.contains(r#"return await ctx.db.system.get("_storage", args.id);"#));
+}
+
+#[test]
+fn test_no_storage_fix_inside_action() {
+    let dir = project_with(
+        r#"import { action } from "./_generated/server";
+
+export const meta = action({
+  args: {},
+  handler: async (ctx, args) => {
+    return await ctx.storage.getMetadata(args.id);
+  },
+});
+"#,
+    );
+    let result = run(dir.path(), false, None).unwrap();
+    let storage = result
+        .diagnostics
+        .iter()
+        .find(|d| d.rule == "correctness/storage-get-metadata-deprecated")
+        .expect("getMetadata should still be reported");
+    assert!(storage.fix.is_none());
+}
+
+#[test]
+fn test_fix_old_function_syntax_wraps_handler_verbatim() {
+    let fixed = fixed_source(
+        r#"import { query } from "./_generated/server";
+
+export const list = query(async (ctx) => {
+  // keep me
+  return await ctx.db.query("messages").take(10);
+});
+"#,
+    );
+    assert_eq!(
+        fixed,
+        r#"import { query } from "./_generated/server";
+
+export const list = query({ args: {}, handler: async (ctx) => {
+  // keep me
+  return await ctx.db.query("messages").take(10);
+} });
+"#
+    );
+}
+
+#[test]
+fn test_fix_old_function_syntax_emits_args_skeleton() {
+    let fixed = fixed_source(
+        r#"import { mutation } from "./_generated/server";
+
+export const send = mutation(async (ctx, { body, author = "anon" }) => {
+  await ctx.db.insert("messages", { body, author });
+});
+"#,
+    );
+    assert!(fixed.contains("import { v } from \"convex/values\";"));
+    assert!(fixed.contains(
+        "mutation({ args: { /* TODO: replace v.any() placeholders with real validators */ body: v.any(), author: v.optional(v.any()) }, handler: async (ctx, { body, author = \"anon\" }) => {"
+    ));
+}
+
+#[test]
+fn test_fix_old_function_syntax_without_known_args() {
+    let fixed = fixed_source(
+        r#"import { internalMutation } from "./_generated/server";
+import { v } from "convex/values";
+
+export const raw = internalMutation(async function (ctx, args) {
+  await ctx.db.insert("messages", args);
+});
+"#,
+    );
+    assert!(fixed.contains("internalMutation({ handler: async function (ctx, args) {"));
+    assert_eq!(fixed.matches("import { v }").count(), 1);
+}
+
+#[test]
+fn test_fix_that_breaks_parsing_is_backed_out() {
+    let dir = project_with(
+        r#"import { mutation } from "./_generated/server";
+
+export const add = mutation({
+  args: {},
+  handler: async (ctx) => {
+    ctx.db.insert("jobs", {});
+  },
+});
+
+export function helper(ctx: any) {
+  ctx.db.delete("x");
+}
+"#,
+    );
+    let result = run(dir.path(), false, None).unwrap();
+    let fixes = plan_fixes(&result.diagnostics);
+    assert_eq!(fixes[0].applied, 1);
+    assert_eq!(fixes[0].skipped, 1);
+    assert!(fixes[0]
+        .fixed
+        .contains(r#"await ctx.db.insert("jobs", {});"#));
+    assert!(fixes[0].fixed.contains(r#"  ctx.db.delete("x");"#));
+}
+
+#[test]
+fn test_overlapping_fixes_apply_first_only() {
+    let dir = TempDir::new().unwrap();
+    let file = dir.path().join("a.ts");
+    std::fs::write(&file, "const a = 1;\n").unwrap();
+    let diagnostic = |edits: Vec<TextEdit>| Diagnostic {
+        rule: "test/rule".to_string(),
+        severity: Severity::Warning,
+        category: Category::Correctness,
+        message: String::new(),
+        help: String::new(),
+        file: file.display().to_string(),
+        line: 1,
+        column: 1,
+        fix: Some(edits),
+    };
+    let diagnostics = vec![
+        diagnostic(vec![TextEdit::replace(6, 7, "b")]),
+        diagnostic(vec![TextEdit::replace(6, 11, "c = 2")]),
+    ];
+
+    let fixes = plan_fixes(&diagnostics);
+    assert_eq!(fixes[0].fixed, "const b = 1;\n");
+    assert_eq!(fixes[0].applied, 1);
+    assert_eq!(fixes[0].skipped, 1);
+
+    write_fixes(&fixes).unwrap();
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "const b = 1;\n");
+}
+
+#[test]
+fn test_unified_diff_format() {
+    let old = "a\nb\nc\nd\ne\nf\ng\nh\ni\n";
+    let new = "a\nb\nc\nd\nE\nf\ng\nh\ni\n";
+    let diff = unified_diff("a/x.ts", "b/x.ts", old, new);
+    assert_eq!(
+        diff,
+        "--- a/x.ts\n+++ b/x.ts\n@@ -2,7 +2,7 @@\n b\n c\n d\n-e\n+E\n f\n g\n h\n"
+    );
+    assert!(unified_diff("a", "b", old, old).is_empty());
+}
+
+#[test]
+fn test_cli_fix_rewrites_files() {
+    let source = r#"import { mutation } from "./_generated/server";
+
+export const add = mutation({
+  args: {},
+  handler: async (ctx) => {
+    ctx.db.insert("jobs", {});
+  },
+});
+"#;
+    let dir = project_with(source);
+    let file = dir.path().join("convex/jobs.ts");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .arg("--fix-dry-run")
+        .arg(dir.path())
+        .output()
+        .expect("binary should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("+++ b/convex/jobs.ts"), "{stdout}");
+    assert!(stdout.contains(r#"+    await ctx.db.insert("jobs", {});"#));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), source);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["--fix", "--format", "json"])
+        .arg(dir.path())
+        .output()
+        .expect("binary should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    assert!(json["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|d| d["rule"] != "correctness/unwaited-promise"));
+    assert!(std::fs::read_to_string(&file)
+        .unwrap()
+        .contains(r#"await ctx.db.insert("jobs", {});"#));
+}
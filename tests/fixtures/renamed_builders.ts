@@ -0,0 +1,34 @@
+import { query as q, internalMutation } from "./_generated/server";
+import * as server from "./_generated/server";
+import { actionGeneric } from "convex/server";
+import { v } from "convex/values";
+
+function mutation(options: { handler: () => void }) {
+  return options.handler;
+}
+
+export const list = q({
+  args: {},
+  handler: async (ctx) => await ctx.db.query("messages").take(10),
+});
+
+export const send = server.mutation({
+  args: { body: v.string() },
+  handler: async (ctx, { body }) => {
+    await ctx.db.insert("messages", { body });
+  },
+});
+
+export const cleanup = internalMutation({
+  args: {},
+  handler: async () => null,
+});
+
+export const notify = actionGeneric({
+  args: {},
+  handler: async () => null,
+});
+
+export const local = mutation({
+  handler: () => undefined,
+});
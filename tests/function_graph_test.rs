@@ -0,0 +1,330 @@
+use std::path::{Path, PathBuf};
+
+use convex_doctor::rules::context::analyze_file;
+use convex_doctor::rules::correctness::{FunctionKindMismatch, UnresolvedFunctionReference};
+use convex_doctor::rules::function_graph::{
+    module_path, CallEdgeKind, FunctionGraph, FunctionReference, Resolution,
+};
+use convex_doctor::rules::{FunctionKind, ProjectContext, Rule};
+use tempfile::TempDir;
+
+fn write(dir: &Path, relative: &str, source: &str) -> PathBuf {
+    let path = dir.join(relative);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, source).unwrap();
+    path
+}
+
+fn build_graph(dir: &TempDir, analyzed: &[PathBuf], all: &[PathBuf]) -> FunctionGraph {
+    let analyses: Vec<_> = analyzed.iter().map(|p| analyze_file(p).unwrap()).collect();
+    FunctionGraph::build(&dir.path().join("convex"), &analyses, all)
+}
+
+#[test]
+fn test_module_path_uses_file_based_routing() {
+    let convex = Path::new("/app/convex");
+    assert_eq!(
+        module_path(convex, Path::new("/app/convex/messages/access.ts")).as_deref(),
+        Some("messages/access")
+    );
+    assert_eq!(
+        module_path(convex, Path::new("/app/convex/users.js")).as_deref(),
+        Some("users")
+    );
+    assert_eq!(
+        module_path(convex, Path::new("/app/convex/_generated/api.ts")),
+        None
+    );
+}
+
+#[test]
+fn test_function_reference_parse() {
+    assert_eq!(
+        FunctionReference::parse("internal.messages.access.h"),
+        Some(FunctionReference {
+            internal: true,
+            module: "messages/access".to_string(),
+            name: "h".to_string(),
+        })
+    );
+    assert_eq!(FunctionReference::parse("api.messages"), None);
+    assert_eq!(FunctionReference::parse("ctx.db.get"), None);
+}
+
+#[test]
+fn test_graph_indexes_and_resolves_functions() {
+    let dir = TempDir::new().unwrap();
+    let access = write(
+        dir.path(),
+        "convex/messages/access.ts",
+        r#"
+import { internalQuery, mutation } from "./_generated/server";
+
+export const h = internalQuery({ args: {}, handler: async () => null });
+export const send = mutation({ args: {}, handler: async () => null });
+const helper = internalQuery({ args: {}, handler: async () => null });
+"#,
+    );
+    let ignored = write(dir.path(), "convex/legacy.ts", "export const x = 1;\n");
+    let all = [access, ignored];
+    let graph = build_graph(&dir, &all[..1], &all);
+
+    match graph.resolve("internal.messages.access.h") {
+        Resolution::Found(func) => {
+            assert_eq!(func.kind, FunctionKind::InternalQuery);
+            assert_eq!(func.id(), "messages/access:h");
+            assert!(!func.is_public());
+        }
+        other => panic!("expected a match, got {other:?}"),
+    }
+    assert!(matches!(
+        graph.resolve("api.messages.access.send"),
+        Resolution::Found(f) if f.is_public()
+    ));
+    assert_eq!(
+        graph.resolve("internal.messages.access.helper"),
+        Resolution::MissingExport
+    );
+    assert_eq!(
+        graph.resolve("api.messages.missing.h"),
+        Resolution::MissingModule
+    );
+    assert_eq!(graph.resolve("api.legacy.x"), Resolution::Unknown);
+    assert_eq!(graph.functions().count(), 2);
+}
+
+#[test]
+fn test_graph_records_run_scheduler_and_cron_edges() {
+    let dir = TempDir::new().unwrap();
+    let jobs = write(
+        dir.path(),
+        "convex/jobs.ts",
+        r#"
+import { action, internalMutation } from "./_generated/server";
+import { internal } from "./_generated/api";
+
+export const sync = action({
+  args: {},
+  handler: async (ctx) => {
+    const user = await ctx.runQuery(internal.users.getById, {});
+    await ctx.runMutation(internal.jobs.record, {});
+    await ctx.scheduler.runAfter(0, internal.jobs.record, {});
+  },
+});
+
+export const record = internalMutation({ args: {}, handler: async () => null });
+"#,
+    );
+    let crons = write(
+        dir.path(),
+        "convex/crons.ts",
+        r#"
+import { cronJobs } from "convex/server";
+import { internal } from "./_generated/api";
+
+const crons = cronJobs();
+crons.interval("sync", { minutes: 5 }, internal.jobs.sync, {});
+export default crons;
+"#,
+    );
+    let all = [jobs, crons];
+    let graph = build_graph(&dir, &all, &all);
+
+    let from_sync: Vec<_> = graph.calls_from("jobs:sync").collect();
+    assert_eq!(from_sync.len(), 3);
+    assert_eq!(from_sync[0].kind, CallEdgeKind::RunQuery);
+    assert_eq!(from_sync[0].target, "internal.users.getById");
+    assert_eq!(from_sync[1].kind, CallEdgeKind::RunMutation);
+    assert_eq!(from_sync[2].kind, CallEdgeKind::Schedule);
+    assert_eq!(from_sync[2].target, "internal.jobs.record");
+
+    let cron_edges: Vec<_> = graph
+        .calls
+        .iter()
+        .filter(|edge| edge.kind == CallEdgeKind::Cron)
+        .collect();
+    assert_eq!(cron_edges.len(), 1);
+    assert_eq!(cron_edges[0].target, "internal.jobs.sync");
+    assert_eq!(cron_edges[0].caller, None);
+}
+
+fn project_ctx(graph: FunctionGraph) -> ProjectContext {
+    ProjectContext {
+        function_graph: graph,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_unresolved_function_reference_rule() {
+    let dir = TempDir::new().unwrap();
+    let users = write(
+        dir.path(),
+        "convex/users.ts",
+        r#"
+import { internalQuery, query, action } from "./_generated/server";
+import { api, internal } from "./_generated/api";
+
+export const getById = internalQuery({ args: {}, handler: async () => null });
+export const list = query({ args: {}, handler: async () => null });
+
+export const sync = action({
+  args: {},
+  handler: async (ctx) => {
+    await ctx.runQuery(internal.users.getById, {});
+    await ctx.runQuery(api.users.getById, {});
+    await ctx.runQuery(internal.users.list, {});
+    await ctx.runQuery(internal.users.missing, {});
+    await ctx.runQuery(internal.accounts.get, {});
+  },
+});
+"#,
+    );
+    let all = [users];
+    let diags =
+        UnresolvedFunctionReference.check_project(&project_ctx(build_graph(&dir, &all, &all)));
+    let messages: Vec<_> = diags.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages.len(), 4, "{messages:?}");
+    assert!(messages[0].contains("`api.users.getById`") && messages[0].contains("internal.*"));
+    assert!(messages[1].contains("`internal.users.list`") && messages[1].contains("api.*"));
+    assert!(messages[2].contains("does not export"));
+    assert!(messages[3].contains("module `accounts`"));
+}
+
+#[test]
+fn test_function_kind_mismatch_rule() {
+    let dir = TempDir::new().unwrap();
+    let jobs = write(
+        dir.path(),
+        "convex/jobs.ts",
+        r#"
+import { internalQuery, internalMutation, internalAction } from "./_generated/server";
+import { internal } from "./_generated/api";
+
+export const count = internalQuery({ args: {}, handler: async () => 0 });
+export const record = internalMutation({ args: {}, handler: async () => null });
+
+export const run = internalAction({
+  args: {},
+  handler: async (ctx) => {
+    await ctx.runMutation(internal.jobs.count, {});
+    await ctx.runQuery(internal.jobs.run, {});
+    await ctx.runMutation(internal.jobs.record, {});
+    await ctx.scheduler.runAfter(0, internal.jobs.count, {});
+  },
+});
+"#,
+    );
+    let crons = write(
+        dir.path(),
+        "convex/crons.ts",
+        r#"
+import { cronJobs } from "convex/server";
+import { internal } from "./_generated/api";
+
+const crons = cronJobs();
+crons.interval("count", { minutes: 5 }, internal.jobs.count, {});
+crons.interval("record", { minutes: 5 }, internal.jobs.record, {});
+export default crons;
+"#,
+    );
+    let all = [jobs, crons];
+    let diags = FunctionKindMismatch.check_project(&project_ctx(build_graph(&dir, &all, &all)));
+    let messages: Vec<_> = diags.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages.len(), 4, "{messages:?}");
+    assert!(messages[0].starts_with("`ctx.runMutation` expects a mutation"));
+    assert!(messages[1].contains("`internalAction`"));
+    assert!(messages[2].starts_with("`ctx.scheduler.*`"));
+    assert!(messages[3].starts_with("`crons.*` expects a mutation or action"));
+}
+
+#[test]
+fn test_client_hooks_are_checked_against_the_graph() {
+    let dir = TempDir::new().unwrap();
+    let messages = write(
+        dir.path(),
+        "convex/messages.ts",
+        r#"
+import { query, mutation } from "./_generated/server";
+
+export const list = query({ args: {}, handler: async () => [] });
+export const send = mutation({ args: {}, handler: async () => null });
+"#,
+    );
+    let page = write(
+        dir.path(),
+        "convex/ui/Page.tsx",
+        r#"
+import { useQuery, useMutation } from "convex/react";
+import { api } from "../_generated/api";
+
+export function Page() {
+  const list = useQuery(api.messages.list);
+  const send = useMutation(api.messages.list);
+  const gone = useQuery(api.messages.removed);
+  return null;
+}
+"#,
+    );
+    let all = [messages, page];
+    let ctx = project_ctx(build_graph(&dir, &all, &all));
+
+    let hook_edges: Vec<_> = ctx
+        .function_graph
+        .calls
+        .iter()
+        .filter(|edge| {
+            matches!(
+                edge.kind,
+                CallEdgeKind::UseQuery | CallEdgeKind::UseMutation
+            )
+        })
+        .collect();
+    assert_eq!(hook_edges.len(), 3);
+
+    let mismatches = FunctionKindMismatch.check_project(&ctx);
+    assert_eq!(mismatches.len(), 1);
+    assert!(mismatches[0]
+        .message
+        .starts_with("`useMutation` expects a mutation"));
+    assert!(mismatches[0].file.ends_with("Page.tsx"));
+
+    let unresolved = UnresolvedFunctionReference.check_project(&ctx);
+    assert_eq!(unresolved.len(), 1);
+    assert!(unresolved[0].message.contains("`api.messages.removed`"));
+}
+
+#[test]
+fn test_exports_built_by_unrecognized_wrappers_are_not_missing() {
+    let dir = TempDir::new().unwrap();
+    let jobs = write(
+        dir.path(),
+        "convex/jobs.ts",
+        r#"
+import { action } from "./_generated/server";
+import { internal } from "./_generated/api";
+import { myWrapper } from "some-wrapper-package";
+
+export const wrapped = myWrapper({ args: {}, run: async () => null });
+
+export const run = action({
+  args: {},
+  handler: async (ctx) => {
+    await ctx.runMutation(internal.jobs.wrapped, {});
+    await ctx.runMutation(internal.jobs.gone, {});
+  },
+});
+"#,
+    );
+    let all = [jobs];
+    let graph = build_graph(&dir, &all, &all);
+    assert_eq!(graph.resolve("internal.jobs.wrapped"), Resolution::Unknown);
+    assert_eq!(
+        graph.resolve("internal.jobs.gone"),
+        Resolution::MissingExport
+    );
+
+    let diags = UnresolvedFunctionReference.check_project(&project_ctx(graph));
+    assert_eq!(diags.len(), 1);
+    assert!(diags[0].message.contains("`internal.jobs.gone`"));
+}
@@ -0,0 +1,251 @@
+use std::io::Cursor;
+use std::path::Path;
+
+use convex_doctor::lsp::{read_message, serve, uri_to_path, write_message};
+use serde_json::{json, Value};
+use tempfile::TempDir;
+
+const ON_DISK: &str = r#"import { mutation } from "./_generated/server";
+import { v } from "convex/values";
+
+export const add = mutation({
+  args: {},
+  returns: v.null(),
+  handler: async (ctx) => {
+    await ctx.db.insert("jobs", {});
+    return null;
+  },
+});
+"#;
+
+fn project() -> (TempDir, String) {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("jobs.ts"), ON_DISK).unwrap();
+    let root = dir.path().canonicalize().unwrap();
+    let uri = format!("file://{}", root.join("convex/jobs.ts").display());
+    (dir, uri)
+}
+
+/// Send `messages` (wrapped in initialize/shutdown/exit) and return the replies.
+fn session(root: &Path, messages: Vec<Value>) -> Vec<Value> {
+    let mut all = vec![
+        json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize",
+                "params": { "rootUri": format!("file://{}", root.display()), "capabilities": {} } }),
+        json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+    ];
+    all.extend(messages);
+    all.push(json!({ "jsonrpc": "2.0", "id": 99, "method": "shutdown" }));
+    all.push(json!({ "jsonrpc": "2.0", "method": "exit" }));
+
+    let mut input = Vec::new();
+    for message in &all {
+        write_message(&mut input, message).unwrap();
+    }
+    let mut output = Vec::new();
+    serve(Cursor::new(input), &mut output).unwrap();
+
+    let mut reader = Cursor::new(output);
+    let mut replies = Vec::new();
+    while let Some(message) = read_message(&mut reader).unwrap() {
+        replies.push(message);
+    }
+    replies
+}
+
+fn did_open(uri: &str, text: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {
+        "textDocument": { "uri": uri, "languageId": "typescript", "version": 1, "text": text } } })
+}
+
+fn published<'a>(replies: &'a [Value], uri: &str) -> Vec<&'a Value> {
+    replies
+        .iter()
+        .filter(|m| m["method"] == "textDocument/publishDiagnostics" && m["params"]["uri"] == uri)
+        .collect()
+}
+
+fn response(replies: &[Value], id: u64) -> &Value {
+    replies
+        .iter()
+        .find(|m| m["id"] == id)
+        .expect("missing response")
+}
+
+fn codes(notification: &Value) -> Vec<&str> {
+    notification["params"]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter_map(|d| d["code"].as_str())
+        .collect()
+}
+
+#[test]
+fn test_lsp_publishes_diagnostics_for_unsaved_contents() {
+    let (dir, uri) = project();
+    let unsaved = ON_DISK.replace("await ctx.db.insert", "ctx.db.insert");
+    let replies = session(
+        dir.path(),
+        vec![
+            did_open(&uri, ON_DISK),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": unsaved }] } }),
+        ],
+    );
+
+    let capabilities = &response(&replies, 1)["result"]["capabilities"];
+    assert_eq!(capabilities["hoverProvider"], true);
+
+    let notifications = published(&replies, &uri);
+    assert_eq!(notifications.len(), 2);
+    assert!(!codes(notifications[0]).contains(&"correctness/unwaited-promise"));
+    assert!(codes(notifications[1]).contains(&"correctness/unwaited-promise"));
+
+    let diagnostic = notifications[1]["params"]["diagnostics"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|d| d["code"] == "correctness/unwaited-promise")
+        .unwrap();
+    assert_eq!(diagnostic["severity"], 1);
+    assert_eq!(
+        diagnostic["range"]["start"],
+        json!({ "line": 7, "character": 4 })
+    );
+    // The file on disk is untouched.
+    assert_eq!(
+        std::fs::read_to_string(dir.path().join("convex/jobs.ts")).unwrap(),
+        ON_DISK
+    );
+}
+
+#[test]
+fn test_lsp_code_action_applies_rule_fix() {
+    let (dir, uri) = project();
+    let unsaved = ON_DISK.replace("await ctx.db.insert", "ctx.db.insert");
+    let replies = session(
+        dir.path(),
+        vec![
+            did_open(&uri, &unsaved),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/codeAction", "params": {
+                "textDocument": { "uri": uri },
+                "range": { "start": { "line": 7, "character": 4 }, "end": { "line": 7, "character": 4 } },
+                "context": { "diagnostics": [] } } }),
+        ],
+    );
+
+    let actions = response(&replies, 2)["result"].as_array().unwrap();
+    assert_eq!(actions.len(), 1);
+    assert_eq!(actions[0]["kind"], "quickfix");
+    let edits = &actions[0]["edit"]["changes"][uri.as_str()];
+    assert_eq!(
+        edits,
+        &json!([{
+            "range": { "start": { "line": 7, "character": 4 }, "end": { "line": 7, "character": 4 } },
+            "newText": "await ",
+        }])
+    );
+}
+
+#[test]
+fn test_lsp_hover_shows_rule_help_and_link() {
+    let (dir, uri) = project();
+    let unsaved = ON_DISK.replace("await ctx.db.insert", "ctx.db.insert");
+    let replies = session(
+        dir.path(),
+        vec![
+            did_open(&uri, &unsaved),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": {
+                "textDocument": { "uri": uri }, "position": { "line": 7, "character": 10 } } }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "textDocument/hover", "params": {
+                "textDocument": { "uri": uri }, "position": { "line": 0, "character": 0 } } }),
+        ],
+    );
+
+    let hover = response(&replies, 2)["result"]["contents"]["value"]
+        .as_str()
+        .unwrap();
+    assert!(hover.contains("correctness/unwaited-promise"));
+    assert!(hover.contains("must be awaited"));
+    assert!(hover.contains("#rule-reference"));
+    assert_eq!(response(&replies, 3)["result"], Value::Null);
+}
+
+#[test]
+fn test_lsp_hover_shows_convex_guidance_for_the_rule() {
+    let (dir, uri) = project();
+    let unsaved = ON_DISK.replace("  args: {},\n", "");
+    let replies = session(
+        dir.path(),
+        vec![
+            did_open(&uri, &unsaved),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": {
+                "textDocument": { "uri": uri }, "position": { "line": 3, "character": 20 } } }),
+        ],
+    );
+
+    let hover = response(&replies, 2)["result"]["contents"]["value"]
+        .as_str()
+        .unwrap();
+    assert!(hover.contains("security/missing-arg-validators"), "{hover}");
+    assert!(hover.contains("Convex guidance ("), "{hover}");
+    assert!(
+        hover.contains("ALWAYS include argument validators for all Convex functions."),
+        "{hover}"
+    );
+    assert!(hover.contains("(`convex_rules:86`)"), "{hover}");
+}
+
+#[test]
+fn test_lsp_close_reverts_to_disk_and_clears_diagnostics() {
+    let (dir, uri) = project();
+    let unsaved = ON_DISK.replace("await ctx.db.insert", "ctx.db.insert");
+    let replies = session(
+        dir.path(),
+        vec![
+            did_open(&uri, &unsaved),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didClose", "params": {
+                "textDocument": { "uri": uri } } }),
+        ],
+    );
+
+    let notifications = published(&replies, &uri);
+    assert!(codes(notifications[0]).contains(&"correctness/unwaited-promise"));
+    assert!(codes(notifications.last().unwrap()).is_empty());
+}
+
+#[test]
+fn test_lsp_reports_missing_project_and_unknown_methods() {
+    let dir = TempDir::new().unwrap();
+    let replies = session(
+        dir.path(),
+        vec![json!({ "jsonrpc": "2.0", "id": 2, "method": "workspace/symbol", "params": {} })],
+    );
+
+    assert!(replies.iter().any(|m| m["method"] == "window/showMessage"
+        && m["params"]["message"]
+            .as_str()
+            .unwrap()
+            .contains("No convex/ directory")));
+    assert_eq!(response(&replies, 2)["error"]["code"], -32601);
+    assert_eq!(response(&replies, 99)["result"], Value::Null);
+}
+
+#[test]
+fn test_lsp_exit_without_shutdown_is_an_error() {
+    let mut input = Vec::new();
+    write_message(&mut input, &json!({ "jsonrpc": "2.0", "method": "exit" })).unwrap();
+    assert!(serve(Cursor::new(input), Vec::new()).is_err());
+}
+
+#[test]
+fn test_uri_to_path_decodes_escapes() {
+    assert_eq!(
+        uri_to_path("file:///no/such%20dir/a%2Bb.ts").unwrap(),
+        Path::new("/no/such dir/a+b.ts")
+    );
+    assert!(uri_to_path("untitled:Untitled-1").is_none());
+}
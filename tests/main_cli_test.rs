@@ -18,3 +18,82 @@ fn test_invalid_format_is_rejected() {
         "stderr should explain invalid format value. stderr: {stderr}"
     );
 }
+
+#[test]
+fn test_sarif_format_is_accepted() {
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["--format", "sarif", "tests/fixtures/sample_project"])
+        .output()
+        .expect("binary should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("SARIF output should be valid JSON");
+    assert_eq!(json["version"], "2.1.0");
+    assert_eq!(json["runs"][0]["tool"]["driver"]["name"], "convex-doctor");
+}
+
+#[test]
+fn test_workspace_json_reports_projects_and_aggregate() {
+    let dir = tempfile::TempDir::new().unwrap();
+    std::fs::write(
+        dir.path().join("package.json"),
+        r#"{"workspaces": ["apps/*"]}"#,
+    )
+    .unwrap();
+    for app in ["web", "admin"] {
+        let convex_dir = dir.path().join("apps").join(app).join("convex");
+        std::fs::create_dir_all(&convex_dir).unwrap();
+        std::fs::write(convex_dir.join("messages.ts"), "export const x = 1;\n").unwrap();
+    }
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .arg("--workspace")
+        .args(["--format", "json"])
+        .arg(dir.path())
+        .output()
+        .expect("binary should run");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json: serde_json::Value =
+        serde_json::from_str(&stdout).expect("workspace output should be valid JSON");
+    assert_eq!(json["summary"]["projects"], 2);
+    assert_eq!(json["projects"][0]["name"], "apps/admin");
+    assert_eq!(json["projects"][1]["name"], "apps/web");
+    assert!(json["score"]["value"].is_number());
+    assert!(json["projects"][0]["diagnostics"].is_array());
+}
+
+#[test]
+fn test_workspace_conflicts_with_baseline() {
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["--workspace", "--baseline", "baseline.json", "."])
+        .output()
+        .expect("binary should run");
+    assert!(!output.status.success());
+}
+
+#[test]
+fn test_explain_and_rules_subcommands() {
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["explain", "perf/unbounded-collect"])
+        .output()
+        .expect("binary should run");
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("perf/unbounded-collect"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["explain", "perf/nope"])
+        .output()
+        .expect("binary should run");
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Unknown rule"));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_convex-doctor"))
+        .args(["rules", "--category", "perf", "--severity", "error"])
+        .output()
+        .expect("binary should run");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("perf/unbounded-collect"));
+    assert!(!stdout.contains("perf/filter-without-index"));
+}
@@ -56,3 +56,110 @@ fn test_discover_convex_files() {
         .iter()
         .all(|f| !f.to_string_lossy().contains("_generated")));
 }
+
+#[test]
+fn test_discover_client_files_from_framework() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("convex")).unwrap();
+    std::fs::write(
+        dir.path().join("package.json"),
+        r#"{"dependencies": {"next": "14.0.0", "convex": "1.17.0"}}"#,
+    )
+    .unwrap();
+    std::fs::create_dir_all(dir.path().join("app/chat")).unwrap();
+    std::fs::write(dir.path().join("app/chat/page.tsx"), "// page").unwrap();
+    std::fs::create_dir(dir.path().join("pages")).unwrap();
+    std::fs::write(dir.path().join("pages/index.tsx"), "// index").unwrap();
+    std::fs::create_dir_all(dir.path().join("app/node_modules/pkg")).unwrap();
+    std::fs::write(dir.path().join("app/node_modules/pkg/index.js"), "").unwrap();
+    std::fs::create_dir(dir.path().join("lib")).unwrap();
+    std::fs::write(dir.path().join("lib/utils.ts"), "// utils").unwrap();
+
+    let info = ProjectInfo::detect(dir.path()).unwrap();
+    let files = info.discover_client_files(&convex_doctor::config::Config::default());
+    let names: Vec<String> = files
+        .iter()
+        .map(|f| {
+            f.strip_prefix(dir.path())
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect();
+    assert_eq!(names, vec!["app/chat/page.tsx", "pages/index.tsx"]);
+}
+
+#[test]
+fn test_discover_client_files_from_config_roots() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("src/convex")).unwrap();
+    std::fs::create_dir(dir.path().join("convex")).unwrap();
+    std::fs::write(
+        dir.path().join("package.json"),
+        r#"{"devDependencies": {"vite": "5.0.0"}}"#,
+    )
+    .unwrap();
+    std::fs::create_dir(dir.path().join("web")).unwrap();
+    std::fs::write(dir.path().join("web/App.tsx"), "// app").unwrap();
+    std::fs::write(dir.path().join("src/main.tsx"), "// main").unwrap();
+
+    let info = ProjectInfo::detect(dir.path()).unwrap();
+    let mut config = convex_doctor::config::Config::default();
+    config.client.roots = vec!["web".to_string(), "missing".to_string()];
+
+    assert_eq!(info.client_roots(&config), vec![dir.path().join("web")]);
+    assert_eq!(
+        info.discover_client_files(&config),
+        vec![dir.path().join("web/App.tsx")]
+    );
+}
+
+#[test]
+fn test_discover_client_files_without_framework() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("convex")).unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    std::fs::write(dir.path().join("src/main.tsx"), "// main").unwrap();
+
+    let info = ProjectInfo::detect(dir.path()).unwrap();
+    assert!(info
+        .discover_client_files(&convex_doctor::config::Config::default())
+        .is_empty());
+}
+
+#[test]
+fn test_detect_functions_dir_from_convex_json() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir_all(dir.path().join("src/convex")).unwrap();
+    std::fs::write(dir.path().join("src/convex/schema.ts"), "// schema").unwrap();
+    std::fs::write(dir.path().join("src/convex/messages.ts"), "// messages").unwrap();
+    std::fs::write(
+        dir.path().join("convex.json"),
+        r#"{"functions": "./src/convex/"}"#,
+    )
+    .unwrap();
+
+    let info = ProjectInfo::detect(dir.path()).unwrap();
+    assert_eq!(info.convex_dir, dir.path().join("src/convex"));
+    assert_eq!(info.convex_dir_display(), "src/convex");
+    assert!(info.has_schema);
+    assert_eq!(
+        info.discover_files(&convex_doctor::config::Config::default())
+            .len(),
+        2
+    );
+}
+
+#[test]
+fn test_detect_missing_custom_functions_dir() {
+    let dir = TempDir::new().unwrap();
+    std::fs::create_dir(dir.path().join("convex")).unwrap();
+    std::fs::write(
+        dir.path().join("convex.json"),
+        r#"{"functions": "backend/"}"#,
+    )
+    .unwrap();
+
+    let err = ProjectInfo::detect(dir.path()).unwrap_err();
+    assert!(err.contains("No backend/ directory found"), "{err}");
+}
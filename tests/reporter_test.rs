@@ -3,6 +3,7 @@ use std::time::Duration;
 use convex_doctor::diagnostic::{Category, Diagnostic, Severity};
 use convex_doctor::reporter::json::JsonReporter;
 use convex_doctor::reporter::Reporter;
+use convex_doctor::rules::RuleRegistry;
 use convex_doctor::scoring::compute_score;
 
 fn sample_diagnostics() -> Vec<Diagnostic> {
@@ -16,6 +17,7 @@ fn sample_diagnostics() -> Vec<Diagnostic> {
             file: "convex/messages.ts".to_string(),
             line: 5,
             column: 1,
+            fix: None,
         },
         Diagnostic {
             rule: "perf/unbounded-collect".to_string(),
@@ -26,6 +28,7 @@ fn sample_diagnostics() -> Vec<Diagnostic> {
             file: "convex/messages.ts".to_string(),
             line: 22,
             column: 10,
+            fix: None,
         },
     ]
 }
@@ -34,7 +37,7 @@ fn sample_diagnostics() -> Vec<Diagnostic> {
 fn test_json_output_structure() {
     let diagnostics = sample_diagnostics();
     let score = compute_score(&diagnostics);
-    let reporter = JsonReporter;
+    let reporter = JsonReporter::default();
     let output = reporter.format(
         &diagnostics,
         &score,
@@ -50,6 +53,38 @@ fn test_json_output_structure() {
     assert_eq!(json["summary"]["files_scanned"].as_u64().unwrap(), 5);
 }
 
+#[test]
+fn test_json_output_includes_rule_metadata() {
+    let diagnostics = sample_diagnostics();
+    let score = compute_score(&diagnostics);
+    let output = JsonReporter::default().format(
+        &diagnostics,
+        &score,
+        "my-app",
+        false,
+        5,
+        Duration::from_millis(42),
+    );
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let rules = json["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), RuleRegistry::new().metadata().len());
+
+    let unknown_table = rules
+        .iter()
+        .find(|r| r["id"] == "correctness/unknown-table")
+        .unwrap();
+    assert_eq!(unknown_table["project_level"], true);
+    assert_eq!(unknown_table["fixable"], false);
+    assert!(!unknown_table["description"].as_str().unwrap().is_empty());
+    assert!(!unknown_table["docs_url"].as_str().unwrap().is_empty());
+
+    let parse_error = rules
+        .iter()
+        .find(|r| r["id"] == "correctness/file-parse-error")
+        .unwrap();
+    assert_eq!(parse_error["engine_emitted"], true);
+}
+
 #[test]
 fn test_json_summary_counts_errors_warnings_and_infos() {
     let diagnostics = vec![
@@ -62,6 +97,7 @@ fn test_json_summary_counts_errors_warnings_and_infos() {
             file: "convex/messages.ts".to_string(),
             line: 5,
             column: 1,
+            fix: None,
         },
         Diagnostic {
             rule: "perf/unbounded-collect".to_string(),
@@ -72,6 +108,7 @@ fn test_json_summary_counts_errors_warnings_and_infos() {
             file: "convex/messages.ts".to_string(),
             line: 22,
             column: 10,
+            fix: None,
         },
         Diagnostic {
             rule: "config/missing-tsconfig".to_string(),
@@ -82,10 +119,11 @@ fn test_json_summary_counts_errors_warnings_and_infos() {
             file: "convex/".to_string(),
             line: 0,
             column: 0,
+            fix: None,
         },
     ];
     let score = compute_score(&diagnostics);
-    let reporter = JsonReporter;
+    let reporter = JsonReporter::default();
     let output = reporter.format(
         &diagnostics,
         &score,
@@ -109,3 +147,109 @@ fn test_score_only_output() {
     let parsed: u32 = output.trim().parse().unwrap();
     assert!(parsed <= 100);
 }
+
+#[test]
+fn test_sarif_output_structure() {
+    use convex_doctor::reporter::sarif::SarifReporter;
+
+    let diagnostics = sample_diagnostics();
+    let score = compute_score(&diagnostics);
+    let reporter = SarifReporter::new(std::path::Path::new("."));
+    let output = reporter.format(
+        &diagnostics,
+        &score,
+        "my-app",
+        false,
+        5,
+        Duration::from_millis(42),
+    );
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(json["version"], "2.1.0");
+
+    let run = &json["runs"][0];
+    let rules = run["tool"]["driver"]["rules"].as_array().unwrap();
+    assert_eq!(rules.len(), RuleRegistry::new().metadata().len());
+    let auth_rule = rules
+        .iter()
+        .find(|r| r["id"] == "security/missing-auth-check")
+        .unwrap();
+    assert_eq!(auth_rule["defaultConfiguration"]["level"], "warning");
+    assert_eq!(auth_rule["properties"]["category"], "Security");
+    assert!(!auth_rule["help"]["text"].as_str().unwrap().is_empty());
+    assert_eq!(
+        auth_rule["shortDescription"]["text"],
+        "Public functions that never call `ctx.auth.getUserIdentity()`"
+    );
+    assert!(auth_rule["helpUri"]
+        .as_str()
+        .unwrap()
+        .starts_with("https://"));
+    let fixable = rules
+        .iter()
+        .find(|r| r["id"] == "correctness/unwaited-promise")
+        .unwrap();
+    assert_eq!(fixable["properties"]["fixable"], true);
+    assert_eq!(fixable["properties"]["projectLevel"], false);
+
+    let results = run["results"].as_array().unwrap();
+    assert_eq!(results.len(), 2);
+    assert_eq!(results[1]["ruleId"], "perf/unbounded-collect");
+    let index = results[1]["ruleIndex"].as_u64().unwrap() as usize;
+    assert_eq!(rules[index]["id"], "perf/unbounded-collect");
+    let location = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(location["artifactLocation"]["uri"], "convex/messages.ts");
+    assert_eq!(location["region"]["startLine"], 22);
+    assert_eq!(location["region"]["startColumn"], 10);
+}
+
+#[test]
+fn test_sarif_paths_are_relative_and_project_level_results_have_no_region() {
+    use convex_doctor::reporter::sarif::SarifReporter;
+
+    let diagnostics = vec![
+        Diagnostic {
+            rule: "perf/unbounded-collect".to_string(),
+            severity: Severity::Info,
+            category: Category::Performance,
+            message: "Unbounded collect".to_string(),
+            help: "Use take".to_string(),
+            file: "/repo/app/convex/messages.ts".to_string(),
+            line: 3,
+            column: 0,
+            fix: None,
+        },
+        Diagnostic {
+            rule: "config/missing-convex-json".to_string(),
+            severity: Severity::Warning,
+            category: Category::Configuration,
+            message: "No convex.json found in project root".to_string(),
+            help: "Create convex.json".to_string(),
+            file: "convex/".to_string(),
+            line: 0,
+            column: 0,
+            fix: None,
+        },
+    ];
+    let score = compute_score(&diagnostics);
+    let reporter = SarifReporter::new(std::path::Path::new("/repo/app"));
+    let output = reporter.format(
+        &diagnostics,
+        &score,
+        "app",
+        false,
+        1,
+        Duration::from_millis(1),
+    );
+    let json: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let results = json["runs"][0]["results"].as_array().unwrap();
+
+    assert_eq!(results[0]["level"], "note");
+    let first = &results[0]["locations"][0]["physicalLocation"];
+    assert_eq!(first["artifactLocation"]["uri"], "convex/messages.ts");
+    assert_eq!(first["region"]["startLine"], 3);
+    assert!(first["region"].get("startColumn").is_none());
+
+    let second = &results[1]["locations"][0]["physicalLocation"];
+    assert_eq!(second["artifactLocation"]["uri"], "convex/");
+    assert!(second.get("region").is_none());
+}
@@ -17,7 +17,7 @@ fn test_registry_has_all_categories() {
 #[test]
 fn test_registry_rule_count() {
     let registry = RuleRegistry::new();
-    assert_eq!(registry.rules().len(), 72);
+    assert_eq!(registry.rules().len(), 76);
 }
 
 #[test]
@@ -127,6 +127,10 @@ fn test_convex_function_is_public() {
         handler_line_count: 10,
         span_line: 5,
         span_col: 1,
+        end_line: 5,
+        helper_calls: vec![],
+        auth_helper: None,
+        imported_builder: None,
     };
     assert!(public_fn.is_public());
 
@@ -143,6 +147,10 @@ fn test_convex_function_is_public() {
         handler_line_count: 10,
         span_line: 5,
         span_col: 1,
+        end_line: 5,
+        helper_calls: vec![],
+        auth_helper: None,
+        imported_builder: None,
     };
     assert!(!internal_fn.is_public());
 }
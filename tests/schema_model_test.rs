@@ -0,0 +1,226 @@
+use std::path::{Path, PathBuf};
+
+use convex_doctor::rules::context::analyze_file;
+use convex_doctor::rules::schema_model::{SchemaModel, Validator};
+use tempfile::TempDir;
+
+fn write(dir: &Path, relative: &str, source: &str) -> PathBuf {
+    let path = dir.join(relative);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(&path, source).unwrap();
+    path
+}
+
+fn build_model(dir: &TempDir, files: &[PathBuf]) -> Option<SchemaModel> {
+    let analyses: Vec<_> = files.iter().map(|p| analyze_file(p).unwrap()).collect();
+    SchemaModel::build(&dir.path().join("convex"), &analyses)
+}
+
+#[test]
+fn test_schema_model_extracts_tables_fields_and_indexes() {
+    let dir = TempDir::new().unwrap();
+    let schema = write(
+        dir.path(),
+        "convex/schema.ts",
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+
+export default defineSchema({
+  messages: defineTable({
+    author: v.id("users"),
+    body: v.string(),
+    status: v.union(v.literal("draft"), v.literal("sent")),
+    tags: v.optional(v.array(v.string())),
+    meta: v.object({ views: v.number(), extra: v.record(v.string(), v.any()) }),
+    embedding: v.array(v.float64()),
+  })
+    .index("by_author", ["author", "status"])
+    .searchIndex("search_body", { searchField: "body", filterFields: ["author"] })
+    .vectorIndex("by_embedding", { vectorField: "embedding", dimensions: 1536 }),
+});
+"#,
+    );
+    let model = build_model(&dir, &[schema]).expect("schema model");
+    assert!(!model.has_unresolved_tables);
+    let messages = model.table("messages").unwrap();
+    assert_eq!(messages.span.line, 6);
+
+    assert_eq!(
+        messages.field("author").unwrap().validator,
+        Validator::Id("users".to_string())
+    );
+    assert_eq!(
+        messages.field("status").unwrap().validator,
+        Validator::Union(vec![
+            Validator::Literal("\"draft\"".to_string()),
+            Validator::Literal("\"sent\"".to_string()),
+        ])
+    );
+    assert_eq!(
+        messages.field("tags").unwrap().validator,
+        Validator::Optional(Box::new(Validator::Array(Box::new(Validator::String))))
+    );
+    let meta = &messages.field("meta").unwrap().validator;
+    assert_eq!(meta.fields().unwrap().len(), 2);
+    assert!(meta.is_fully_resolved());
+
+    assert_eq!(
+        messages.index("by_author").unwrap().fields,
+        ["author", "status"]
+    );
+    assert_eq!(
+        messages.search_indexes[0].search_field.as_deref(),
+        Some("body")
+    );
+    assert_eq!(messages.search_indexes[0].filter_fields, ["author"]);
+    assert_eq!(messages.vector_indexes[0].dimensions, Some(1536));
+    assert_eq!(messages.vector_indexes[0].span.line, 16);
+}
+
+#[test]
+fn test_schema_model_resolves_constants_across_files() {
+    let dir = TempDir::new().unwrap();
+    let validators = write(
+        dir.path(),
+        "convex/lib/validators.ts",
+        r#"
+import { v } from "convex/values";
+
+export const statusValidator = v.union(v.literal("active"), v.literal("banned"));
+export const timestamps = { createdAt: v.number(), updatedAt: v.number() };
+export const profile = v.object({ bio: v.string() });
+"#,
+    );
+    let roles = write(
+        dir.path(),
+        "convex/lib/roles.ts",
+        r#"
+import { v } from "convex/values";
+
+export const roleValidator = v.union(v.literal("admin"), v.literal("member"));
+"#,
+    );
+    let barrel = write(
+        dir.path(),
+        "convex/lib/index.ts",
+        r#"
+export { timestamps as auditFields } from "./validators";
+export * from "./roles";
+"#,
+    );
+    let tables = write(
+        dir.path(),
+        "convex/tables.ts",
+        r#"
+import { defineTable } from "convex/server";
+import { v } from "convex/values";
+import * as validators from "./lib/validators";
+import { auditFields, roleValidator } from "./lib";
+
+export const users = defineTable({
+  name: v.string(),
+  status: validators.statusValidator,
+  role: roleValidator,
+  ...validators.timestamps,
+  ...validators.profile.fields,
+}).index("by_name", ["name"]);
+
+export const events = defineTable({ ...auditFields });
+"#,
+    );
+    let schema = write(
+        dir.path(),
+        "convex/schema.ts",
+        r#"
+import { defineSchema } from "convex/server";
+import { authTables } from "@convex-dev/auth/server";
+import { events, users } from "./tables";
+
+export default defineSchema({
+  ...authTables,
+  users: users.index("by_status", ["status"]),
+  events,
+});
+"#,
+    );
+    let model = build_model(&dir, &[validators, roles, barrel, tables, schema]).unwrap();
+    assert!(model.has_unresolved_tables, "authTables cannot be resolved");
+
+    let users = model.table("users").unwrap();
+    let names: Vec<_> = users
+        .fields()
+        .unwrap()
+        .iter()
+        .map(|f| f.name.as_str())
+        .collect();
+    assert_eq!(
+        names,
+        ["createdAt", "updatedAt", "bio", "name", "status", "role"]
+    );
+    assert!(matches!(
+        users.field("status").unwrap().validator,
+        Validator::Union(ref members) if members.len() == 2
+    ));
+    // Re-exported through the `lib/index.ts` barrel.
+    assert!(matches!(
+        users.field("role").unwrap().validator,
+        Validator::Union(ref members) if members.len() == 2
+    ));
+    let events = model.table("events").unwrap();
+    assert!(events.document.as_ref().unwrap().is_fully_resolved());
+    assert!(events.field("updatedAt").is_some());
+    assert!(users
+        .field("bio")
+        .unwrap()
+        .span
+        .file
+        .ends_with("validators.ts"));
+    assert!(users.document.as_ref().unwrap().is_fully_resolved());
+    let indexes: Vec<_> = users.indexes.iter().map(|i| i.name.as_str()).collect();
+    assert_eq!(indexes, ["by_name", "by_status"]);
+}
+
+#[test]
+fn test_schema_model_keeps_unresolved_references() {
+    let dir = TempDir::new().unwrap();
+    let schema = write(
+        dir.path(),
+        "convex/schema.ts",
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+import { shared } from "../shared/validators";
+
+const local = v.object({ a: v.string() });
+
+export default defineSchema({
+  items: defineTable({ ...shared, nested: local, other: missing }),
+});
+"#,
+    );
+    let model = build_model(&dir, &[schema]).unwrap();
+    let items = model.table("items").unwrap();
+    let document = items.document.as_ref().unwrap();
+    assert!(!document.is_fully_resolved());
+    assert!(matches!(document, Validator::Object { spreads, .. } if spreads == &["shared"]));
+    assert!(items.field("nested").unwrap().validator.fields().is_some());
+    assert_eq!(
+        items.field("other").unwrap().validator,
+        Validator::Reference("missing".to_string())
+    );
+}
+
+#[test]
+fn test_schema_model_requires_schema_module() {
+    let dir = TempDir::new().unwrap();
+    let other = write(
+        dir.path(),
+        "convex/notSchema.ts",
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+export default defineSchema({ a: defineTable({}) });
+"#,
+    );
+    assert!(build_model(&dir, &[other]).is_none());
+}
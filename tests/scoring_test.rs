@@ -1,5 +1,5 @@
 use convex_doctor::diagnostic::{Category, Diagnostic, Severity};
-use convex_doctor::scoring::compute_score;
+use convex_doctor::scoring::{aggregate_score, compute_score, ScoreResult};
 
 fn make_diagnostic(rule: &str, severity: Severity, category: Category) -> Diagnostic {
     Diagnostic {
@@ -11,6 +11,7 @@ fn make_diagnostic(rule: &str, severity: Severity, category: Category) -> Diagno
         file: "convex/test.ts".to_string(),
         line: 1,
         column: 1,
+        fix: None,
     }
 }
 
@@ -132,3 +133,16 @@ fn test_multiple_categories() {
     // total deduction = 5.72, score = 94
     assert_eq!(result.value, 94);
 }
+
+#[test]
+fn test_aggregate_score_weights_by_files_scanned() {
+    let score = |value| ScoreResult { value, label: "" };
+    let result = aggregate_score(&[(score(100), 30), (score(40), 10)]);
+    assert_eq!(result.value, 85);
+    assert_eq!(result.label, "Healthy");
+
+    // Projects without files count equally.
+    let result = aggregate_score(&[(score(100), 0), (score(50), 0)]);
+    assert_eq!(result.value, 75);
+    assert_eq!(result.label, "Needs attention");
+}
@@ -443,3 +443,521 @@ export const webhookPublic = httpAction(async (ctx, request) => {
         diagnostics
     );
 }
+
+fn write_auth_helper_project(dir: &Path) {
+    let lib = dir.join("convex/lib");
+    std::fs::create_dir_all(&lib).unwrap();
+    std::fs::write(
+        lib.join("auth.ts"),
+        r#"
+import { QueryCtx } from "../_generated/server";
+
+export async function getViewer(context: QueryCtx) {
+  const identity = await context.auth.getUserIdentity();
+  if (!identity) {
+    throw new Error("Not signed in");
+  }
+  return identity;
+}
+
+export const formatName = (name: string) => name.trim();
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        lib.join("membership.ts"),
+        r#"
+import { getViewer } from "./auth";
+
+export const loadMembership = async (ctx, orgId) => {
+  const viewer = await getViewer(ctx);
+  return await ctx.db
+    .query("members")
+    .withIndex("by_org_user", (q) => q.eq("orgId", orgId).eq("user", viewer.subject))
+    .unique();
+};
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("convex/orgs.ts"),
+        r#"
+import { query } from "./_generated/server";
+import { v } from "convex/values";
+import { loadMembership } from "./lib/membership";
+import * as auth from "./lib/auth";
+import { formatName } from "./lib/auth";
+
+export const members = query({
+  args: { orgId: v.id("orgs") },
+  returns: v.any(),
+  handler: async (ctx, { orgId }) => {
+    await loadMembership(ctx, orgId);
+    return null;
+  },
+});
+
+export const viewer = query({
+  args: {},
+  returns: v.any(),
+  handler: async (ctx) => {
+    return await auth.getViewer(ctx);
+  },
+});
+
+export const rename = query({
+  args: { userId: v.id("users") },
+  returns: v.any(),
+  handler: async (ctx, { userId }) => {
+    return formatName(userId);
+  },
+});
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_auth_checks_through_helpers_are_resolved_across_files() {
+    use convex_doctor::config::AuthConfig;
+    use convex_doctor::rules::auth_summary::resolve_auth_helpers;
+
+    let dir = TempDir::new().unwrap();
+    write_auth_helper_project(dir.path());
+    let mut analyses: Vec<_> = [
+        "convex/orgs.ts",
+        "convex/lib/membership.ts",
+        "convex/lib/auth.ts",
+    ]
+    .iter()
+    .map(|file| analyze_file(&dir.path().join(file)).unwrap())
+    .collect();
+    resolve_auth_helpers(&mut analyses, &AuthConfig::default());
+
+    let orgs = &analyses[0];
+    let function = |name: &str| orgs.functions.iter().find(|f| f.name == name).unwrap();
+    assert_eq!(
+        function("members").auth_helper.as_deref(),
+        Some("loadMembership")
+    );
+    assert_eq!(
+        function("viewer").auth_helper.as_deref(),
+        Some("auth.getViewer")
+    );
+    assert_eq!(function("rename").auth_helper, None);
+
+    let missing_auth: Vec<_> = MissingAuthCheck
+        .check(orgs)
+        .into_iter()
+        .map(|d| d.message)
+        .collect();
+    assert_eq!(missing_auth.len(), 1, "{missing_auth:?}");
+    assert!(missing_auth[0].contains("`rename`"));
+
+    let spoofable = SpoofableAccessControl.check(orgs);
+    assert_eq!(spoofable.len(), 1);
+    assert!(spoofable[0].message.contains("`rename`"));
+}
+
+#[test]
+fn test_auth_helpers_in_unanalyzed_files_are_loaded_on_demand() {
+    use convex_doctor::config::AuthConfig;
+    use convex_doctor::rules::auth_summary::resolve_auth_helpers;
+
+    let dir = TempDir::new().unwrap();
+    write_auth_helper_project(dir.path());
+    let mut analyses = vec![analyze_file(&dir.path().join("convex/orgs.ts")).unwrap()];
+    resolve_auth_helpers(&mut analyses, &AuthConfig::default());
+
+    let diagnostics = MissingAuthCheck.check(&analyses[0]);
+    assert_eq!(diagnostics.len(), 1);
+    assert!(diagnostics[0].message.contains("`rename`"));
+}
+
+#[test]
+fn test_configured_auth_helpers_and_wrappers_count_as_auth_checks() {
+    use convex_doctor::config::AuthConfig;
+    use convex_doctor::rules::context::analyze_file_with_auth;
+    use convex_doctor::rules::FunctionKind;
+
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("orgs.ts");
+    std::fs::write(
+        &path,
+        r#"
+import { query } from "./_generated/server";
+import { v } from "convex/values";
+import { authedQuery, internalAdminMutation } from "./lib/functions";
+import { mustGetCurrentUser } from "@acme/auth";
+
+export const list = query({
+  args: { orgId: v.id("orgs") },
+  returns: v.any(),
+  handler: async (ctx, { orgId }) => {
+    await mustGetCurrentUser(ctx);
+    return null;
+  },
+});
+
+export const members = authedQuery({
+  args: { userId: v.id("users") },
+  returns: v.any(),
+  handler: async (ctx, { userId }) => null,
+});
+
+export const promote = internalAdminMutation({
+  args: { userId: v.id("users") },
+  returns: v.null(),
+  handler: async (ctx, { userId }) => null,
+});
+"#,
+    )
+    .unwrap();
+
+    // Unconfigured, the imported builders are still recognized by name, but
+    // nothing says they authenticate the caller.
+    let unconfigured = analyze_file(&path).unwrap();
+    assert_eq!(unconfigured.functions.len(), 3);
+    assert_eq!(MissingAuthCheck.check(&unconfigured).len(), 2);
+
+    let auth = AuthConfig {
+        helpers: vec!["mustGetCurrentUser".to_string()],
+        wrappers: vec![
+            "authedQuery".to_string(),
+            "internalAdminMutation".to_string(),
+        ],
+    };
+    let analysis = analyze_file_with_auth(&path, &auth).unwrap();
+    let kinds: Vec<_> = analysis
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.kind.clone()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            ("list", FunctionKind::Query),
+            ("members", FunctionKind::Query),
+            ("promote", FunctionKind::InternalMutation),
+        ]
+    );
+    assert!(MissingAuthCheck.check(&analysis).is_empty());
+    assert!(SpoofableAccessControl.check(&analysis).is_empty());
+}
+
+fn write_custom_builder_project(dir: &Path) {
+    let lib = dir.join("convex/lib");
+    std::fs::create_dir_all(&lib).unwrap();
+    std::fs::write(
+        lib.join("functions.ts"),
+        r#"
+import { query, internalMutation } from "../_generated/server";
+import {
+  customCtx,
+  customMutation,
+  customQuery as makeQuery,
+} from "convex-helpers/server/customFunctions";
+import { zCustomQuery } from "convex-helpers/server/zod";
+import { NoOp } from "convex-helpers/server/customFunctions";
+import { getViewer } from "./auth";
+
+export const userQuery = makeQuery(
+  query,
+  customCtx(async (ctx) => {
+    const user = await getViewer(ctx);
+    return { user };
+  }),
+);
+
+export const adminMutation = customMutation(internalMutation, {
+  args: {},
+  input: async (ctx, args) => {
+    const identity = await ctx.auth.getUserIdentity();
+    return { ctx: { identity }, args };
+  },
+});
+
+export const zQuery = zCustomQuery(query, NoOp);
+export const authed = userQuery;
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        lib.join("auth.ts"),
+        r#"
+export async function getViewer(ctx) {
+  const identity = await ctx.auth.getUserIdentity();
+  if (!identity) throw new Error("Not signed in");
+  return identity;
+}
+"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.join("convex/messages.ts"),
+        r#"
+import { v } from "convex/values";
+import { authed, adminMutation, zQuery } from "./lib/functions";
+import { defineThing } from "./lib/things";
+
+const listQuery = authed;
+
+export const list = listQuery({
+  args: {},
+  returns: v.any(),
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").take(10);
+  },
+});
+
+export const purge = adminMutation({
+  args: { id: v.id("messages") },
+  handler: async (ctx, { id }) => {
+    await ctx.db.delete(id);
+  },
+});
+
+export const search = zQuery({
+  args: {},
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").take(10);
+  },
+});
+
+export const thing = defineThing({
+  handler: () => null,
+});
+"#,
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_custom_function_builders_are_resolved_across_files() {
+    use convex_doctor::config::AuthConfig;
+    use convex_doctor::rules::auth_summary::resolve_auth_helpers;
+    use convex_doctor::rules::FunctionKind;
+
+    let dir = TempDir::new().unwrap();
+    write_custom_builder_project(dir.path());
+    let mut analyses = vec![analyze_file(&dir.path().join("convex/messages.ts")).unwrap()];
+    resolve_auth_helpers(&mut analyses, &AuthConfig::default());
+
+    let messages = &analyses[0];
+    let functions: Vec<_> = messages
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.kind.clone(), f.checks_auth()))
+        .collect();
+    assert_eq!(
+        functions,
+        vec![
+            ("list", FunctionKind::Query, true),
+            ("purge", FunctionKind::InternalMutation, true),
+            ("search", FunctionKind::Query, false),
+        ]
+    );
+
+    let missing_auth = MissingAuthCheck.check(messages);
+    assert_eq!(missing_auth.len(), 1);
+    assert!(missing_auth[0].message.contains("`search`"));
+    assert_eq!(MissingReturnValidators.check(messages).len(), 1);
+}
+
+#[test]
+fn test_local_custom_builders_infer_kind_and_auth() {
+    use convex_doctor::rules::FunctionKind;
+
+    let dir = TempDir::new().unwrap();
+    write_custom_builder_project(dir.path());
+    let path = dir.path().join("convex/lib/functions.ts");
+    let mut source = std::fs::read_to_string(&path).unwrap();
+    source.push_str(
+        r#"
+export const me = authed({
+  args: {},
+  handler: async (ctx) => ctx.user,
+});
+
+export const open = zQuery({
+  args: {},
+  handler: async () => null,
+});
+"#,
+    );
+    std::fs::write(&path, source).unwrap();
+
+    let analysis = analyze_file(&path).unwrap();
+    let builders: Vec<_> = analysis
+        .custom_builders
+        .iter()
+        .map(|b| {
+            (
+                b.name.as_str(),
+                b.base.as_str(),
+                b.kind.clone(),
+                b.reads_auth,
+            )
+        })
+        .collect();
+    assert_eq!(
+        builders,
+        vec![
+            ("userQuery", "query", Some(FunctionKind::Query), false),
+            (
+                "adminMutation",
+                "internalMutation",
+                Some(FunctionKind::Mutation),
+                true
+            ),
+            ("zQuery", "query", Some(FunctionKind::Query), false),
+            ("authed", "userQuery", None, false),
+        ]
+    );
+    assert_eq!(analysis.custom_builders[0].calls, vec!["getViewer"]);
+
+    let me = analysis.functions.iter().find(|f| f.name == "me").unwrap();
+    assert_eq!(me.kind, FunctionKind::Query);
+    assert_eq!(me.helper_calls, vec!["getViewer"]);
+    assert_eq!(me.imported_builder, None);
+    let open = analysis
+        .functions
+        .iter()
+        .find(|f| f.name == "open")
+        .unwrap();
+    assert!(!open.checks_auth());
+}
+
+#[test]
+fn test_builders_re_exported_through_a_barrel_are_resolved() {
+    use convex_doctor::config::AuthConfig;
+    use convex_doctor::rules::auth_summary::resolve_auth_helpers;
+    use convex_doctor::rules::FunctionKind;
+
+    let dir = TempDir::new().unwrap();
+    write_custom_builder_project(dir.path());
+    let lib = dir.path().join("convex/lib");
+    std::fs::write(
+        lib.join("index.ts"),
+        r#"
+export { internalQuery as privateQuery } from "../_generated/server";
+export { userQuery as signedIn } from "./functions";
+export * from "./functions";
+export * as server from "../_generated/server";
+"#,
+    )
+    .unwrap();
+    let path = dir.path().join("convex/users.ts");
+    std::fs::write(
+        &path,
+        r#"
+import { privateQuery, signedIn, adminMutation } from "./lib";
+import * as fns from "./lib";
+
+export const lookup = privateQuery({
+  args: {},
+  handler: async () => null,
+});
+
+export const me = signedIn({
+  args: {},
+  handler: async () => null,
+});
+
+export const ban = adminMutation({
+  args: {},
+  handler: async () => null,
+});
+
+export const update = fns.adminMutation({
+  args: {},
+  handler: async () => null,
+});
+"#,
+    )
+    .unwrap();
+
+    let mut analyses = vec![analyze_file(&path).unwrap()];
+    resolve_auth_helpers(&mut analyses, &AuthConfig::default());
+    let functions: Vec<_> = analyses[0]
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.kind.clone(), f.checks_auth()))
+        .collect();
+    assert_eq!(
+        functions,
+        vec![
+            ("lookup", FunctionKind::InternalQuery, false),
+            ("me", FunctionKind::Query, true),
+            ("ban", FunctionKind::InternalMutation, true),
+            ("update", FunctionKind::InternalMutation, true),
+        ]
+    );
+}
+
+#[test]
+fn test_zod_validators_are_read_like_convex_validators() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("profiles.ts");
+    std::fs::write(
+        &path,
+        r#"
+import { z } from "zod";
+import { query, mutation } from "./_generated/server";
+import { zCustomMutation, zCustomQuery, zid } from "convex-helpers/server/zod";
+import { NoOp } from "convex-helpers/server/customFunctions";
+
+const zQuery = zCustomQuery(query, NoOp);
+const zMutation = zCustomMutation(mutation, NoOp);
+
+export const get = zQuery({
+  args: z.object({ userId: zid("users"), fields: z.array(z.string()) }).strict(),
+  output: z.object({ name: z.string() }),
+  handler: async (ctx, { userId }) => {
+    return { name: "x" };
+  },
+});
+
+export const update = zMutation({
+  args: { profileId: zid("profiles"), patch: z.unknown().optional() },
+  handler: async (ctx, args) => null,
+});
+
+export const remove = zMutation({
+  args: { profileId: zid("profiles"), reason: z.string() },
+  output: z.null(),
+  handler: async (ctx, args) => null,
+});
+"#,
+    )
+    .unwrap();
+
+    let analysis = analyze_file(&path).unwrap();
+    let arg_names: Vec<_> = analysis
+        .functions
+        .iter()
+        .map(|f| (f.name.as_str(), f.arg_names.clone()))
+        .collect();
+    assert_eq!(
+        arg_names,
+        vec![
+            ("get", vec!["userId".to_string(), "fields".to_string()]),
+            ("update", vec!["profileId".to_string(), "patch".to_string()]),
+            (
+                "remove",
+                vec!["profileId".to_string(), "reason".to_string()]
+            ),
+        ]
+    );
+
+    assert!(MissingArgValidators.check(&analysis).is_empty());
+    let missing_returns = MissingReturnValidators.check(&analysis);
+    assert_eq!(missing_returns.len(), 1);
+    assert!(missing_returns[0].message.contains("`update`"));
+    let generic_args = GenericMutationArgs.check(&analysis);
+    assert_eq!(generic_args.len(), 1);
+    assert!(generic_args[0].message.contains("update"));
+    let spoofable = SpoofableAccessControl.check(&analysis);
+    assert_eq!(spoofable.len(), 1);
+    assert!(spoofable[0].message.contains("`get`"));
+}
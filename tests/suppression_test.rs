@@ -0,0 +1,143 @@
+use convex_doctor::engine::{run_with_options, RunOptions};
+use convex_doctor::rules::context::analyze_file;
+use convex_doctor::suppression::SuppressionKind;
+use tempfile::TempDir;
+
+fn project_with(source: &str) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("messages.ts"), source).unwrap();
+    dir
+}
+
+fn rule_count(dir: &TempDir, options: &RunOptions, rule: &str) -> usize {
+    run_with_options(dir.path(), options)
+        .unwrap()
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == rule)
+        .count()
+}
+
+#[test]
+fn test_parse_directives_with_rules_and_reason() {
+    let dir = project_with(
+        r#"
+/* convex-doctor-disable-file arch/no-convex-error */
+// convex-doctor-disable-next-line security/missing-auth-check, perf/unbounded-collect -- public feed
+export const x = 1;
+// convex-doctor-disable
+// convex-doctor-enable
+"#,
+    );
+    let analysis = analyze_file(&dir.path().join("convex/messages.ts")).unwrap();
+    let directives = &analysis.suppressions;
+    assert_eq!(directives.len(), 4);
+
+    assert_eq!(directives[0].kind, SuppressionKind::File);
+    assert_eq!(directives[0].rules, vec!["arch/no-convex-error"]);
+
+    assert_eq!(directives[1].kind, SuppressionKind::NextLine);
+    assert_eq!(
+        directives[1].rules,
+        vec!["security/missing-auth-check", "perf/unbounded-collect"]
+    );
+    assert_eq!(directives[1].reason.as_deref(), Some("public feed"));
+    assert_eq!(directives[1].line, 3);
+
+    assert_eq!(directives[2].kind, SuppressionKind::Disable);
+    assert!(directives[2].rules.is_empty());
+    assert_eq!(directives[3].kind, SuppressionKind::Enable);
+}
+
+#[test]
+fn test_disable_next_line_suppresses_only_named_rule() {
+    let dir = project_with(
+        r#"
+import { query } from "convex/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx) => {
+    // convex-doctor-disable-next-line perf/unbounded-collect -- small table
+    const a = await ctx.db.query("tags").collect();
+    const b = await ctx.db.query("messages").collect();
+    return [a, b];
+  },
+});
+"#,
+    );
+    let options = RunOptions::default();
+    assert_eq!(rule_count(&dir, &options, "perf/unbounded-collect"), 1);
+}
+
+#[test]
+fn test_disable_enable_block() {
+    let dir = project_with(
+        r#"
+import { query } from "convex/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx) => {
+    // convex-doctor-disable perf/unbounded-collect
+    const a = await ctx.db.query("tags").collect();
+    const b = await ctx.db.query("labels").collect();
+    // convex-doctor-enable perf/unbounded-collect
+    const c = await ctx.db.query("messages").collect();
+    return [a, b, c];
+  },
+});
+"#,
+    );
+    let options = RunOptions::default();
+    assert_eq!(rule_count(&dir, &options, "perf/unbounded-collect"), 1);
+}
+
+#[test]
+fn test_disable_file_suppresses_rule_everywhere_in_file() {
+    let dir = project_with(
+        r#"
+/* convex-doctor-disable-file security/missing-auth-check */
+import { query } from "convex/server";
+import { v } from "convex/values";
+
+export const a = query({ args: {}, returns: v.null(), handler: async () => null });
+export const b = query({ args: {}, returns: v.null(), handler: async () => null });
+"#,
+    );
+    let options = RunOptions::default();
+    assert_eq!(rule_count(&dir, &options, "security/missing-auth-check"), 0);
+    assert_eq!(rule_count(&dir, &options, "config/unused-suppression"), 0);
+}
+
+#[test]
+fn test_report_unused_suppressions() {
+    let dir = project_with(
+        r#"
+import { query } from "convex/server";
+import { v } from "convex/values";
+
+// convex-doctor-disable-next-line perf/unbounded-collect -- stale
+export const a = query({ args: {}, returns: v.null(), handler: async () => null });
+"#,
+    );
+
+    let options = RunOptions::default();
+    assert_eq!(rule_count(&dir, &options, "config/unused-suppression"), 0);
+
+    let options = RunOptions {
+        report_unused_suppressions: true,
+        ..RunOptions::default()
+    };
+    let result = run_with_options(dir.path(), &options).unwrap();
+    let unused: Vec<_> = result
+        .diagnostics
+        .iter()
+        .filter(|d| d.rule == "config/unused-suppression")
+        .collect();
+    assert_eq!(unused.len(), 1);
+    assert_eq!(unused[0].line, 5);
+    assert!(unused[0].message.contains("perf/unbounded-collect"));
+}
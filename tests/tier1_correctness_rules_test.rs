@@ -725,3 +725,86 @@ export default mutation({
         "Should detect runAt return value not captured"
     );
 }
+
+// ---------------------------------------------------------------------------
+// 9. UnknownTable (project-level)
+// ---------------------------------------------------------------------------
+
+fn unknown_table_ctx(schema: &str, source: &str) -> ProjectContext {
+    use convex_doctor::rules::schema_model::SchemaModel;
+
+    let dir = TempDir::new().unwrap();
+    let convex = dir.path().join("convex");
+    std::fs::create_dir_all(&convex).unwrap();
+    std::fs::write(convex.join("schema.ts"), schema).unwrap();
+    std::fs::write(convex.join("messages.ts"), source).unwrap();
+    let analyses = vec![
+        analyze_file(&convex.join("schema.ts")).unwrap(),
+        analyze_file(&convex.join("messages.ts")).unwrap(),
+    ];
+    ProjectContext {
+        has_schema: true,
+        all_table_name_refs: analyses
+            .iter()
+            .flat_map(|a| a.table_name_refs.clone())
+            .collect(),
+        schema: SchemaModel::build(&convex, &analyses),
+        ..Default::default()
+    }
+}
+
+const UNKNOWN_TABLE_SOURCE: &str = r#"
+import { mutation } from "./_generated/server";
+import { v } from "convex/values";
+
+export const send = mutation({
+  args: { author: v.id("user"), channel: v.id("channels") },
+  handler: async (ctx, args) => {
+    await ctx.db.query("mesages").collect();
+    await ctx.db.insert("messages", { body: "hi" });
+    await ctx.db.normalizeId("invoices", "abc");
+    await ctx.db.get("_storage", args.author);
+  },
+});
+"#;
+
+#[test]
+fn test_unknown_table_flags_typos_with_suggestions() {
+    let ctx = unknown_table_ctx(
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+
+export default defineSchema({
+  users: defineTable({ name: v.string() }),
+  channels: defineTable({ name: v.string() }),
+  messages: defineTable({ body: v.string() }),
+});
+"#,
+        UNKNOWN_TABLE_SOURCE,
+    );
+    let diagnostics = UnknownTable.check_project(&ctx);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages.len(), 3, "{messages:?}");
+    assert!(messages[0].starts_with("`v.id` references table `user`"));
+    assert!(messages[0].ends_with("did you mean `users`?"));
+    assert!(messages[1].contains("`mesages`") && messages[1].ends_with("`messages`?"));
+    assert!(messages[2].contains("`invoices`") && !messages[2].contains("did you mean"));
+}
+
+#[test]
+fn test_unknown_table_skips_schemas_with_unresolved_tables() {
+    let ctx = unknown_table_ctx(
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+import { authTables } from "@convex-dev/auth/server";
+
+export default defineSchema({ ...authTables });
+"#,
+        UNKNOWN_TABLE_SOURCE,
+    );
+    assert!(UnknownTable.check_project(&ctx).is_empty());
+    assert!(UnknownTable
+        .check_project(&ProjectContext::default())
+        .is_empty());
+}
@@ -160,6 +160,10 @@ fn test_action_from_client_ignores_internal_action() {
             handler_line_count: 10,
             span_line: 1,
             span_col: 1,
+            end_line: 1,
+            helper_calls: vec![],
+            auth_helper: None,
+            imported_builder: None,
         }],
         ..Default::default()
     };
@@ -188,6 +192,10 @@ fn test_action_from_client_ignores_queries_and_mutations() {
                 handler_line_count: 5,
                 span_line: 1,
                 span_col: 1,
+                end_line: 1,
+                helper_calls: vec![],
+                auth_helper: None,
+                imported_builder: None,
             },
             ConvexFunction {
                 name: "updateItem".to_string(),
@@ -202,6 +210,10 @@ fn test_action_from_client_ignores_queries_and_mutations() {
                 handler_line_count: 5,
                 span_line: 10,
                 span_col: 1,
+                end_line: 10,
+                helper_calls: vec![],
+                auth_helper: None,
+                imported_builder: None,
             },
         ],
         ..Default::default()
@@ -364,6 +376,10 @@ fn test_no_pagination_for_list_no_public_query() {
             handler_line_count: 5,
             span_line: 1,
             span_col: 1,
+            end_line: 1,
+            helper_calls: vec![],
+            auth_helper: None,
+            imported_builder: None,
         }],
         ctx_calls: vec![CtxCall {
             chain: "ctx.db.query.collect".to_string(),
@@ -376,6 +392,8 @@ fn test_no_pagination_for_list_no_public_query() {
             enclosing_function_id: None,
             enclosing_function_name: Some("getItems".to_string()),
             enclosing_function_has_internal_secret: false,
+            span: Default::default(),
+            first_arg_root: None,
             first_arg_chain: None,
         }],
         ..Default::default()
@@ -404,6 +422,10 @@ fn test_no_pagination_for_list_no_collect() {
             handler_line_count: 5,
             span_line: 1,
             span_col: 1,
+            end_line: 1,
+            helper_calls: vec![],
+            auth_helper: None,
+            imported_builder: None,
         }],
         collect_calls: vec![], // no collect calls
         ..Default::default()
@@ -433,6 +455,10 @@ fn test_no_pagination_emits_one_diagnostic_per_file() {
                 handler_line_count: 5,
                 span_line: 1,
                 span_col: 1,
+                end_line: 1,
+                helper_calls: vec![],
+                auth_helper: None,
+                imported_builder: None,
             },
             ConvexFunction {
                 name: "getOtherItems".to_string(),
@@ -447,6 +473,10 @@ fn test_no_pagination_emits_one_diagnostic_per_file() {
                 handler_line_count: 5,
                 span_line: 10,
                 span_col: 1,
+                end_line: 10,
+                helper_calls: vec![],
+                auth_helper: None,
+                imported_builder: None,
             },
         ],
         ctx_calls: vec![
@@ -461,6 +491,8 @@ fn test_no_pagination_emits_one_diagnostic_per_file() {
                 enclosing_function_id: None,
                 enclosing_function_name: Some("getItems".to_string()),
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
                 first_arg_chain: None,
             },
             CtxCall {
@@ -474,6 +506,8 @@ fn test_no_pagination_emits_one_diagnostic_per_file() {
                 enclosing_function_id: None,
                 enclosing_function_name: Some("getOtherItems".to_string()),
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
                 first_arg_chain: None,
             },
         ],
@@ -505,6 +539,10 @@ fn test_no_pagination_does_not_cross_trigger_from_mutation_collect() {
                 handler_line_count: 5,
                 span_line: 1,
                 span_col: 1,
+                end_line: 1,
+                helper_calls: vec![],
+                auth_helper: None,
+                imported_builder: None,
             },
             ConvexFunction {
                 name: "mutateItems".to_string(),
@@ -519,6 +557,10 @@ fn test_no_pagination_does_not_cross_trigger_from_mutation_collect() {
                 handler_line_count: 5,
                 span_line: 10,
                 span_col: 1,
+                end_line: 10,
+                helper_calls: vec![],
+                auth_helper: None,
+                imported_builder: None,
             },
         ],
         ctx_calls: vec![CtxCall {
@@ -532,6 +574,8 @@ fn test_no_pagination_does_not_cross_trigger_from_mutation_collect() {
             enclosing_function_id: None,
             enclosing_function_name: Some("mutateItems".to_string()),
             enclosing_function_has_internal_secret: false,
+            span: Default::default(),
+            first_arg_root: None,
             first_arg_chain: None,
         }],
         ..Default::default()
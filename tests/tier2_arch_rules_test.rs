@@ -23,6 +23,8 @@ fn action_without_scheduling_flags_run_action_in_mutation() {
             enclosing_function_name: None,
             first_arg_chain: None,
             enclosing_function_has_internal_secret: false,
+            span: Default::default(),
+            first_arg_root: None,
         }],
         ..Default::default()
     };
@@ -51,6 +53,8 @@ fn action_without_scheduling_flags_run_action_in_internal_mutation() {
             enclosing_function_name: None,
             first_arg_chain: None,
             enclosing_function_has_internal_secret: false,
+            span: Default::default(),
+            first_arg_root: None,
         }],
         ..Default::default()
     };
@@ -76,6 +80,8 @@ fn action_without_scheduling_ignores_run_action_in_action() {
             enclosing_function_name: None,
             first_arg_chain: None,
             enclosing_function_has_internal_secret: false,
+            span: Default::default(),
+            first_arg_root: None,
         }],
         ..Default::default()
     };
@@ -101,6 +107,8 @@ fn action_without_scheduling_ignores_run_query_in_mutation() {
             enclosing_function_name: None,
             first_arg_chain: None,
             enclosing_function_has_internal_secret: false,
+            span: Default::default(),
+            first_arg_root: None,
         }],
         ..Default::default()
     };
@@ -165,6 +173,10 @@ fn make_function(name: &str, kind: FunctionKind) -> ConvexFunction {
         handler_line_count: 10,
         span_line: 1,
         span_col: 1,
+        end_line: 1,
+        helper_calls: vec![],
+        auth_helper: None,
+        imported_builder: None,
     }
 }
 
@@ -264,6 +276,10 @@ fn make_large_function(name: &str, kind: FunctionKind, lines: u32) -> ConvexFunc
         handler_line_count: lines,
         span_line: 1,
         span_col: 1,
+        end_line: 1,
+        helper_calls: vec![],
+        auth_helper: None,
+        imported_builder: None,
     }
 }
 
@@ -413,6 +429,8 @@ fn make_action_ctx_call(chain: &str, line: u32) -> CtxCall {
         enclosing_function_name: Some("anonymous_action".to_string()),
         first_arg_chain: None,
         enclosing_function_has_internal_secret: false,
+        span: Default::default(),
+        first_arg_root: None,
     }
 }
 
@@ -454,6 +472,8 @@ fn deep_function_chain_skips_internal_secret_actions() {
                 enclosing_function_name: Some("syncUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: true,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runMutation".to_string(),
@@ -468,6 +488,8 @@ fn deep_function_chain_skips_internal_secret_actions() {
                 enclosing_function_name: Some("syncUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: true,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runQuery".to_string(),
@@ -482,6 +504,8 @@ fn deep_function_chain_skips_internal_secret_actions() {
                 enclosing_function_name: Some("syncUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: true,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runMutation".to_string(),
@@ -496,6 +520,8 @@ fn deep_function_chain_skips_internal_secret_actions() {
                 enclosing_function_name: Some("syncUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: true,
+                span: Default::default(),
+                first_arg_root: None,
             },
         ],
         ..Default::default()
@@ -539,6 +565,8 @@ fn deep_function_chain_skips_sync_backfill_named_actions() {
                 enclosing_function_name: Some("backfillUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runMutation".to_string(),
@@ -553,6 +581,8 @@ fn deep_function_chain_skips_sync_backfill_named_actions() {
                 enclosing_function_name: Some("backfillUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runQuery".to_string(),
@@ -567,6 +597,8 @@ fn deep_function_chain_skips_sync_backfill_named_actions() {
                 enclosing_function_name: Some("backfillUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runMutation".to_string(),
@@ -581,6 +613,8 @@ fn deep_function_chain_skips_sync_backfill_named_actions() {
                 enclosing_function_name: Some("backfillUsers".to_string()),
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
         ],
         ..Default::default()
@@ -626,6 +660,8 @@ fn deep_function_chain_ignores_calls_in_mutations() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runQuery".to_string(),
@@ -640,6 +676,8 @@ fn deep_function_chain_ignores_calls_in_mutations() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runQuery".to_string(),
@@ -654,6 +692,8 @@ fn deep_function_chain_ignores_calls_in_mutations() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runQuery".to_string(),
@@ -668,6 +708,8 @@ fn deep_function_chain_ignores_calls_in_mutations() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
         ],
         ..Default::default()
@@ -695,6 +737,8 @@ fn deep_function_chain_counts_internal_actions_too() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runMutation".to_string(),
@@ -709,6 +753,8 @@ fn deep_function_chain_counts_internal_actions_too() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runQuery".to_string(),
@@ -723,6 +769,8 @@ fn deep_function_chain_counts_internal_actions_too() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
             CtxCall {
                 chain: "ctx.runMutation".to_string(),
@@ -737,6 +785,8 @@ fn deep_function_chain_counts_internal_actions_too() {
                 enclosing_function_name: None,
                 first_arg_chain: None,
                 enclosing_function_has_internal_secret: false,
+                span: Default::default(),
+                first_arg_root: None,
             },
         ],
         ..Default::default()
@@ -369,3 +369,99 @@ fn test_missing_index_for_query_with_filter_fields() {
     );
     assert!(diagnostics[0].message.contains("userId"));
 }
+
+// ── InvalidIndexQuery ───────────────────────────────────────────────────────
+
+fn index_query_ctx(queries: &str) -> ProjectContext {
+    use convex_doctor::rules::schema_model::SchemaModel;
+
+    let dir = TempDir::new().unwrap();
+    let convex = dir.path().join("convex");
+    std::fs::create_dir_all(&convex).unwrap();
+    std::fs::write(
+        convex.join("schema.ts"),
+        r#"
+import { defineSchema, defineTable } from "convex/server";
+import { v } from "convex/values";
+
+export default defineSchema({
+  messages: defineTable({ channel: v.id("channels"), author: v.string(), sentAt: v.number() })
+    .index("by_channel_and_author", ["channel", "author"])
+    .index("by_channel_and_sent_at", ["channel", "sentAt"]),
+  channels: defineTable({ name: v.string() }).index("by_name", ["name"]),
+});
+"#,
+    )
+    .unwrap();
+    std::fs::write(convex.join("messages.ts"), queries).unwrap();
+
+    let analyses = vec![
+        analyze_file(&convex.join("schema.ts")).unwrap(),
+        analyze_file(&convex.join("messages.ts")).unwrap(),
+    ];
+    ProjectContext {
+        has_schema: true,
+        all_with_index_calls: analyses
+            .iter()
+            .flat_map(|a| a.with_index_calls.clone())
+            .collect(),
+        schema: SchemaModel::build(&convex, &analyses),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_invalid_index_query_accepts_prefix_and_range() {
+    let ctx = index_query_ctx(
+        r#"
+import { query } from "./_generated/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx, args) => {
+    await ctx.db.query("messages").withIndex("by_channel_and_author", (q) => q.eq("channel", args.c)).collect();
+    await ctx.db
+      .query("messages")
+      .withIndex("by_channel_and_sent_at", (q) =>
+        q.eq("channel", args.c).gte("sentAt", 0).lt("sentAt", 10),
+      )
+      .collect();
+    await ctx.db.query("messages").withIndex("by_creation_time").collect();
+    await ctx.db.query("channels").withIndex("by_name", (q) => q.eq("name", "a").eq("_creationTime", 1)).unique();
+  },
+});
+"#,
+    );
+    let diagnostics = InvalidIndexQuery.check_project(&ctx);
+    assert!(diagnostics.is_empty(), "{diagnostics:?}");
+}
+
+#[test]
+fn test_invalid_index_query_flags_problems() {
+    let ctx = index_query_ctx(
+        r#"
+import { query } from "./_generated/server";
+
+export const list = query({
+  args: {},
+  handler: async (ctx, args) => {
+    await ctx.db.query("messages").withIndex("by_author", (q) => q.eq("author", "a")).collect();
+    await ctx.db.query("messages").withIndex("by_name", (q) => q.eq("name", "a")).collect();
+    await ctx.db.query("messages").withIndex("by_channel_and_author", (q) => q.eq("author", "a")).collect();
+    await ctx.db.query("messages").withIndex("by_channel_and_author", (q) => q.eq("channel", args.c).eq("sentAt", 1)).collect();
+    await ctx.db.query("messages").withIndex("by_channel_and_sent_at", (q) => q.gt("sentAt", 1).eq("channel", args.c)).collect();
+  },
+});
+"#,
+    );
+    let diagnostics = InvalidIndexQuery.check_project(&ctx);
+    let messages: Vec<&str> = diagnostics.iter().map(|d| d.message.as_str()).collect();
+    assert_eq!(messages.len(), 5, "{messages:?}");
+    assert!(messages[0].contains("`by_author` is not defined on table `messages`"));
+    assert!(messages[1].contains("`by_name` is defined on `channels`"));
+    assert!(messages[2].contains("`channel` comes before `author`"));
+    assert!(messages[3].contains("`sentAt` is not part of index `by_channel_and_author`"));
+    assert!(messages[4].contains("`channel` comes before `sentAt`"));
+    assert_eq!(diagnostics[0].severity, Severity::Error);
+    assert_eq!(diagnostics[0].line, 7);
+}
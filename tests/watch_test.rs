@@ -0,0 +1,216 @@
+use convex_doctor::diagnostic::{Category, Diagnostic, Severity};
+use convex_doctor::engine::RunOptions;
+use convex_doctor::watch::{diagnostic_delta, WatchSession};
+use tempfile::TempDir;
+
+const BOUNDED: &str = r#"
+import { query } from "./_generated/server";
+import { v } from "convex/values";
+
+export const list = query({
+  args: {},
+  returns: v.null(),
+  handler: async (ctx) => {
+    await ctx.db.query("messages").take(10);
+    return null;
+  },
+});
+"#;
+
+fn unbounded() -> String {
+    BOUNDED.replace(".take(10)", ".collect()")
+}
+
+fn diagnostic(rule: &str, line: u32) -> Diagnostic {
+    Diagnostic {
+        rule: rule.to_string(),
+        severity: Severity::Warning,
+        category: Category::Performance,
+        message: "message".to_string(),
+        help: "help".to_string(),
+        file: "convex/messages.ts".to_string(),
+        line,
+        column: 1,
+        fix: None,
+    }
+}
+
+#[test]
+fn test_watch_reports_new_and_resolved_findings() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    let file = convex_dir.join("messages.ts");
+    std::fs::write(&file, BOUNDED).unwrap();
+
+    let mut session = WatchSession::start(dir.path(), &RunOptions::default()).unwrap();
+    assert_eq!(session.files_scanned(), 1);
+    assert!(session
+        .diagnostics()
+        .iter()
+        .all(|d| d.rule != "perf/unbounded-collect"));
+    assert!(session.poll().unwrap().is_none());
+
+    std::fs::write(&file, unbounded()).unwrap();
+    let update = session.poll().unwrap().expect("edit should be detected");
+    assert_eq!(update.changed_files, vec![file.clone()]);
+    assert!(update
+        .new
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+    assert!(update.resolved.is_empty());
+    assert!(update.score.value < update.previous_score.value);
+
+    std::fs::write(&file, BOUNDED).unwrap();
+    let update = session.poll().unwrap().expect("revert should be detected");
+    assert!(update.new.is_empty());
+    assert!(update
+        .resolved
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+}
+
+#[test]
+fn test_watch_tracks_added_and_removed_files() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("messages.ts"), BOUNDED).unwrap();
+    let mut session = WatchSession::start(dir.path(), &RunOptions::default()).unwrap();
+
+    let added = convex_dir.join("more.ts");
+    std::fs::write(&added, unbounded()).unwrap();
+    let update = session
+        .poll()
+        .unwrap()
+        .expect("new file should be detected");
+    assert_eq!(update.changed_files, vec![added.clone()]);
+    assert_eq!(session.files_scanned(), 2);
+    assert!(update
+        .new
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+
+    std::fs::remove_file(&added).unwrap();
+    let update = session.poll().unwrap().expect("removal should be detected");
+    assert_eq!(session.files_scanned(), 1);
+    assert!(update
+        .resolved
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+}
+
+#[test]
+fn test_watch_reloads_config_changes() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("messages.ts"), unbounded()).unwrap();
+    let mut session = WatchSession::start(dir.path(), &RunOptions::default()).unwrap();
+
+    std::fs::write(
+        dir.path().join("convex-doctor.toml"),
+        "[rules]\n\"perf/unbounded-collect\" = \"off\"\n",
+    )
+    .unwrap();
+    let update = session
+        .poll()
+        .unwrap()
+        .expect("config edit should be detected");
+    assert_eq!(
+        update.changed_files,
+        vec![dir.path().join("convex-doctor.toml")]
+    );
+    assert!(update
+        .resolved
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+}
+
+#[test]
+fn test_diagnostic_delta_ignores_moved_findings() {
+    let before = vec![diagnostic("a", 1), diagnostic("b", 2)];
+    let after = vec![diagnostic("a", 5), diagnostic("c", 6)];
+    let (new, resolved) = diagnostic_delta(&before, &after);
+    assert_eq!(new, vec![diagnostic("c", 6)]);
+    assert_eq!(resolved, vec![diagnostic("b", 2)]);
+
+    // A second identical finding is new even though one already existed.
+    let (new, resolved) =
+        diagnostic_delta(&after, &[after.clone(), vec![diagnostic("a", 9)]].concat());
+    assert_eq!(new, vec![diagnostic("a", 9)]);
+    assert!(resolved.is_empty());
+}
+
+#[test]
+fn test_watch_restores_functions_of_a_builder_that_was_briefly_broken() {
+    let dir = TempDir::new().unwrap();
+    let lib = dir.path().join("convex/lib");
+    std::fs::create_dir_all(&lib).unwrap();
+    let builder = lib.join("functions.ts");
+    let builder_source = r#"
+import { query } from "../_generated/server";
+import { customQuery, NoOp } from "convex-helpers/server/customFunctions";
+
+export const withUser = customQuery(query, NoOp);
+"#;
+    std::fs::write(&builder, builder_source).unwrap();
+    std::fs::write(
+        dir.path().join("convex/items.ts"),
+        r#"
+import { withUser } from "./lib/functions";
+
+export const list = withUser({
+  args: {},
+  handler: async (ctx) => null,
+});
+"#,
+    )
+    .unwrap();
+    let has_findings = |diagnostics: &[Diagnostic]| {
+        diagnostics
+            .iter()
+            .any(|d| d.rule == "security/missing-return-validators" && d.file.ends_with("items.ts"))
+    };
+
+    let mut session = WatchSession::start(dir.path(), &RunOptions::default()).unwrap();
+    assert!(has_findings(session.diagnostics()));
+
+    std::fs::write(
+        &builder,
+        builder_source.replace("withUser", "withUserRenamed"),
+    )
+    .unwrap();
+    let update = session.poll().unwrap().expect("rename should be detected");
+    assert!(has_findings(&update.resolved));
+
+    std::fs::write(&builder, builder_source).unwrap();
+    let update = session.poll().unwrap().expect("restore should be detected");
+    assert!(has_findings(&update.new));
+    assert!(has_findings(session.diagnostics()));
+}
+
+#[test]
+fn test_watch_reports_a_broken_config_once_per_edit() {
+    let dir = TempDir::new().unwrap();
+    let convex_dir = dir.path().join("convex");
+    std::fs::create_dir(&convex_dir).unwrap();
+    std::fs::write(convex_dir.join("messages.ts"), unbounded()).unwrap();
+    let mut session = WatchSession::start(dir.path(), &RunOptions::default()).unwrap();
+
+    let config = dir.path().join("convex-doctor.toml");
+    std::fs::write(&config, "[rules\n").unwrap();
+    assert!(session.poll().is_err());
+    assert!(session.poll().unwrap().is_none());
+    assert!(session
+        .diagnostics()
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+
+    std::fs::write(&config, "[rules]\n\"perf/unbounded-collect\" = \"off\"\n").unwrap();
+    let update = session.poll().unwrap().expect("fixed config should load");
+    assert!(update
+        .resolved
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+}
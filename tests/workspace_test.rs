@@ -0,0 +1,166 @@
+use std::path::Path;
+
+use convex_doctor::engine::RunOptions;
+use convex_doctor::workspace::{discover_projects, run_workspace};
+use tempfile::TempDir;
+
+const QUERY: &str = r#"
+import { query } from "./_generated/server";
+
+export const list = query({
+  handler: async (ctx) => {
+    return await ctx.db.query("messages").collect();
+  },
+});
+"#;
+
+fn write(root: &Path, file: &str, contents: &str) {
+    let path = root.join(file);
+    std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+    std::fs::write(path, contents).unwrap();
+}
+
+fn names(root: &Path, projects: &[std::path::PathBuf]) -> Vec<String> {
+    projects
+        .iter()
+        .map(|p| {
+            p.strip_prefix(root)
+                .unwrap()
+                .to_string_lossy()
+                .replace('\\', "/")
+        })
+        .collect()
+}
+
+#[test]
+fn test_discover_projects_from_package_json_workspaces() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "package.json",
+        r#"{"workspaces": ["apps/*", "!apps/legacy"]}"#,
+    );
+    write(dir.path(), "apps/web/convex/messages.ts", QUERY);
+    write(dir.path(), "apps/admin/convex/users.ts", QUERY);
+    write(dir.path(), "apps/legacy/convex/old.ts", QUERY);
+    write(dir.path(), "apps/docs/index.md", "# docs");
+    write(dir.path(), "tools/convex/stray.ts", QUERY);
+
+    let projects = discover_projects(dir.path());
+    assert_eq!(names(dir.path(), &projects), vec!["apps/admin", "apps/web"]);
+}
+
+#[test]
+fn test_discover_projects_from_pnpm_workspace_and_convex_json() {
+    let dir = TempDir::new().unwrap();
+    write(
+        dir.path(),
+        "pnpm-workspace.yaml",
+        "packages:\n  - 'backends/*' # Convex apps\n  - \"services/api\"\ncatalog:\n  - ignored\n",
+    );
+    write(dir.path(), "backends/chat/convex/messages.ts", QUERY);
+    write(
+        dir.path(),
+        "services/api/convex.json",
+        r#"{"functions": "src/convex/"}"#,
+    );
+    write(dir.path(), "services/api/src/convex/messages.ts", QUERY);
+
+    let projects = discover_projects(dir.path());
+    assert_eq!(
+        names(dir.path(), &projects),
+        vec!["backends/chat", "services/api"]
+    );
+}
+
+#[test]
+fn test_discover_projects_scans_tree_without_workspaces() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "convex/messages.ts", QUERY);
+    write(dir.path(), "apps/web/convex/messages.ts", QUERY);
+    write(dir.path(), "node_modules/pkg/convex/index.ts", QUERY);
+
+    let projects = discover_projects(dir.path());
+    assert_eq!(names(dir.path(), &projects), vec!["", "apps/web"]);
+}
+
+#[test]
+fn test_run_workspace_reports_each_project_and_aggregate() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "package.json", r#"{"workspaces": ["apps/*"]}"#);
+    write(dir.path(), "apps/web/convex/messages.ts", QUERY);
+    write(dir.path(), "apps/api/convex/messages.ts", QUERY);
+    write(
+        dir.path(),
+        "apps/api/convex-doctor.toml",
+        "[rules]\n\"perf/unbounded-collect\" = \"off\"\n\n[ci]\nfail_below = 100\n",
+    );
+
+    let workspace = run_workspace(dir.path(), &RunOptions::default()).unwrap();
+    let names: Vec<&str> = workspace.projects.iter().map(|p| p.name.as_str()).collect();
+    assert_eq!(names, vec!["apps/api", "apps/web"]);
+    assert_eq!(workspace.files_scanned, 2);
+
+    let api = &workspace.projects[0].result;
+    let web = &workspace.projects[1].result;
+    assert!(api
+        .diagnostics
+        .iter()
+        .all(|d| d.rule != "perf/unbounded-collect"));
+    assert!(web
+        .diagnostics
+        .iter()
+        .any(|d| d.rule == "perf/unbounded-collect"));
+
+    let expected = (api.score.value + web.score.value) as f64 / 2.0;
+    assert_eq!(workspace.score.value, expected.round() as u32);
+
+    let failing: Vec<&str> = workspace
+        .failing_projects()
+        .map(|p| p.name.as_str())
+        .collect();
+    assert_eq!(failing, vec!["apps/api"]);
+}
+
+#[test]
+fn test_run_workspace_without_projects_fails() {
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "package.json", r#"{"workspaces": ["apps/*"]}"#);
+    assert!(run_workspace(dir.path(), &RunOptions::default()).is_err());
+}
+
+#[test]
+fn test_reporters_without_workspace_output_report_each_project() {
+    use std::time::Duration;
+
+    use convex_doctor::diagnostic::Diagnostic;
+    use convex_doctor::reporter::Reporter;
+    use convex_doctor::scoring::ScoreResult;
+
+    struct NameReporter;
+    impl Reporter for NameReporter {
+        fn format(
+            &self,
+            diagnostics: &[Diagnostic],
+            _score: &ScoreResult,
+            project_name: &str,
+            _verbose: bool,
+            _files_scanned: usize,
+            _elapsed: Duration,
+        ) -> String {
+            format!("{project_name}: {}\n", diagnostics.len())
+        }
+    }
+
+    let dir = TempDir::new().unwrap();
+    write(dir.path(), "package.json", r#"{"workspaces": ["apps/*"]}"#);
+    write(dir.path(), "apps/web/convex/messages.ts", QUERY);
+    write(dir.path(), "apps/api/convex/messages.ts", QUERY);
+    let workspace = run_workspace(dir.path(), &RunOptions::default()).unwrap();
+
+    let output = NameReporter.format_workspace(&workspace, false, Duration::ZERO);
+    let lines: Vec<&str> = output.lines().collect();
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with("apps/api: "));
+    assert!(lines[1].starts_with("apps/web: "));
+}